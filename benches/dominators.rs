@@ -0,0 +1,88 @@
+#![feature(test)]
+
+extern crate petgraph;
+extern crate test;
+
+use test::Bencher;
+
+use petgraph::algo::dominators::{lengauer_tarjan, simple_fast};
+use petgraph::graph::node_index;
+use petgraph::prelude::*;
+
+/// A control-flow-like graph: a chain of `n` nodes where each node also jumps
+/// forward and backward to pseudo random nodes, which makes most of the loops
+/// irreducible.
+fn irreducible_cfg(n: usize, jumps: usize) -> DiGraph<(), ()> {
+    let mut g = DiGraph::with_capacity(n, n * (jumps + 1));
+    for _ in 0..n {
+        g.add_node(());
+    }
+    // xorshift, so that the graphs are the same on every run
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    };
+    for i in 1..n {
+        g.add_edge(node_index(i - 1), node_index(i), ());
+        for _ in 0..jumps {
+            let target = next() % n;
+            if target != 0 {
+                g.add_edge(node_index(i), node_index(target), ());
+            }
+        }
+    }
+    g
+}
+
+/// A deeply nested loop structure: a long chain where every node branches
+/// back to the start of the chain.
+fn nested_loops(n: usize) -> DiGraph<(), ()> {
+    let mut g = DiGraph::with_capacity(n, 2 * n);
+    for _ in 0..n {
+        g.add_node(());
+    }
+    for i in 1..n {
+        g.add_edge(node_index(i - 1), node_index(i), ());
+        g.add_edge(node_index(i), node_index(1), ());
+    }
+    g
+}
+
+#[bench]
+fn simple_fast_irreducible_1000(bench: &mut Bencher) {
+    let g = irreducible_cfg(1_000, 2);
+    bench.iter(|| simple_fast(&g, node_index(0)));
+}
+
+#[bench]
+fn lengauer_tarjan_irreducible_1000(bench: &mut Bencher) {
+    let g = irreducible_cfg(1_000, 2);
+    bench.iter(|| lengauer_tarjan(&g, node_index(0)));
+}
+
+#[bench]
+fn simple_fast_irreducible_10000(bench: &mut Bencher) {
+    let g = irreducible_cfg(10_000, 2);
+    bench.iter(|| simple_fast(&g, node_index(0)));
+}
+
+#[bench]
+fn lengauer_tarjan_irreducible_10000(bench: &mut Bencher) {
+    let g = irreducible_cfg(10_000, 2);
+    bench.iter(|| lengauer_tarjan(&g, node_index(0)));
+}
+
+#[bench]
+fn simple_fast_nested_loops_10000(bench: &mut Bencher) {
+    let g = nested_loops(10_000);
+    bench.iter(|| simple_fast(&g, node_index(0)));
+}
+
+#[bench]
+fn lengauer_tarjan_nested_loops_10000(bench: &mut Bencher) {
+    let g = nested_loops(10_000);
+    bench.iter(|| lengauer_tarjan(&g, node_index(0)));
+}
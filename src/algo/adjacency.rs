@@ -0,0 +1,86 @@
+//! Undirected adjacency lists for algorithms that ignore edge direction.
+
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// A graph as undirected adjacency lists of compact node numbers, without
+/// self loops or parallel edges.
+///
+/// Edges of directed graphs are followed in both directions.
+pub(crate) struct Adjacency<N> {
+    /// The nodes, numbered by their position.
+    pub nodes: Vec<N>,
+    /// The sorted neighbors of each node.
+    pub neighbors: Vec<Vec<usize>>,
+}
+
+impl<N> Adjacency<N> {
+    pub fn new<G>(graph: G) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + IntoNodeIdentifiers + NodeIndexable,
+        N: Copy,
+    {
+        let mut index = vec![usize::MAX; graph.node_bound()];
+        let mut nodes = Vec::new();
+        for n in graph.node_identifiers() {
+            index[graph.to_index(n)] = nodes.len();
+            nodes.push(n);
+        }
+        let mut neighbors = vec![Vec::new(); nodes.len()];
+        for (u, &n) in nodes.iter().enumerate() {
+            for m in graph.neighbors(n) {
+                let v = index[graph.to_index(m)];
+                if u != v {
+                    neighbors[u].push(v);
+                    neighbors[v].push(u);
+                }
+            }
+        }
+        for list in &mut neighbors {
+            list.sort();
+            list.dedup();
+        }
+        Adjacency { nodes, neighbors }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn degree(&self, v: usize) -> usize {
+        self.neighbors[v].len()
+    }
+
+    /// Return the nodes in the order they are removed by repeatedly removing
+    /// a node of smallest degree.
+    pub fn smallest_last(&self) -> Vec<usize> {
+        let length = self.len();
+        let mut degree: Vec<_> = (0..length).map(|v| self.degree(v)).collect();
+        let mut buckets = vec![Vec::new(); length];
+        for v in 0..length {
+            buckets[degree[v]].push(v);
+        }
+        let mut removed = vec![false; length];
+        let mut order = Vec::with_capacity(length);
+        let mut smallest: usize = 0;
+        while order.len() < length {
+            // A removal lowers degrees by at most one.
+            smallest = smallest.saturating_sub(1);
+            while buckets[smallest].is_empty() {
+                smallest += 1;
+            }
+            let v = buckets[smallest].pop().unwrap();
+            if removed[v] || degree[v] != smallest {
+                continue;
+            }
+            removed[v] = true;
+            order.push(v);
+            for &w in &self.neighbors[v] {
+                if !removed[w] {
+                    degree[w] -= 1;
+                    buckets[degree[w]].push(w);
+                }
+            }
+        }
+        order
+    }
+}
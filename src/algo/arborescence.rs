@@ -0,0 +1,357 @@
+//! Optimum branchings of directed graphs (Chu–Liu/Edmonds).
+
+use std::ops::Sub;
+
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+use super::Measure;
+
+/// An algorithm error: a node is not reachable from the root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unreachable<N>(N);
+
+impl<N> Unreachable<N> {
+    /// Return a node id that can not be reached from the root
+    pub fn node_id(&self) -> N
+    where
+        N: Copy,
+    {
+        self.0
+    }
+}
+
+/// \[Generic\] Compute a *minimum spanning arborescence* of a directed graph
+/// rooted at `root`, using the [Chu–Liu/Edmonds algorithm][1].
+///
+/// A spanning arborescence is a set of edges such that every node other than
+/// `root` has exactly one incoming edge, and there is a directed path from `root`
+/// to every node. It is the directed analogue of a spanning tree.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Costs may be negative. Edges into `root` and self loops are never chosen.
+///
+/// Returns the chosen edges, or an `Unreachable` error if some node can not be
+/// reached from `root`.
+///
+/// Runtime is **O(|V| |E|)**.
+///
+/// **Panics** if `root` is not a node of the graph.
+///
+/// [1]: https://en.wikipedia.org/wiki/Edmonds%27_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::min_spanning_arborescence;
+/// use petgraph::visit::EdgeRef;
+///
+/// let mut g = Graph::new();
+/// let r = g.add_node("r");
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.extend_with_edges(&[
+///     (r, a, 10),
+///     (r, b, 2),
+///     (b, a, 1),
+///     (a, c, 8),
+///     (c, b, 4),
+///     (r, c, 10),
+/// ]);
+///
+/// let edges = min_spanning_arborescence(&g, r, |e| *e.weight()).unwrap();
+/// let total: i32 = edges.iter().map(|e| *e.weight()).sum();
+/// assert_eq!(edges.len(), 3);
+/// assert_eq!(total, 2 + 1 + 8);
+///
+/// // Nothing reaches `d`.
+/// let d = g.add_node("d");
+/// let err = min_spanning_arborescence(&g, r, |e| *e.weight()).unwrap_err();
+/// assert_eq!(err.node_id(), d);
+/// ```
+pub fn min_spanning_arborescence<G, F, K>(
+    graph: G,
+    root: G::NodeId,
+    mut edge_cost: F,
+) -> Result<Vec<G::EdgeRef>, Unreachable<G::NodeId>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    let Indexed { nodes, refs, edges } = branching_edges(graph, &mut edge_cost);
+    let root_ix = nodes
+        .iter()
+        .position(|&n| n == root)
+        .expect("root is not a node of the graph");
+
+    if let Some(v) = first_unreachable(nodes.len(), root_ix, &edges) {
+        return Err(Unreachable(nodes[v]));
+    }
+    let chosen = optimum_arborescence(nodes.len(), root_ix, edges, |a, b| a < b);
+    Ok(chosen.into_iter().map(|i| refs[i]).collect())
+}
+
+/// \[Generic\] Compute a *maximum branching* of a directed graph, using the
+/// [Chu–Liu/Edmonds algorithm][1].
+///
+/// A branching is a set of edges that forms a forest of arborescences: no node
+/// has more than one incoming edge, and the edges contain no cycle. The
+/// maximum branching is the branching with the largest total cost; edges with
+/// a negative cost are never part of it.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+///
+/// Returns the chosen edges. Runtime is **O(|V| |E|)**.
+///
+/// [1]: https://en.wikipedia.org/wiki/Edmonds%27_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::max_branching;
+/// use petgraph::visit::EdgeRef;
+///
+/// let mut g = Graph::<(), i32>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// g.extend_with_edges(&[(a, b, 5), (b, c, 3), (c, a, 4), (c, b, -1)]);
+///
+/// let edges = max_branching(&g, |e| *e.weight());
+/// let total: i32 = edges.iter().map(|e| *e.weight()).sum();
+/// assert_eq!(total, 9);
+/// ```
+pub fn max_branching<G, F, K>(graph: G, mut edge_cost: F) -> Vec<G::EdgeRef>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    let Indexed {
+        nodes,
+        refs,
+        mut edges,
+    } = branching_edges(graph, &mut edge_cost);
+    let node_count = nodes.len();
+
+    // A virtual root with zero cost edges to every node turns the maximum
+    // branching into a maximum spanning arborescence; the virtual edges mark
+    // the roots of the branching.
+    let virtual_root = node_count;
+    for v in 0..node_count {
+        edges.push(BranchingEdge {
+            source: virtual_root,
+            target: v,
+            cost: K::default(),
+            origin: refs.len() + v,
+        });
+    }
+    let chosen = optimum_arborescence(node_count + 1, virtual_root, edges, |a, b| a > b);
+    chosen
+        .into_iter()
+        .filter(|&i| i < refs.len())
+        .map(|i| refs[i])
+        .collect()
+}
+
+/// Collect the nodes and edges of `graph`, with nodes renumbered compactly.
+fn branching_edges<G, F, K>(graph: G, edge_cost: &mut F) -> Indexed<G::NodeId, G::EdgeRef, K>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+{
+    let mut index = vec![None; graph.node_bound()];
+    let mut nodes = Vec::new();
+    for n in graph.node_identifiers() {
+        index[graph.to_index(n)] = Some(nodes.len());
+        nodes.push(n);
+    }
+
+    let mut refs = Vec::new();
+    let mut edges = Vec::new();
+    for edge in graph.edge_references() {
+        edges.push(BranchingEdge {
+            source: index[graph.to_index(edge.source())].unwrap(),
+            target: index[graph.to_index(edge.target())].unwrap(),
+            cost: edge_cost(edge),
+            origin: refs.len(),
+        });
+        refs.push(edge);
+    }
+    Indexed { nodes, refs, edges }
+}
+
+struct Indexed<N, R, K> {
+    nodes: Vec<N>,
+    refs: Vec<R>,
+    edges: Vec<BranchingEdge<K>>,
+}
+
+#[derive(Clone, Debug)]
+struct BranchingEdge<K> {
+    source: usize,
+    target: usize,
+    cost: K,
+    /// Index of this edge in the previous contraction level.
+    origin: usize,
+}
+
+/// Contracted cycles of one level of the algorithm.
+struct Level<K> {
+    edges: Vec<BranchingEdge<K>>,
+    /// The chosen incoming edge of each node.
+    best_in: Vec<usize>,
+    /// The cycle each node belongs to, if any.
+    cycle_of: Vec<Option<usize>>,
+    cycles: Vec<Vec<usize>>,
+}
+
+fn first_unreachable<K>(
+    node_count: usize,
+    root: usize,
+    edges: &[BranchingEdge<K>],
+) -> Option<usize> {
+    let mut adjacency = vec![Vec::new(); node_count];
+    for e in edges {
+        adjacency[e.source].push(e.target);
+    }
+    let mut reached = vec![false; node_count];
+    reached[root] = true;
+    let mut stack = vec![root];
+    while let Some(u) = stack.pop() {
+        for &v in &adjacency[u] {
+            if !reached[v] {
+                reached[v] = true;
+                stack.push(v);
+            }
+        }
+    }
+    reached.iter().position(|&r| !r)
+}
+
+/// Return the indices of the edges of an optimum spanning arborescence, where
+/// `better(a, b)` is true if cost `a` is preferred over cost `b`.
+///
+/// Every node must be reachable from `root`.
+fn optimum_arborescence<K, B>(
+    mut node_count: usize,
+    mut root: usize,
+    mut edges: Vec<BranchingEdge<K>>,
+    better: B,
+) -> Vec<usize>
+where
+    K: Copy + Sub<K, Output = K>,
+    B: Fn(&K, &K) -> bool,
+{
+    const NONE: usize = usize::MAX;
+    let mut levels: Vec<Level<K>> = Vec::new();
+
+    // Contract cycles of cheapest incoming edges until there are none left.
+    let mut chosen = loop {
+        let mut best_in = vec![NONE; node_count];
+        for (i, e) in edges.iter().enumerate() {
+            if e.source == e.target || e.target == root {
+                continue;
+            }
+            let best = best_in[e.target];
+            if best == NONE || better(&e.cost, &edges[best].cost) {
+                best_in[e.target] = i;
+            }
+        }
+
+        let mut cycle_of = vec![None; node_count];
+        let mut cycles = Vec::new();
+        let mut stamp = vec![NONE; node_count];
+        for start in 0..node_count {
+            let mut v = start;
+            while v != root && stamp[v] == NONE {
+                stamp[v] = start;
+                v = edges[best_in[v]].source;
+            }
+            if v != root && stamp[v] == start && cycle_of[v].is_none() {
+                let mut cycle = vec![v];
+                cycle_of[v] = Some(cycles.len());
+                let mut u = edges[best_in[v]].source;
+                while u != v {
+                    cycle_of[u] = Some(cycles.len());
+                    cycle.push(u);
+                    u = edges[best_in[u]].source;
+                }
+                cycles.push(cycle);
+            }
+        }
+
+        if cycles.is_empty() {
+            break (0..node_count)
+                .filter(|&v| v != root)
+                .map(|v| best_in[v])
+                .collect::<Vec<_>>();
+        }
+
+        // Every cycle becomes a single node; the other nodes are renumbered.
+        let mut component = vec![NONE; node_count];
+        let mut next = cycles.len();
+        for v in 0..node_count {
+            match cycle_of[v] {
+                Some(c) => component[v] = c,
+                None => {
+                    component[v] = next;
+                    next += 1;
+                }
+            }
+        }
+
+        let mut contracted = Vec::new();
+        for (i, e) in edges.iter().enumerate() {
+            let (u, v) = (component[e.source], component[e.target]);
+            if u == v {
+                continue;
+            }
+            let cost = match cycle_of[e.target] {
+                Some(_) => e.cost - edges[best_in[e.target]].cost,
+                None => e.cost,
+            };
+            contracted.push(BranchingEdge {
+                source: u,
+                target: v,
+                cost,
+                origin: i,
+            });
+        }
+
+        root = component[root];
+        node_count = next;
+        levels.push(Level {
+            edges: ::std::mem::replace(&mut edges, contracted),
+            best_in,
+            cycle_of,
+            cycles,
+        });
+    };
+
+    // Expand the contracted cycles again: each cycle keeps all of its edges
+    // except the one into the node where the chosen edge enters the cycle.
+    while let Some(level) = levels.pop() {
+        let mut entered = vec![NONE; level.cycles.len()];
+        let mut expanded = Vec::with_capacity(chosen.len());
+        for &i in &chosen {
+            let origin = edges[i].origin;
+            if let Some(c) = level.cycle_of[level.edges[origin].target] {
+                entered[c] = level.edges[origin].target;
+            }
+            expanded.push(origin);
+        }
+        for (c, cycle) in level.cycles.iter().enumerate() {
+            for &v in cycle {
+                if v != entered[c] {
+                    expanded.push(level.best_in[v]);
+                }
+            }
+        }
+        chosen = expanded;
+        edges = level.edges;
+    }
+    chosen.sort_by_key(|&i| edges[i].target);
+    chosen.into_iter().map(|i| edges[i].origin).collect()
+}
@@ -0,0 +1,67 @@
+//! Weighted arc lists for algorithms that follow the edges of a graph.
+
+use crate::visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// A graph as lists of outgoing arcs between compact node numbers, with a
+/// cost for each edge.
+///
+/// Undirected edges are arcs in both directions, and self loops are a single
+/// arc.
+pub(crate) struct Arcs<N, E, K> {
+    /// The nodes, numbered by their position.
+    pub nodes: Vec<N>,
+    /// The number of each node index, or `usize::MAX` if it is not in use.
+    pub index: Vec<usize>,
+    /// The edges, numbered by their position.
+    pub edges: Vec<E>,
+    /// The cost of each edge.
+    pub cost: Vec<K>,
+    /// The outgoing arcs of each node, as `(target, edge)`.
+    pub out: Vec<Vec<(usize, usize)>>,
+    pub directed: bool,
+}
+
+impl<N, E, K> Arcs<N, E, K> {
+    pub fn new<G, F>(graph: G, mut edge_cost: F) -> Self
+    where
+        G: IntoEdgeReferences<NodeId = N, EdgeId = E>
+            + IntoNodeIdentifiers
+            + NodeIndexable
+            + GraphProp,
+        F: FnMut(G::EdgeRef) -> K,
+        N: Copy,
+    {
+        let mut index = vec![usize::MAX; graph.node_bound()];
+        let mut nodes = Vec::new();
+        for n in graph.node_identifiers() {
+            index[graph.to_index(n)] = nodes.len();
+            nodes.push(n);
+        }
+        let directed = graph.is_directed();
+        let mut out = vec![Vec::new(); nodes.len()];
+        let mut edges = Vec::new();
+        let mut cost = Vec::new();
+        for edge in graph.edge_references() {
+            let a = index[graph.to_index(edge.source())];
+            let b = index[graph.to_index(edge.target())];
+            out[a].push((b, edges.len()));
+            if !directed && a != b {
+                out[b].push((a, edges.len()));
+            }
+            edges.push(edge.id());
+            cost.push(edge_cost(edge));
+        }
+        Arcs {
+            nodes,
+            index,
+            edges,
+            cost,
+            out,
+            directed,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+}
@@ -0,0 +1,426 @@
+//! Betweenness centrality (Brandes).
+
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::algo::Measure;
+use crate::scored::MinScored;
+use crate::visit::{GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+use super::arcs::Arcs;
+
+/// \[Generic\] Compute the *betweenness centrality* of every node.
+///
+/// The betweenness of a node is the sum, over all pairs of other nodes
+/// **s** and **t**, of the fraction of shortest paths from **s** to **t**
+/// that pass through the node. Paths are counted by their number of edges;
+/// see [`weighted_betweenness_centrality`](fn.weighted_betweenness_centrality.html)
+/// for weighted edges. Parallel edges are distinct paths, and self loops are
+/// ignored. In an undirected graph, every pair of nodes is counted once.
+///
+/// If `normalized` is `true`, the scores are divided by the number of pairs
+/// of other nodes, **(n - 1)(n - 2)** for directed graphs and half as much
+/// for undirected graphs.
+///
+/// If `sources` is not `None`, only the shortest paths starting at the given
+/// nodes are counted, and the scores are multiplied by **n / k**, where **k**
+/// is the number of sources. With a random sample of sources, this
+/// approximates the betweenness of large graphs.
+///
+/// This uses the algorithm of [Brandes][1]. Runtime is **O(k (|V| + |E|))**,
+/// or **O(|V| (|V| + |E|))** without `sources`.
+///
+/// [1]: https://doi.org/10.1080/0022250X.2001.9990249
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::betweenness_centrality;
+///
+/// // A path: every shortest path between the ends passes through the middle.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// let scores = betweenness_centrality(&g, false, None);
+/// assert_eq!(scores[&0.into()], 0.);
+/// assert_eq!(scores[&1.into()], 1.);
+/// ```
+pub fn betweenness_centrality<G>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+) -> HashMap<G::NodeId, f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let arcs = Arcs::new(graph, |_| 0);
+    let scores = arcs.brandes(graph, sources, false);
+    arcs.node_scores(scores, normalized)
+}
+
+/// \[Generic\] Compute the *betweenness centrality* of every node, with
+/// weighted edges.
+///
+/// This is [`betweenness_centrality`](fn.betweenness_centrality.html), except
+/// that paths are measured by the sum of their edge costs, given by
+/// `edge_cost`. Edge costs must be non-negative, and zero-cost edges must
+/// not form a cycle, so in an undirected graph they must be positive.
+///
+/// Runtime is **O(k (|E| + |V|) log |V|)**, or
+/// **O(|V| (|E| + |V|) log |V|)** without `sources`.
+///
+/// **Panics** if a cycle of zero-cost edges is reachable from a source.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::weighted_betweenness_centrality;
+/// use petgraph::visit::EdgeRef;
+///
+/// // A square, where the path through 1 is shorter than the path through 3.
+/// let g = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 2), (3, 0, 2)]);
+///
+/// let scores = weighted_betweenness_centrality(&g, false, None, |e| *e.weight());
+/// assert_eq!(scores[&1.into()], 1.);
+/// assert_eq!(scores[&3.into()], 0.);
+/// ```
+pub fn weighted_betweenness_centrality<G, F, K>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+    edge_cost: F,
+) -> HashMap<G::NodeId, f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let arcs = Arcs::new(graph, edge_cost);
+    let scores = arcs.brandes(graph, sources, true);
+    arcs.node_scores(scores, normalized)
+}
+
+/// \[Generic\] Compute the *betweenness centrality* of every edge.
+///
+/// The betweenness of an edge is the sum, over all pairs of nodes **s** and
+/// **t**, of the fraction of shortest paths from **s** to **t** that pass
+/// through the edge. See [`betweenness_centrality`](fn.betweenness_centrality.html)
+/// for how paths and `sources` are counted. If `normalized` is `true`, the
+/// scores are divided by the number of pairs of nodes, **n (n - 1)** for
+/// directed graphs and half as much for undirected graphs.
+///
+/// The scores are keyed by the ids of the edges produced by
+/// `edge_references`. Self loops have a score of zero.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::edge_betweenness_centrality;
+///
+/// // A path: the pairs (0, 1), (0, 2) pass through the first edge.
+/// let mut g = UnGraph::<(), ()>::new_undirected();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// let ab = g.add_edge(a, b, ());
+/// let bc = g.add_edge(b, c, ());
+///
+/// let scores = edge_betweenness_centrality(&g, false, None);
+/// assert_eq!(scores[&ab], 2.);
+/// assert_eq!(scores[&bc], 2.);
+/// ```
+pub fn edge_betweenness_centrality<G>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+) -> HashMap<G::EdgeId, f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::EdgeId: Eq + Hash,
+{
+    let arcs = Arcs::new(graph, |_| 0);
+    let scores = arcs.brandes(graph, sources, false);
+    arcs.edge_scores(scores, normalized)
+}
+
+/// \[Generic\] Compute the *betweenness centrality* of every edge, with
+/// weighted edges.
+///
+/// This is [`edge_betweenness_centrality`](fn.edge_betweenness_centrality.html),
+/// except that paths are measured by the sum of their edge costs, given by
+/// `edge_cost`. Edge costs must be non-negative, and zero-cost edges must
+/// not form a cycle, so in an undirected graph they must be positive.
+///
+/// **Panics** if a cycle of zero-cost edges is reachable from a source.
+pub fn weighted_edge_betweenness_centrality<G, F, K>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+    edge_cost: F,
+) -> HashMap<G::EdgeId, f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let arcs = Arcs::new(graph, edge_cost);
+    let scores = arcs.brandes(graph, sources, true);
+    arcs.edge_scores(scores, normalized)
+}
+
+/// Unscaled betweenness, by compact node index and edge.
+struct Scores {
+    nodes: Vec<f64>,
+    edges: Vec<f64>,
+    /// The number of sources.
+    sources: usize,
+}
+
+/// The shortest path DAG of a single source.
+struct ShortestPaths {
+    /// The nodes in order of nondecreasing distance.
+    order: Vec<usize>,
+    /// The number of shortest paths to each node.
+    sigma: Vec<f64>,
+    /// The last arcs of the shortest paths to each node, as `(source, edge)`.
+    predecessors: Vec<Vec<(usize, usize)>>,
+}
+
+impl<N, E, K> Arcs<N, E, K>
+where
+    N: Copy,
+    K: Measure + Copy,
+{
+    fn brandes<G>(&self, graph: G, sources: Option<&[N]>, weighted: bool) -> Scores
+    where
+        G: NodeIndexable<NodeId = N>,
+    {
+        let sources: Vec<usize> = match sources {
+            Some(sources) => sources
+                .iter()
+                .map(|&n| self.index[graph.to_index(n)])
+                .collect(),
+            None => (0..self.nodes.len()).collect(),
+        };
+
+        let mut nodes = vec![0.; self.nodes.len()];
+        let mut edges = vec![0.; self.edges.len()];
+        let mut delta = vec![0.; self.nodes.len()];
+        for &s in &sources {
+            let paths = if weighted {
+                self.dijkstra(s)
+            } else {
+                self.breadth_first(s)
+            };
+            // Accumulate the dependencies in order of nonincreasing distance.
+            for &w in paths.order.iter().rev() {
+                for &(v, e) in &paths.predecessors[w] {
+                    let c = paths.sigma[v] / paths.sigma[w] * (1. + delta[w]);
+                    edges[e] += c;
+                    delta[v] += c;
+                }
+                if w != s {
+                    nodes[w] += delta[w];
+                }
+            }
+            for &w in &paths.order {
+                delta[w] = 0.;
+            }
+        }
+        Scores {
+            nodes,
+            edges,
+            sources: sources.len(),
+        }
+    }
+
+    fn breadth_first(&self, s: usize) -> ShortestPaths {
+        let length = self.nodes.len();
+        let mut order = Vec::new();
+        let mut sigma = vec![0.; length];
+        let mut predecessors = vec![Vec::new(); length];
+        let mut distance = vec![usize::MAX; length];
+        let mut queue = VecDeque::new();
+        sigma[s] = 1.;
+        distance[s] = 0;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &(w, e) in &self.out[v] {
+                if distance[w] == usize::MAX {
+                    distance[w] = distance[v] + 1;
+                    queue.push_back(w);
+                }
+                if distance[w] == distance[v] + 1 {
+                    sigma[w] += sigma[v];
+                    predecessors[w].push((v, e));
+                }
+            }
+        }
+        ShortestPaths {
+            order,
+            sigma,
+            predecessors,
+        }
+    }
+
+    fn dijkstra(&self, s: usize) -> ShortestPaths {
+        let length = self.nodes.len();
+        let mut order = Vec::new();
+        let mut sigma = vec![0.; length];
+        let mut predecessors = vec![Vec::new(); length];
+        let mut distance: Vec<Option<K>> = vec![None; length];
+        let mut finished = vec![false; length];
+        let mut heap = BinaryHeap::new();
+        // The nodes at the distance of the current layer, and the number of
+        // their unprocessed predecessors within the layer.
+        let mut layer = Vec::new();
+        let mut pending = vec![0; length];
+        let mut ready = Vec::new();
+        distance[s] = Some(K::default());
+        sigma[s] = 1.;
+        heap.push(MinScored(K::default(), s));
+        while let Some(MinScored(d, v)) = heap.pop() {
+            if finished[v] {
+                continue;
+            }
+            // Finish all nodes at distance `d` at once: those on the heap,
+            // and those they reach by zero-cost arcs.
+            finished[v] = true;
+            layer.push(v);
+            while let Some(top) = heap.peek() {
+                if top.0 != d {
+                    break;
+                }
+                let MinScored(_, u) = heap.pop().unwrap();
+                if !finished[u] {
+                    finished[u] = true;
+                    layer.push(u);
+                }
+            }
+            let mut i = 0;
+            while i < layer.len() {
+                let u = layer[i];
+                i += 1;
+                for &(w, e) in &self.out[u] {
+                    if w == u || d + self.cost[e] != d {
+                        continue;
+                    }
+                    if !finished[w] {
+                        // Any paths found so far are longer.
+                        finished[w] = true;
+                        distance[w] = Some(d);
+                        sigma[w] = 0.;
+                        predecessors[w].clear();
+                        layer.push(w);
+                    }
+                    if distance[w] == Some(d) {
+                        pending[w] += 1;
+                    }
+                }
+            }
+
+            // Count the paths within the layer in topological order of the
+            // zero-cost arcs, and relax the arcs that leave it.
+            ready.extend(layer.iter().cloned().filter(|&u| pending[u] == 0));
+            let mut processed = 0;
+            while let Some(u) = ready.pop() {
+                processed += 1;
+                order.push(u);
+                for &(w, e) in &self.out[u] {
+                    let next = d + self.cost[e];
+                    if next == d {
+                        if w != u && distance[w] == Some(d) {
+                            sigma[w] += sigma[u];
+                            predecessors[w].push((u, e));
+                            pending[w] -= 1;
+                            if pending[w] == 0 {
+                                ready.push(w);
+                            }
+                        }
+                        continue;
+                    }
+                    if finished[w] {
+                        continue;
+                    }
+                    let shorter = match distance[w] {
+                        None => true,
+                        Some(current) => next < current,
+                    };
+                    if shorter {
+                        distance[w] = Some(next);
+                        sigma[w] = sigma[u];
+                        predecessors[w].clear();
+                        predecessors[w].push((u, e));
+                        heap.push(MinScored(next, w));
+                    } else if distance[w] == Some(next) {
+                        sigma[w] += sigma[u];
+                        predecessors[w].push((u, e));
+                    }
+                }
+            }
+            assert!(
+                processed == layer.len(),
+                "betweenness: a cycle of zero-cost edges is reachable from a source"
+            );
+            layer.clear();
+        }
+        ShortestPaths {
+            order,
+            sigma,
+            predecessors,
+        }
+    }
+
+    /// Return the node scores, scaled.
+    fn node_scores(&self, scores: Scores, normalized: bool) -> HashMap<N, f64>
+    where
+        N: Eq + Hash,
+    {
+        let n = self.nodes.len() as f64;
+        let pairs = if normalized && n > 2. {
+            (n - 1.) * (n - 2.)
+        } else {
+            1.
+        };
+        let scale = self.scale(pairs, normalized, scores.sources);
+        self.nodes
+            .iter()
+            .zip(scores.nodes)
+            .map(|(&node, score)| (node, score * scale))
+            .collect()
+    }
+
+    /// Return the edge scores, scaled.
+    fn edge_scores(&self, scores: Scores, normalized: bool) -> HashMap<E, f64>
+    where
+        E: Copy + Eq + Hash,
+    {
+        let n = self.nodes.len() as f64;
+        let pairs = if normalized && n > 1. {
+            n * (n - 1.)
+        } else {
+            1.
+        };
+        let scale = self.scale(pairs, normalized, scores.sources);
+        self.edges
+            .iter()
+            .zip(scores.edges)
+            .map(|(&edge, score)| (edge, score * scale))
+            .collect()
+    }
+
+    /// Return the factor for the scores of the paths from `sources` sources,
+    /// divided by `pairs`.
+    fn scale(&self, pairs: f64, normalized: bool, sources: usize) -> f64 {
+        // Undirected paths are found from both ends, and normalized scores
+        // divide by the number of ordered pairs.
+        let mut scale = if self.directed || normalized { 1. } else { 0.5 } / pairs;
+        if sources > 0 {
+            scale *= self.nodes.len() as f64 / sources as f64;
+        }
+        scale
+    }
+}
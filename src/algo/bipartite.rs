@@ -0,0 +1,122 @@
+//! Bipartite partitions with odd cycle certificates.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, VisitMap, Visitable};
+
+/// An algorithm error: the graph is not bipartite, as shown by a cycle of odd
+/// length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OddCycle<N>(Vec<N>);
+
+impl<N> OddCycle<N> {
+    /// Return the nodes of the cycle, in order: each node is adjacent to the
+    /// next one, and the last node is adjacent to the first.
+    ///
+    /// A self loop is a cycle of length one.
+    pub fn nodes(&self) -> &[N] {
+        &self.0
+    }
+
+    /// Consume the error and return the nodes of the cycle.
+    pub fn into_nodes(self) -> Vec<N> {
+        self.0
+    }
+}
+
+/// \[Generic\] Partition the nodes of a bipartite graph into two sets, such
+/// that every edge connects a node of one set to a node of the other.
+///
+/// The graph is treated as undirected, and all of its components are
+/// partitioned. In each component, the first node produced by
+/// `node_identifiers` goes into the left set.
+///
+/// Returns the `(left, right)` sets, or an `OddCycle` error if the graph is
+/// not bipartite.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::bipartite_partition;
+///
+/// // A square and a single edge.
+/// let mut g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)]);
+/// let (left, right) = bipartite_partition(&g).unwrap();
+/// assert_eq!(left, vec![0.into(), 2.into(), 4.into()]);
+/// assert_eq!(right, vec![1.into(), 3.into(), 5.into()]);
+///
+/// // A diagonal closes two triangles.
+/// g.add_edge(0.into(), 2.into(), ());
+/// let cycle = bipartite_partition(&g).unwrap_err();
+/// assert_eq!(cycle.nodes().len(), 3);
+/// ```
+pub fn bipartite_partition<G, N>(graph: G) -> Result<(Vec<N>, Vec<N>), OddCycle<N>>
+where
+    G: IntoNeighbors<NodeId = N> + IntoNodeIdentifiers + Visitable,
+    N: Copy + Eq + Hash,
+{
+    // Edges are followed in both directions.
+    let mut incoming: HashMap<N, Vec<N>> = HashMap::new();
+    for u in graph.node_identifiers() {
+        for v in graph.neighbors(u) {
+            incoming.entry(v).or_default().push(u);
+        }
+    }
+
+    let mut discovered = graph.visit_map();
+    let mut is_left = graph.visit_map();
+    let mut parent = HashMap::new();
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    let mut queue = VecDeque::new();
+    for start in graph.node_identifiers() {
+        if !discovered.visit(start) {
+            continue;
+        }
+        is_left.visit(start);
+        queue.push_back(start);
+        while let Some(u) = queue.pop_front() {
+            let u_is_left = is_left.is_visited(&u);
+            if u_is_left {
+                left.push(u);
+            } else {
+                right.push(u);
+            }
+            let reverse = incoming.get(&u).map_or(&[][..], |preds| &preds[..]);
+            for v in graph.neighbors(u).chain(reverse.iter().cloned()) {
+                if discovered.visit(v) {
+                    if !u_is_left {
+                        is_left.visit(v);
+                    }
+                    parent.insert(v, u);
+                    queue.push_back(v);
+                } else if is_left.is_visited(&v) == u_is_left {
+                    return Err(OddCycle(odd_cycle(&parent, u, v)));
+                }
+            }
+        }
+    }
+    Ok((left, right))
+}
+
+/// Return the cycle closed by the edge `(u, v)` in the breadth first search
+/// tree given by `parent`, where `u` and `v` have the same depth.
+fn odd_cycle<N>(parent: &HashMap<N, N>, mut u: N, mut v: N) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+{
+    let mut path_from_u = Vec::new();
+    let mut path_from_v = Vec::new();
+    while u != v {
+        path_from_u.push(u);
+        path_from_v.push(v);
+        u = parent[&u];
+        v = parent[&v];
+    }
+    path_from_u.push(u);
+    path_from_u.extend(path_from_v.into_iter().rev());
+    path_from_u
+}
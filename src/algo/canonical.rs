@@ -0,0 +1,463 @@
+//! Weisfeiler–Lehman graph hashing and canonical labeling.
+
+use std::hash::{Hash, Hasher};
+
+use crate::isomorphism::ColoredAutomorphisms;
+use crate::unionfind::UnionFind;
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, IntoNodeReferences, NodeIndexable,
+    NodeRef,
+};
+
+/// A 64-bit FNV-1a hasher that hashes integers as little-endian bytes, and
+/// `usize` and `isize` as 64-bit integers, so that its hashes are the same on
+/// every platform and in every run of the program.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i])
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8)
+    }
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16)
+    }
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32)
+    }
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128)
+    }
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64)
+    }
+}
+
+fn stable_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = StableHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Return the number of each node of `graph`, in the order of their indices,
+/// by their index.
+fn numbering<G>(graph: G, nodes: &[G::NodeId]) -> Vec<usize>
+where
+    G: NodeIndexable,
+{
+    let mut index = vec![0; graph.node_bound()];
+    for (i, &n) in nodes.iter().enumerate() {
+        index[graph.to_index(n)] = i;
+    }
+    index
+}
+
+/// \[Generic\] Compute the Weisfeiler–Lehman subtree hash of the graph.
+///
+/// Each node starts with the hash of its label, given by `node_label`. At
+/// each of the `iterations`, the label of each node is replaced by the hash
+/// of its label and of the multiset of the labels of its neighbors, combined
+/// with the labels of the edges to them, given by `edge_label`. In directed
+/// graphs, successors and predecessors are kept apart. The hash of the graph
+/// is the hash of the multisets of node labels at every iteration.
+///
+/// Isomorphic graphs have the same hash, while graphs with different hashes
+/// are not isomorphic; graphs that are not isomorphic usually have different
+/// hashes, but not always (for example, regular graphs of the same size and
+/// degree are not distinguished). Pass `|_| ()` to ignore the node or edge
+/// weights.
+///
+/// The hash does not depend on the order of the nodes and edges, and is the
+/// same on every platform and in every run of the program, so that it can be
+/// stored, as long as the labels hash their contents (like integers, strings
+/// and tuples of them do). See [Weisfeiler–Lehman graph kernels][1].
+///
+/// Runtime is **O(k (|V| + |E|) log |V|)**, where **k** is the number of
+/// iterations.
+///
+/// [1]: https://www.jmlr.org/papers/volume12/shervashidze11a/shervashidze11a.pdf
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::wl_hash;
+///
+/// // Two labelings of a path a - b - a.
+/// let g0 = UnGraph::<char, ()>::from_edges(&[(0, 1), (1, 2)]).map(|i, _| ['a', 'b', 'a'][i.index()], |_, _| ());
+/// let g1 = UnGraph::<char, ()>::from_edges(&[(0, 2), (2, 1)]).map(|i, _| ['a', 'a', 'b'][i.index()], |_, _| ());
+/// let g2 = UnGraph::<char, ()>::from_edges(&[(0, 1), (1, 2)]).map(|i, _| ['b', 'a', 'a'][i.index()], |_, _| ());
+///
+/// let hash = |g: &UnGraph<char, ()>| wl_hash(g, 3, |n| *n.1, |_| ());
+/// assert_eq!(hash(&g0), hash(&g1));
+/// assert_ne!(hash(&g0), hash(&g2));
+/// ```
+pub fn wl_hash<G, F, H, L, M>(
+    graph: G,
+    iterations: usize,
+    mut node_label: F,
+    mut edge_label: H,
+) -> u64
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    F: FnMut(G::NodeRef) -> L,
+    H: FnMut(G::EdgeRef) -> M,
+    L: Hash,
+    M: Hash,
+{
+    let directed = graph.is_directed();
+    let nodes: Vec<_> = graph.node_references().collect();
+    let ids: Vec<_> = nodes.iter().map(|n| n.id()).collect();
+    let index = numbering(graph, &ids);
+    let mut labels: Vec<u64> = nodes
+        .into_iter()
+        .map(|n| stable_hash(&node_label(n)))
+        .collect();
+
+    let n = labels.len();
+    let mut succ = vec![Vec::new(); n];
+    let mut pred = vec![Vec::new(); n];
+    for edge in graph.edge_references() {
+        let a = index[graph.to_index(edge.source())];
+        let b = index[graph.to_index(edge.target())];
+        let label = stable_hash(&edge_label(edge));
+        succ[a].push((b, label));
+        if directed {
+            pred[b].push((a, label));
+        } else if a != b {
+            succ[b].push((a, label));
+        }
+    }
+
+    let mut graph_hasher = StableHasher::new();
+    directed.hash(&mut graph_hasher);
+    let mut sorted = labels.clone();
+    sorted.sort_unstable();
+    sorted.hash(&mut graph_hasher);
+    for _ in 0..iterations {
+        let neighbor_labels = |neighbors: &[(usize, u64)]| {
+            let mut l: Vec<_> = neighbors
+                .iter()
+                .map(|&(j, edge)| (edge, labels[j]))
+                .collect();
+            l.sort_unstable();
+            l
+        };
+        let next: Vec<u64> = (0..n)
+            .map(|i| {
+                stable_hash(&(
+                    labels[i],
+                    neighbor_labels(&succ[i]),
+                    neighbor_labels(&pred[i]),
+                ))
+            })
+            .collect();
+        labels = next;
+        sorted.clone_from(&labels);
+        sorted.sort_unstable();
+        sorted.hash(&mut graph_hasher);
+    }
+    graph_hasher.finish()
+}
+
+/// \[Generic\] Compute a canonical labeling of the graph.
+///
+/// Return the nodes of the graph in canonical order, and a hash of the
+/// graph relabeled in that order. Two graphs are isomorphic (with matching
+/// node and edge labels, as given by `node_label` and `edge_label`) if and
+/// only if renumbering their nodes in canonical order gives the same graph,
+/// so graphs with different hashes are not isomorphic, and graphs with the
+/// same hash are isomorphic unless their hashes collide. Pass `|_| ()` to
+/// ignore the node or edge weights. Parallel edges are supported.
+///
+/// Like [`wl_hash`](fn.wl_hash.html), the hash is the same on every platform
+/// and in every run of the program, as long as the labels hash their
+/// contents.
+///
+/// The canonical order is the one whose relabeled graph is least, among
+/// those found by individualization and refinement of the colors of the
+/// nodes. The search is pruned by the automorphisms of the graph, found with
+/// the VF2 algorithm, but it is exponential in the worst case, so it is only
+/// suitable for small graphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::canonical_labeling;
+///
+/// // Two numberings of a triangle with a pendant node.
+/// let g0 = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let g1 = UnGraph::<(), ()>::from_edges(&[(3, 1), (1, 2), (2, 3), (0, 3)]);
+/// let (order0, hash0) = canonical_labeling(&g0, |_| (), |_| ());
+/// let (order1, hash1) = canonical_labeling(&g1, |_| (), |_| ());
+/// assert_eq!(hash0, hash1);
+/// // The canonical orders map the pendant node to the same position.
+/// let pendant0 = order0.iter().position(|n| n.index() == 3);
+/// let pendant1 = order1.iter().position(|n| n.index() == 0);
+/// assert_eq!(pendant0, pendant1);
+/// ```
+pub fn canonical_labeling<G, F, H, L, M>(
+    graph: G,
+    mut node_label: F,
+    mut edge_label: H,
+) -> (Vec<G::NodeId>, u64)
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    F: FnMut(G::NodeRef) -> L,
+    H: FnMut(G::EdgeRef) -> M,
+    L: Hash + Ord,
+    M: Hash + Ord,
+{
+    let directed = graph.is_directed();
+    let automorphisms = ColoredAutomorphisms::new(graph);
+    let index = numbering(graph, automorphisms.nodes());
+    let n = automorphisms.nodes().len();
+
+    let mut node_labels: Vec<_> = graph
+        .node_references()
+        .map(|node| (index[graph.to_index(node.id())], node_label(node)))
+        .collect();
+    node_labels.sort_by_key(|&(i, _)| i);
+    let node_labels: Vec<L> = node_labels.into_iter().map(|(_, label)| label).collect();
+    let edges: Vec<(usize, usize, M)> = graph
+        .edge_references()
+        .map(|edge| {
+            let a = index[graph.to_index(edge.source())];
+            let b = index[graph.to_index(edge.target())];
+            (a, b, edge_label(edge))
+        })
+        .collect();
+
+    // Rank the labels, which gives the initial colors.
+    let colors = ranks(&node_labels);
+    let edge_ranks = {
+        let labels: Vec<_> = edges.iter().map(|e| &e.2).collect();
+        ranks(&labels)
+    };
+    let mut search = CanonicalSearch {
+        directed,
+        succ: vec![Vec::new(); n],
+        pred: vec![Vec::new(); n],
+        edges: Vec::with_capacity(edges.len()),
+        automorphisms,
+        best: None,
+    };
+    for (&(a, b, _), &label) in edges.iter().zip(&edge_ranks) {
+        search.succ[a].push((b, label));
+        if directed {
+            search.pred[b].push((a, label));
+        } else if a != b {
+            search.succ[b].push((a, label));
+        }
+        search.edges.push((a, b, label));
+    }
+    search.edges = search.relabel(&(0..n).collect::<Vec<_>>());
+    search.search(colors);
+
+    let order = search.best.map(|(order, _)| order).unwrap_or_default();
+    let mut position = vec![0; n];
+    for (p, &i) in order.iter().enumerate() {
+        position[i] = p;
+    }
+    // Hash the relabeled graph.
+    let mut hasher = StableHasher::new();
+    directed.hash(&mut hasher);
+    n.hash(&mut hasher);
+    for &i in &order {
+        node_labels[i].hash(&mut hasher);
+    }
+    let mut relabeled: Vec<_> = edges
+        .iter()
+        .zip(&edge_ranks)
+        .map(|(edge, &rank)| {
+            let (mut a, mut b) = (position[edge.0], position[edge.1]);
+            if !directed && a > b {
+                ::std::mem::swap(&mut a, &mut b);
+            }
+            (a, b, rank, &edge.2)
+        })
+        .collect();
+    relabeled.sort_unstable_by_key(|&(a, b, rank, _)| (a, b, rank));
+    relabeled.len().hash(&mut hasher);
+    for (a, b, _, label) in relabeled {
+        (a, b, label).hash(&mut hasher);
+    }
+    let nodes = search.automorphisms.nodes();
+    (order.iter().map(|&i| nodes[i]).collect(), hasher.finish())
+}
+
+/// Return the rank of each item among the distinct items.
+fn ranks<T: Ord>(items: &[T]) -> Vec<usize> {
+    let mut sorted: Vec<&T> = items.iter().collect();
+    sorted.sort();
+    sorted.dedup();
+    items
+        .iter()
+        .map(|item| sorted.binary_search(&item).unwrap())
+        .collect()
+}
+
+/// Edges as their endpoints and the rank of their label.
+type RankedEdges = Vec<(usize, usize, usize)>;
+
+/// The search tree of individualization and refinement.
+struct CanonicalSearch<G: IntoEdgeReferences> {
+    directed: bool,
+    /// The neighbors of each node, with the ranks of the edge labels.
+    succ: Vec<Vec<(usize, usize)>>,
+    pred: Vec<Vec<(usize, usize)>>,
+    /// The edges, with the ranks of their labels, in sorted order.
+    edges: RankedEdges,
+    automorphisms: ColoredAutomorphisms<G>,
+    /// The least order found so far, and its relabeled edges.
+    best: Option<(Vec<usize>, RankedEdges)>,
+}
+
+impl<G> CanonicalSearch<G>
+where
+    G: IntoNodeIdentifiers + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    /// Return the edges with their endpoints renumbered by `position`, in
+    /// sorted order.
+    fn relabel(&self, position: &[usize]) -> RankedEdges {
+        let mut edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|&(a, b, label)| {
+                let (a, b) = (position[a], position[b]);
+                if !self.directed && a > b {
+                    (b, a, label)
+                } else {
+                    (a, b, label)
+                }
+            })
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Refine the colors until nodes of the same color have the same numbers
+    /// of neighbors of each color. Colors are ranks, ordered first by the
+    /// previous color, so that the refinement does not depend on the
+    /// numbering of the nodes.
+    fn refine(&self, colors: &mut Vec<usize>) {
+        let mut classes = colors.iter().max().map_or(0, |&c| c + 1);
+        loop {
+            let signatures: Vec<_> = (0..colors.len())
+                .map(|i| {
+                    let neighbor_colors = |neighbors: &[(usize, usize)]| {
+                        let mut c: Vec<_> = neighbors
+                            .iter()
+                            .map(|&(j, label)| (colors[j], label))
+                            .collect();
+                        c.sort_unstable();
+                        c
+                    };
+                    (
+                        colors[i],
+                        neighbor_colors(&self.succ[i]),
+                        neighbor_colors(&self.pred[i]),
+                    )
+                })
+                .collect();
+            *colors = ranks(&signatures);
+            let refined = colors.iter().max().map_or(0, |&c| c + 1);
+            if refined == classes {
+                break;
+            }
+            classes = refined;
+        }
+    }
+
+    fn search(&mut self, mut colors: Vec<usize>) {
+        self.refine(&mut colors);
+        let n = colors.len();
+        // Find the first color that is shared by several nodes.
+        let mut sizes = vec![0; n];
+        for &c in &colors {
+            sizes[c] += 1;
+        }
+        let cell = match sizes.iter().position(|&size| size > 1) {
+            None => {
+                // All nodes have distinct colors, which give their order.
+                let edges = self.relabel(&colors);
+                let is_better = match self.best {
+                    None => true,
+                    Some((_, ref best)) => edges < *best,
+                };
+                if is_better {
+                    let mut order = vec![0; n];
+                    for (i, &c) in colors.iter().enumerate() {
+                        order[c] = i;
+                    }
+                    self.best = Some((order, edges));
+                }
+                return;
+            }
+            Some(cell) => cell,
+        };
+
+        // Individualize each node of the cell in turn, except for those that
+        // an automorphism maps to a node that was already individualized.
+        let mut orbits = UnionFind::new(n);
+        let mut explored: Vec<usize> = Vec::new();
+        for v in (0..n).filter(|&v| colors[v] == cell) {
+            for &u in &explored {
+                if orbits.equiv(u, v) {
+                    break;
+                }
+                if let Some(automorphism) = self.automorphisms.find(&colors, u, v) {
+                    if self.relabel(&automorphism) == self.edges {
+                        for (i, &j) in automorphism.iter().enumerate() {
+                            orbits.union(i, j);
+                        }
+                    }
+                }
+            }
+            if explored.iter().any(|&u| orbits.equiv(u, v)) {
+                continue;
+            }
+            explored.push(v);
+            let individualized = colors
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| if c < cell || i == v { c } else { c + 1 })
+                .collect();
+            self.search(individualized);
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! Centrality measures: closeness, harmonic, eigenvector, Katz and HITS.
+//!
+//! All measures return a score for every node, indexed by
+//! [`NodeIndexable::to_index`](../../visit/trait.NodeIndexable.html#tymethod.to_index).
+//! Indices that are not in use have a score of zero.
+//!
+//! The edges of undirected graphs are followed in both directions, and
+//! parallel edges are counted with their multiplicity. Every measure has a
+//! `weighted_` variant that takes the edge weights from a closure. See also
+//! [`page_rank`](../fn.page_rank.html) and
+//! [`betweenness_centrality`](../fn.betweenness_centrality.html).
+
+use std::collections::BinaryHeap;
+
+use crate::scored::MinScored;
+use crate::visit::{GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+use super::arcs::Arcs;
+
+/// \[Generic\] Compute the *closeness centrality* of every node.
+///
+/// The closeness of a node **u** is the number of nodes **r** that can be
+/// reached from **u**, divided by the sum of their distances from **u**.
+/// It is scaled by **r / (n - 1)**, where **n** is the number of nodes, so
+/// that nodes that reach only a small part of the graph do not get high
+/// scores ([Wasserman and Faust][1]). Nodes that reach no other node have a
+/// closeness of zero.
+///
+/// Distances are measured along outgoing edges, by their number of edges;
+/// see [`weighted_closeness_centrality`](fn.weighted_closeness_centrality.html)
+/// for weighted edges. Use [`Reversed`](../../visit/struct.Reversed.html) to
+/// measure distances to a node instead.
+///
+/// Runtime is **O(|V| (|E| + |V|) log |V|)**.
+///
+/// [1]: https://en.wikipedia.org/wiki/Closeness_centrality
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::centrality::closeness_centrality;
+///
+/// // A path: the middle node is at distance 1 from both ends.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// let scores = closeness_centrality(&g);
+/// assert_eq!(scores, vec![2. / 3., 1., 2. / 3.]);
+/// ```
+pub fn closeness_centrality<G>(graph: G) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    Arcs::new(graph, |_| 1.).closeness()
+}
+
+/// \[Generic\] Compute the *closeness centrality* of every node, with
+/// weighted edges.
+///
+/// This is [`closeness_centrality`](fn.closeness_centrality.html), except
+/// that distances are measured by the sum of the edge costs given by
+/// `edge_cost`. Edge costs must be non-negative.
+///
+/// Runtime is **O(|V| (|E| + |V|) log |V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::centrality::weighted_closeness_centrality;
+/// use petgraph::visit::EdgeRef;
+///
+/// // A path, where the middle node is at distance 1 and 3 from the ends.
+/// let g = UnGraph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 2, 3.)]);
+///
+/// let scores = weighted_closeness_centrality(&g, |e| *e.weight());
+/// assert_eq!(scores[1], 0.5);
+/// ```
+pub fn weighted_closeness_centrality<G, F>(graph: G, edge_cost: F) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    Arcs::new(graph, edge_cost).closeness()
+}
+
+/// \[Generic\] Compute the *harmonic centrality* of every node.
+///
+/// The harmonic centrality of a node is the sum of the inverse distances
+/// from the node to all other nodes, where unreachable nodes contribute
+/// zero. Unlike [`closeness_centrality`](fn.closeness_centrality.html), it
+/// is well defined on graphs that are not connected.
+///
+/// Distances are measured along outgoing edges, by their number of edges;
+/// see [`weighted_harmonic_centrality`](fn.weighted_harmonic_centrality.html)
+/// for weighted edges.
+///
+/// Runtime is **O(|V| (|E| + |V|) log |V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::centrality::harmonic_centrality;
+///
+/// // A path, and a node by itself.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 3)]);
+///
+/// let scores = harmonic_centrality(&g);
+/// assert_eq!(scores, vec![1.5, 2., 1.5, 0.]);
+/// ```
+pub fn harmonic_centrality<G>(graph: G) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    Arcs::new(graph, |_| 1.).harmonic()
+}
+
+/// \[Generic\] Compute the *harmonic centrality* of every node, with
+/// weighted edges.
+///
+/// This is [`harmonic_centrality`](fn.harmonic_centrality.html), except that
+/// distances are measured by the sum of the edge costs given by
+/// `edge_cost`. Edge costs must be positive.
+///
+/// Runtime is **O(|V| (|E| + |V|) log |V|)**.
+pub fn weighted_harmonic_centrality<G, F>(graph: G, edge_cost: F) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    Arcs::new(graph, edge_cost).harmonic()
+}
+
+/// \[Generic\] Compute the *eigenvector centrality* of every node.
+///
+/// The eigenvector centrality of a node is proportional to the sum of the
+/// centralities of the nodes with an edge to it: the scores form the
+/// eigenvector of the largest eigenvalue of the (transposed) adjacency
+/// matrix, with a Euclidean norm of one. See
+/// [`weighted_eigenvector_centrality`](fn.weighted_eigenvector_centrality.html)
+/// for weighted edges.
+///
+/// The scores are computed by power iteration on **A + I**, which has the
+/// same eigenvectors as the adjacency matrix **A** but converges on
+/// bipartite graphs too. The iteration stops when the sum of the absolute
+/// changes of one iteration is below `tolerance`, or after at most
+/// `max_iter` iterations.
+///
+/// Runtime is **O(|V| + |E|)** per iteration. See
+/// [eigenvector centrality][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Eigenvector_centrality
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::centrality::eigenvector_centrality;
+///
+/// // A star: the center has the largest score.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+///
+/// let scores = eigenvector_centrality(&g, 1e-12, 1000);
+/// assert!((scores[0] - 0.5f64.sqrt()).abs() < 1e-6);
+/// assert!((scores[1] - 0.125f64.sqrt()).abs() < 1e-6);
+/// ```
+pub fn eigenvector_centrality<G>(graph: G, tolerance: f64, max_iter: usize) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    Arcs::new(graph, |_| 1.).eigenvector(tolerance, max_iter)
+}
+
+/// \[Generic\] Compute the *eigenvector centrality* of every node, with
+/// weighted edges.
+///
+/// This is [`eigenvector_centrality`](fn.eigenvector_centrality.html), with
+/// the entries of the adjacency matrix given by `edge_weight`. Edge weights
+/// must be non-negative.
+///
+/// Runtime is **O(|V| + |E|)** per iteration.
+pub fn weighted_eigenvector_centrality<G, F>(
+    graph: G,
+    tolerance: f64,
+    max_iter: usize,
+    edge_weight: F,
+) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    Arcs::new(graph, edge_weight).eigenvector(tolerance, max_iter)
+}
+
+/// \[Generic\] Compute the *Katz centrality* of every node.
+///
+/// The Katz centrality of a node is `beta` plus `alpha` times the sum of the
+/// centralities of the nodes with an edge to it. It counts the walks that
+/// end at the node, with walks of length **k** attenuated by
+/// **alpha<sup>k</sup>**. See
+/// [`weighted_katz_centrality`](fn.weighted_katz_centrality.html) for
+/// weighted edges.
+///
+/// The scores are computed by iteration from zero, which converges if
+/// `alpha` is smaller than the inverse of the largest eigenvalue of the
+/// adjacency matrix. The iteration stops when the sum of the absolute
+/// changes of one iteration is below `tolerance`, or after at most
+/// `max_iter` iterations. The scores are not normalized.
+///
+/// Runtime is **O(|V| + |E|)** per iteration. See [Katz centrality][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Katz_centrality
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::centrality::katz_centrality;
+///
+/// // A directed path: x0 = 1, x1 = 1 + x0 / 2, x2 = 1 + x1 / 2.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// let scores = katz_centrality(&g, 0.5, 1., 1e-12, 100);
+/// assert_eq!(scores, vec![1., 1.5, 1.75]);
+/// ```
+pub fn katz_centrality<G>(
+    graph: G,
+    alpha: f64,
+    beta: f64,
+    tolerance: f64,
+    max_iter: usize,
+) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    Arcs::new(graph, |_| 1.).katz(alpha, beta, tolerance, max_iter)
+}
+
+/// \[Generic\] Compute the *Katz centrality* of every node, with weighted
+/// edges.
+///
+/// This is [`katz_centrality`](fn.katz_centrality.html), with the entries of
+/// the adjacency matrix given by `edge_weight`.
+///
+/// Runtime is **O(|V| + |E|)** per iteration.
+pub fn weighted_katz_centrality<G, F>(
+    graph: G,
+    alpha: f64,
+    beta: f64,
+    tolerance: f64,
+    max_iter: usize,
+    edge_weight: F,
+) -> Vec<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    Arcs::new(graph, edge_weight).katz(alpha, beta, tolerance, max_iter)
+}
+
+/// \[Generic\] Compute the *hub* and *authority* scores of every node with
+/// the HITS algorithm.
+///
+/// The authority score of a node is proportional to the sum of the hub
+/// scores of the nodes with an edge to it, and the hub score of a node is
+/// proportional to the sum of the authority scores of the nodes it has an
+/// edge to. Both are normalized to sum to one. See
+/// [`weighted_hits`](fn.weighted_hits.html) for weighted edges.
+///
+/// The scores are computed by power iteration, starting from uniform hub
+/// scores, until the sum of the absolute changes of the hub scores in one
+/// iteration is below `tolerance`, or for at most `max_iter` iterations.
+///
+/// Returns `(hubs, authorities)`. Runtime is **O(|V| + |E|)** per
+/// iteration. See [HITS][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/HITS_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::centrality::hits;
+///
+/// // Nodes 0 and 1 both link to 2 and 3; node 1 also links to 4.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 2), (0, 3), (1, 2), (1, 3), (1, 4)]);
+///
+/// let (hubs, authorities) = hits(&g, 1e-12, 1000);
+/// assert!(hubs[1] > hubs[0]);
+/// assert_eq!(hubs[2], 0.);
+/// assert!(authorities[2] > authorities[4]);
+/// assert_eq!(authorities[0], 0.);
+/// ```
+pub fn hits<G>(graph: G, tolerance: f64, max_iter: usize) -> (Vec<f64>, Vec<f64>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    Arcs::new(graph, |_| 1.).hits(tolerance, max_iter)
+}
+
+/// \[Generic\] Compute the *hub* and *authority* scores of every node with
+/// the HITS algorithm, with weighted edges.
+///
+/// This is [`hits`](fn.hits.html), with the entries of the adjacency matrix
+/// given by `edge_weight`. Edge weights must be non-negative.
+///
+/// Runtime is **O(|V| + |E|)** per iteration.
+pub fn weighted_hits<G, F>(
+    graph: G,
+    tolerance: f64,
+    max_iter: usize,
+    edge_weight: F,
+) -> (Vec<f64>, Vec<f64>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    Arcs::new(graph, edge_weight).hits(tolerance, max_iter)
+}
+
+impl<N, E> Arcs<N, E, f64> {
+    fn closeness(&self) -> Vec<f64> {
+        let others = self.len().saturating_sub(1) as f64;
+        let mut scores = vec![0.; self.len()];
+        for (u, score) in scores.iter_mut().enumerate() {
+            let (mut reached, mut total) = (0, 0.);
+            self.distances(u, |d| {
+                reached += 1;
+                total += d;
+            });
+            if total > 0. {
+                let reached = reached as f64;
+                *score = reached / total * (reached / others);
+            }
+        }
+        self.by_node_index(&scores)
+    }
+
+    fn harmonic(&self) -> Vec<f64> {
+        let mut scores = vec![0.; self.len()];
+        for (u, score) in scores.iter_mut().enumerate() {
+            self.distances(u, |d| *score += 1. / d);
+        }
+        self.by_node_index(&scores)
+    }
+
+    fn eigenvector(&self, tolerance: f64, max_iter: usize) -> Vec<f64> {
+        let mut scores = vec![1.; self.len()];
+        normalize_euclidean(&mut scores);
+        let mut next = vec![0.; scores.len()];
+        for _ in 0..max_iter {
+            next.copy_from_slice(&scores);
+            self.propagate(&scores, &mut next, 1.);
+            normalize_euclidean(&mut next);
+            let change = distance(&scores, &next);
+            std::mem::swap(&mut scores, &mut next);
+            if change < tolerance {
+                break;
+            }
+        }
+        self.by_node_index(&scores)
+    }
+
+    fn katz(&self, alpha: f64, beta: f64, tolerance: f64, max_iter: usize) -> Vec<f64> {
+        let mut scores = vec![0.; self.len()];
+        let mut next = vec![0.; scores.len()];
+        for _ in 0..max_iter {
+            next.iter_mut().for_each(|x| *x = beta);
+            self.propagate(&scores, &mut next, alpha);
+            let change = distance(&scores, &next);
+            std::mem::swap(&mut scores, &mut next);
+            if change < tolerance {
+                break;
+            }
+        }
+        self.by_node_index(&scores)
+    }
+
+    fn hits(&self, tolerance: f64, max_iter: usize) -> (Vec<f64>, Vec<f64>) {
+        let mut hubs = vec![1.; self.len()];
+        normalize_sum(&mut hubs);
+        let mut authorities = vec![0.; hubs.len()];
+        let mut next = vec![0.; hubs.len()];
+        for _ in 0..max_iter {
+            authorities.iter_mut().for_each(|a| *a = 0.);
+            self.propagate(&hubs, &mut authorities, 1.);
+            normalize_sum(&mut authorities);
+            for (u, hub) in next.iter_mut().enumerate() {
+                *hub = self.out[u]
+                    .iter()
+                    .fold(0., |total, &(v, e)| total + self.cost[e] * authorities[v]);
+            }
+            normalize_sum(&mut next);
+            let change = distance(&hubs, &next);
+            std::mem::swap(&mut hubs, &mut next);
+            if change < tolerance {
+                break;
+            }
+        }
+        (self.by_node_index(&hubs), self.by_node_index(&authorities))
+    }
+
+    /// Return `scores`, by compact node number, indexed by node index.
+    fn by_node_index(&self, scores: &[f64]) -> Vec<f64> {
+        self.index
+            .iter()
+            .map(|&u| if u == usize::MAX { 0. } else { scores[u] })
+            .collect()
+    }
+
+    /// Add `factor` times the weighted sum of `scores` over the incoming
+    /// edges of each node to `next`.
+    fn propagate(&self, scores: &[f64], next: &mut [f64], factor: f64) {
+        for (u, arcs) in self.out.iter().enumerate() {
+            for &(v, e) in arcs {
+                next[v] += factor * self.cost[e] * scores[u];
+            }
+        }
+    }
+
+    /// Call `f` with the distance of every node reachable from `source`,
+    /// other than `source` itself.
+    fn distances<F>(&self, source: usize, mut f: F)
+    where
+        F: FnMut(f64),
+    {
+        let mut distance = vec![None; self.out.len()];
+        let mut finished = vec![false; self.out.len()];
+        let mut heap = BinaryHeap::new();
+        distance[source] = Some(0.);
+        heap.push(MinScored(0., source));
+        while let Some(MinScored(d, u)) = heap.pop() {
+            if finished[u] {
+                continue;
+            }
+            finished[u] = true;
+            if u != source {
+                f(d);
+            }
+            for &(v, e) in &self.out[u] {
+                let next = d + self.cost[e];
+                let shorter = match distance[v] {
+                    None => true,
+                    Some(current) => next < current,
+                };
+                if !finished[v] && shorter {
+                    distance[v] = Some(next);
+                    heap.push(MinScored(next, v));
+                }
+            }
+        }
+    }
+}
+
+/// Scale `scores` to a Euclidean norm of one, unless they are all zero.
+fn normalize_euclidean(scores: &mut [f64]) {
+    let norm = scores.iter().map(|x| x * x).sum::<f64>().sqrt();
+    divide(scores, norm);
+}
+
+/// Scale `scores` to sum to one, unless they are all zero.
+fn normalize_sum(scores: &mut [f64]) {
+    let total = scores.iter().sum();
+    divide(scores, total);
+}
+
+fn divide(scores: &mut [f64], norm: f64) {
+    if norm > 0. {
+        for x in scores {
+            *x /= norm;
+        }
+    }
+}
+
+/// Return the sum of the absolute differences of `a` and `b`.
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+}
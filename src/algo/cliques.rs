@@ -0,0 +1,312 @@
+//! Maximal clique enumeration (Bron–Kerbosch).
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::adjacency::Adjacency;
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Return an iterator over the *maximal cliques* of the graph.
+///
+/// A clique is a set of nodes that are all adjacent to each other, and it is
+/// maximal if no other node can be added to it. The cliques are produced as
+/// vectors of nodes, each exactly once, in no particular order.
+///
+/// The edges of directed graphs are treated as if undirected, and self loops
+/// are ignored. Every node without neighbors is a maximal clique by itself.
+///
+/// This uses the [Bron–Kerbosch algorithm][1] with pivoting, with the outer
+/// level in degeneracy order. The iterator is lazy, so enumeration can be
+/// stopped early. A graph with **n** nodes and degeneracy **d** is enumerated
+/// in **O(d n 3<sup>d/3</sup>)** time, after **O(|V| + |E| log |E|)**
+/// preprocessing.
+///
+/// [1]: https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::graphmap::UnGraphMap;
+/// use petgraph::algo::maximal_cliques;
+///
+/// // Two triangles sharing the edge b-c, and a pendant edge.
+/// let g = UnGraphMap::<_, ()>::from_edges(&[
+///     ("a", "b"), ("a", "c"), ("b", "c"), ("b", "d"), ("c", "d"), ("d", "e"),
+/// ]);
+///
+/// let mut cliques: Vec<_> = maximal_cliques(&g)
+///     .map(|mut clique| {
+///         clique.sort();
+///         clique
+///     })
+///     .collect();
+/// cliques.sort();
+/// assert_eq!(cliques, vec![vec!["a", "b", "c"], vec!["b", "c", "d"], vec!["d", "e"]]);
+/// ```
+pub fn maximal_cliques<G>(graph: G) -> MaximalCliques<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let adjacency = Adjacency::new(graph);
+    let order = adjacency.smallest_last();
+    let mut position = vec![0; adjacency.len()];
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+    MaximalCliques {
+        adjacency,
+        order,
+        position,
+        next_vertex: 0,
+        stack: Vec::new(),
+        clique: Vec::new(),
+    }
+}
+
+/// Iterator over the maximal cliques of a graph, created by
+/// [`maximal_cliques`](fn.maximal_cliques.html).
+pub struct MaximalCliques<N> {
+    adjacency: Adjacency<N>,
+    /// The degeneracy order.
+    order: Vec<usize>,
+    position: Vec<usize>,
+    next_vertex: usize,
+    stack: Vec<Frame>,
+    /// The current clique; every frame of the stack extends it by one node.
+    clique: Vec<usize>,
+}
+
+/// One level of the Bron–Kerbosch recursion.
+struct Frame {
+    /// Nodes that extend the current clique (sorted).
+    candidates: Vec<usize>,
+    /// Nodes that extend the current clique, but whose cliques have been
+    /// reported already (sorted).
+    excluded: Vec<usize>,
+    /// The candidates that are not neighbors of the pivot, still to branch on.
+    branches: Vec<usize>,
+}
+
+impl<N> MaximalCliques<N>
+where
+    N: Copy,
+{
+    /// Start a new level for the current clique. Returns the clique if it is
+    /// maximal.
+    fn enter(&mut self, candidates: Vec<usize>, excluded: Vec<usize>) -> Option<Vec<N>> {
+        if candidates.is_empty() {
+            let result = if excluded.is_empty() {
+                Some(
+                    self.clique
+                        .iter()
+                        .map(|&v| self.adjacency.nodes[v])
+                        .collect(),
+                )
+            } else {
+                None
+            };
+            self.clique.pop();
+            return result;
+        }
+
+        // Choose the pivot that leaves the fewest branches.
+        let neighbors = &self.adjacency.neighbors;
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .max_by_key(|&&u| intersection_size(&candidates, &neighbors[u]))
+            .cloned()
+            .unwrap();
+        let mut branches = difference(&candidates, &neighbors[pivot]);
+        branches.reverse();
+        self.stack.push(Frame {
+            candidates,
+            excluded,
+            branches,
+        });
+        None
+    }
+}
+
+impl<N> Iterator for MaximalCliques<N>
+where
+    N: Copy,
+{
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Vec<N>> {
+        loop {
+            let (candidates, excluded) = match self.stack.last_mut() {
+                None => {
+                    // Start from the next node in degeneracy order, with its
+                    // later neighbors as candidates.
+                    let v = *self.order.get(self.next_vertex)?;
+                    self.next_vertex += 1;
+                    let (mut candidates, mut excluded) = (Vec::new(), Vec::new());
+                    for &w in &self.adjacency.neighbors[v] {
+                        if self.position[w] > self.position[v] {
+                            candidates.push(w);
+                        } else {
+                            excluded.push(w);
+                        }
+                    }
+                    self.clique.push(v);
+                    (candidates, excluded)
+                }
+                Some(frame) => match frame.branches.pop() {
+                    None => {
+                        self.stack.pop();
+                        self.clique.pop();
+                        continue;
+                    }
+                    Some(v) => {
+                        let neighbors = &self.adjacency.neighbors[v];
+                        let candidates = intersection(&frame.candidates, neighbors);
+                        let excluded = intersection(&frame.excluded, neighbors);
+                        // Move `v` from the candidates to the excluded nodes.
+                        if let Ok(i) = frame.candidates.binary_search(&v) {
+                            frame.candidates.remove(i);
+                        }
+                        if let Err(i) = frame.excluded.binary_search(&v) {
+                            frame.excluded.insert(i, v);
+                        }
+                        self.clique.push(v);
+                        (candidates, excluded)
+                    }
+                },
+            };
+            if let Some(clique) = self.enter(candidates, excluded) {
+                return Some(clique);
+            }
+        }
+    }
+}
+
+/// \[Generic\] Return a *maximum clique* of the graph: a clique with the
+/// largest number of nodes.
+///
+/// The edges of directed graphs are treated as if undirected, and self loops
+/// are ignored. The clique is found by enumerating the maximal cliques, see
+/// [`maximal_cliques`](fn.maximal_cliques.html); finding a maximum clique is
+/// NP-hard. An empty graph has an empty maximum clique.
+///
+/// # Example
+/// ```rust
+/// use petgraph::matrix_graph::UnMatrix;
+/// use petgraph::algo::{clique_number, maximum_clique};
+///
+/// // A square with one diagonal.
+/// let g = UnMatrix::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+///
+/// let mut clique = maximum_clique(&g);
+/// clique.sort();
+/// assert!(clique == vec![0.into(), 1.into(), 2.into()] || clique == vec![0.into(), 2.into(), 3.into()]);
+/// assert_eq!(clique_number(&g), 3);
+/// ```
+pub fn maximum_clique<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    maximal_cliques(graph).fold(Vec::new(), |best, clique| {
+        if clique.len() > best.len() {
+            clique
+        } else {
+            best
+        }
+    })
+}
+
+/// \[Generic\] Return the *clique number* of the graph: the number of nodes in
+/// its largest clique.
+///
+/// See [`maximum_clique`](fn.maximum_clique.html).
+pub fn clique_number<G>(graph: G) -> usize
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    maximal_cliques(graph)
+        .map(|clique| clique.len())
+        .max()
+        .unwrap_or(0)
+}
+
+/// \[Generic\] Return the size of the largest clique containing each node.
+///
+/// See [`maximal_cliques`](fn.maximal_cliques.html).
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::node_clique_number;
+///
+/// // A triangle with a pendant edge.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+///
+/// let sizes = node_clique_number(&g);
+/// assert_eq!(sizes[&2.into()], 3);
+/// assert_eq!(sizes[&3.into()], 2);
+/// ```
+pub fn node_clique_number<G>(graph: G) -> HashMap<G::NodeId, usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let mut sizes = HashMap::new();
+    for clique in maximal_cliques(graph) {
+        for &n in &clique {
+            let size = sizes.entry(n).or_insert(0);
+            if clique.len() > *size {
+                *size = clique.len();
+            }
+        }
+    }
+    sizes
+}
+
+/// Return the elements of the sorted slice `a` that are in the sorted slice
+/// `b`.
+fn intersection(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let mut result = Vec::new();
+    merge(a, b, |x, in_b| {
+        if in_b {
+            result.push(x)
+        }
+    });
+    result
+}
+
+/// Return the elements of the sorted slice `a` that are not in the sorted
+/// slice `b`.
+fn difference(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let mut result = Vec::new();
+    merge(a, b, |x, in_b| {
+        if !in_b {
+            result.push(x)
+        }
+    });
+    result
+}
+
+fn intersection_size(a: &[usize], b: &[usize]) -> usize {
+    let mut count = 0;
+    merge(a, b, |_, in_b| {
+        if in_b {
+            count += 1
+        }
+    });
+    count
+}
+
+/// Call `f` for every element of the sorted slice `a`, with `true` if the
+/// element is also in the sorted slice `b`.
+fn merge<F>(a: &[usize], b: &[usize], mut f: F)
+where
+    F: FnMut(usize, bool),
+{
+    let mut j = 0;
+    for &x in a {
+        while j < b.len() && b[j] < x {
+            j += 1;
+        }
+        f(x, j < b.len() && b[j] == x);
+    }
+}
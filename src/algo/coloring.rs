@@ -0,0 +1,319 @@
+//! Vertex colorings.
+//!
+//! A *(proper) vertex coloring* assigns a color to every node so that no two
+//! adjacent nodes have the same color. Colors are numbered from `0`, and
+//! every function returns the coloring as a map from node to color together
+//! with the number of colors used.
+//!
+//! The edges of directed graphs are treated as if undirected, and self loops
+//! are ignored.
+//!
+//! Finding a coloring with the fewest colors is NP-hard. The heuristics
+//! [`greedy_coloring`](fn.greedy_coloring.html) and
+//! [`dsatur_coloring`](fn.dsatur_coloring.html) run in polynomial time, while
+//! [`exact_coloring`](fn.exact_coloring.html) finds an optimal coloring in
+//! exponential time.
+
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use fixedbitset::FixedBitSet;
+
+use super::adjacency::Adjacency;
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+const UNCOLORED: usize = usize::MAX;
+
+/// The order in which [`greedy_coloring`](fn.greedy_coloring.html) colors
+/// the nodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NodeOrdering {
+    /// The order of the graph's node identifiers.
+    Natural,
+    /// Nodes with larger degree first (Welsh–Powell).
+    LargestFirst,
+    /// The reverse of the order in which nodes are removed when repeatedly
+    /// removing a node of smallest degree (Matula–Beck). A graph with
+    /// degeneracy **d** is colored with at most **d + 1** colors.
+    SmallestLast,
+}
+
+/// \[Generic\] Color the nodes greedily, in the given order.
+///
+/// Each node gets the smallest color not used by its already colored
+/// neighbors.
+///
+/// Runtime is **O(|V| + |E| log |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::coloring::{greedy_coloring, is_proper_coloring, NodeOrdering};
+///
+/// // A cycle of length 6.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+///
+/// let (colors, count) = greedy_coloring(&g, NodeOrdering::SmallestLast);
+/// assert!(count <= 3);
+/// assert!(is_proper_coloring(&g, &colors));
+/// ```
+pub fn greedy_coloring<G>(graph: G, ordering: NodeOrdering) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let adjacency = Adjacency::new(graph);
+    let order = match ordering {
+        NodeOrdering::Natural => (0..adjacency.len()).collect(),
+        NodeOrdering::LargestFirst => {
+            let mut order: Vec<_> = (0..adjacency.len()).collect();
+            order.sort_by_key(|&v| ::std::cmp::Reverse(adjacency.degree(v)));
+            order
+        }
+        NodeOrdering::SmallestLast => {
+            let mut order = adjacency.smallest_last();
+            order.reverse();
+            order
+        }
+    };
+
+    let mut colors = vec![UNCOLORED; adjacency.len()];
+    let mut taken = FixedBitSet::with_capacity(0);
+    for v in order {
+        taken.clear();
+        taken.grow(adjacency.degree(v) + 1);
+        for &w in &adjacency.neighbors[v] {
+            if colors[w] < taken.len() {
+                taken.insert(colors[w]);
+            }
+        }
+        colors[v] = smallest_absent(&taken);
+    }
+    into_coloring(adjacency, colors)
+}
+
+/// \[Generic\] Color the nodes with the [DSatur][1] heuristic.
+///
+/// The next node to be colored is always the one whose neighbors use the
+/// most distinct colors (ties are broken by the number of uncolored
+/// neighbors), and it gets the smallest color not used by its neighbors.
+/// DSatur colors bipartite graphs, cycles and wheels optimally.
+///
+/// Runtime is **O((|V| + |E|) log |V|)**.
+///
+/// [1]: https://en.wikipedia.org/wiki/DSatur
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::coloring::dsatur_coloring;
+///
+/// // A path and a triangle.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 4), (4, 5), (5, 3)]);
+///
+/// let (colors, count) = dsatur_coloring(&g);
+/// assert_eq!(count, 3);
+/// assert_ne!(colors[&3.into()], colors[&4.into()]);
+/// ```
+pub fn dsatur_coloring<G>(graph: G) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let adjacency = Adjacency::new(graph);
+    let length = adjacency.len();
+    let mut colors = vec![UNCOLORED; length];
+    // The colors used by the neighbors of each node.
+    let mut neighbor_colors = vec![FixedBitSet::with_capacity(0); length];
+    let mut saturation = vec![0; length];
+    let mut uncolored_degree: Vec<_> = (0..length).map(|v| adjacency.degree(v)).collect();
+
+    // Entries are updated lazily: an entry is stale if it doesn't match the
+    // node's current saturation and degree.
+    let mut queue: BinaryHeap<_> = (0..length)
+        .map(|v| (0, uncolored_degree[v], ::std::cmp::Reverse(v)))
+        .collect();
+    while let Some((sat, degree, ::std::cmp::Reverse(v))) = queue.pop() {
+        if colors[v] != UNCOLORED || sat != saturation[v] || degree != uncolored_degree[v] {
+            continue;
+        }
+        let color = smallest_absent(&neighbor_colors[v]);
+        colors[v] = color;
+        for &w in &adjacency.neighbors[v] {
+            if colors[w] != UNCOLORED {
+                continue;
+            }
+            uncolored_degree[w] -= 1;
+            if color >= neighbor_colors[w].len() {
+                neighbor_colors[w].grow(color + 1);
+            }
+            if !neighbor_colors[w].put(color) {
+                saturation[w] += 1;
+            }
+            queue.push((saturation[w], uncolored_degree[w], ::std::cmp::Reverse(w)));
+        }
+    }
+    into_coloring(adjacency, colors)
+}
+
+/// \[Generic\] Color the nodes with the fewest possible colors.
+///
+/// The coloring is found by a backtracking search that colors the nodes in
+/// DSatur order, starting from the DSatur coloring as an upper bound and
+/// stopping early when a clique shows that the coloring is optimal.
+///
+/// The search takes exponential time in the worst case and is only suitable
+/// for small graphs, up to a few dozen nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::coloring::{dsatur_coloring, exact_coloring, is_proper_coloring};
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[
+///     (0, 1), (0, 5), (0, 7), (1, 2), (1, 4), (3, 4),
+///     (3, 7), (3, 8), (4, 8), (7, 8),
+/// ]);
+///
+/// // DSatur needs four colors, although three are enough.
+/// assert_eq!(dsatur_coloring(&g).1, 4);
+/// let (colors, count) = exact_coloring(&g);
+/// assert_eq!(count, 3);
+/// assert!(is_proper_coloring(&g, &colors));
+/// ```
+pub fn exact_coloring<G>(graph: G) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (upper_bound, _) = dsatur_coloring(graph);
+    let adjacency = Adjacency::new(graph);
+    let length = adjacency.len();
+    let best: Vec<_> = adjacency.nodes.iter().map(|n| upper_bound[n]).collect();
+    let mut search = ExactSearch {
+        adjacency: &adjacency,
+        colors: vec![UNCOLORED; length],
+        best_count: best.iter().map(|&c| c + 1).max().unwrap_or(0),
+        best,
+        lower_bound: greedy_clique_size(&adjacency),
+    };
+    search.search(0, 0);
+    let colors = search.best;
+    into_coloring(adjacency, colors)
+}
+
+/// \[Generic\] Return `true` if `colors` is a proper coloring of the graph:
+/// every node has a color, and no two adjacent nodes have the same color.
+///
+/// Self loops are ignored.
+pub fn is_proper_coloring<G>(graph: G, colors: &HashMap<G::NodeId, usize>) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers,
+    G::NodeId: Eq + Hash,
+{
+    graph.node_identifiers().all(|u| match colors.get(&u) {
+        None => false,
+        Some(color) => graph
+            .neighbors(u)
+            .all(|v| u == v || colors.get(&v) != Some(color)),
+    })
+}
+
+/// Return the smallest color that is not in `colors`.
+fn smallest_absent(colors: &FixedBitSet) -> usize {
+    (0..colors.len())
+        .find(|&c| !colors[c])
+        .unwrap_or_else(|| colors.len())
+}
+
+/// Return the size of a clique found greedily, a lower bound on the number
+/// of colors.
+fn greedy_clique_size<N>(adjacency: &Adjacency<N>) -> usize {
+    let mut order: Vec<_> = (0..adjacency.len()).collect();
+    order.sort_by_key(|&v| ::std::cmp::Reverse(adjacency.degree(v)));
+    let mut best = 0;
+    for &start in &order {
+        if adjacency.degree(start) < best {
+            break;
+        }
+        let mut clique = vec![start];
+        for &v in &order {
+            if clique
+                .iter()
+                .all(|&u| adjacency.neighbors[u].binary_search(&v).is_ok())
+            {
+                clique.push(v);
+            }
+        }
+        best = best.max(clique.len());
+    }
+    best
+}
+
+fn into_coloring<N>(adjacency: Adjacency<N>, colors: Vec<usize>) -> (HashMap<N, usize>, usize)
+where
+    N: Eq + Hash,
+{
+    let count = colors.iter().map(|&c| c + 1).max().unwrap_or(0);
+    (adjacency.nodes.into_iter().zip(colors).collect(), count)
+}
+
+struct ExactSearch<'a, N> {
+    adjacency: &'a Adjacency<N>,
+    colors: Vec<usize>,
+    best: Vec<usize>,
+    best_count: usize,
+    lower_bound: usize,
+}
+
+impl<'a, N> ExactSearch<'a, N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Extend the partial coloring of `colored` nodes with `used` colors.
+    fn search(&mut self, colored: usize, used: usize) {
+        if colored == self.colors.len() {
+            self.best.clone_from(&self.colors);
+            self.best_count = used;
+            return;
+        }
+
+        // Branch on the uncolored node with the most distinct neighbor
+        // colors, then the most neighbors.
+        let mut next = None;
+        let mut taken = FixedBitSet::with_capacity(used);
+        for v in 0..self.colors.len() {
+            if self.colors[v] != UNCOLORED {
+                continue;
+            }
+            let mut seen = FixedBitSet::with_capacity(used);
+            for &w in &self.adjacency.neighbors[v] {
+                if self.colors[w] != UNCOLORED {
+                    seen.insert(self.colors[w]);
+                }
+            }
+            let key = (seen.count_ones(..), self.adjacency.degree(v));
+            let better = match next {
+                None => true,
+                Some((_, best)) => key > best,
+            };
+            if better {
+                next = Some((v, key));
+                taken = seen;
+            }
+        }
+        let (v, _) = next.unwrap();
+
+        for color in 0..=used {
+            if color + 1 >= self.best_count || self.best_count <= self.lower_bound {
+                break;
+            }
+            if color < used && taken[color] {
+                continue;
+            }
+            self.colors[v] = color;
+            self.search(colored + 1, used.max(color + 1));
+        }
+        self.colors[v] = UNCOLORED;
+    }
+}
@@ -0,0 +1,470 @@
+//! Community detection: label propagation and the Louvain method.
+//!
+//! A *community* is a set of nodes that are more densely connected to each
+//! other than to the rest of the graph. The algorithms here assign every
+//! node a community, numbered from zero, and report the *modularity* of the
+//! assignment, see [`modularity`](fn.modularity.html).
+//!
+//! The graphs are treated as undirected and weighted: the edge weights are
+//! given by a closure, and must be non-negative. Use `|_| 1.` for unweighted
+//! graphs. Parallel edges add up their weights, and the edges of directed
+//! graphs are treated as if undirected.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// The smallest gain of a move in the Louvain method, relative to the total
+/// edge weight: about the smallest increase of the modularity.
+const EPSILON: f64 = 1e-10;
+
+/// \[Generic\] Return the *modularity* of a partition of the nodes into
+/// communities.
+///
+/// The modularity is the fraction of the edge weight that is inside
+/// communities, minus the fraction expected if the edges were placed at
+/// random, keeping the weighted degrees of the nodes. It is at most one, and
+/// higher values mean more distinct communities. A self loop adds its weight
+/// to the degree of its node twice. The modularity of a graph without edges
+/// is zero.
+///
+/// `partition` maps every node to its community.
+///
+/// **Panics** if a node is not in `partition`.
+///
+/// Runtime is **O(|V| + |E|)**. See [modularity][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Modularity_(networks)
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::{NodeIndex, UnGraph};
+/// use petgraph::algo::community::modularity;
+///
+/// // Two triangles joined by an edge.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)]);
+///
+/// let partition = (0..6).map(|i| (NodeIndex::new(i), i / 3)).collect();
+/// let q = modularity(&g, &partition, |_| 1.);
+/// assert!((q - 5. / 14.).abs() < 1e-9);
+/// ```
+pub fn modularity<G, F>(graph: G, partition: &HashMap<G::NodeId, usize>, edge_weight: F) -> f64
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let network = Network::new(graph, edge_weight);
+    let community: Vec<_> = network.nodes.iter().map(|n| partition[n]).collect();
+    let (community, _) = relabel(&community);
+    network.modularity(&community)
+}
+
+/// \[Generic\] Detect communities by asynchronous *label propagation*.
+///
+/// Every node starts in a community of its own. In every pass, the nodes are
+/// visited in random order, and every node joins the community with the
+/// largest total edge weight to it, unless its own community is one of
+/// those; ties are broken at random. The passes are repeated until no node
+/// changes its community, which is guaranteed to happen because every change
+/// increases the total weight of the edges inside communities.
+///
+/// The random choices are made by a pseudorandom generator initialized with
+/// `seed`, so the result only depends on the graph and `seed`.
+///
+/// Returns the community of every node and the modularity of the
+/// communities.
+///
+/// Label propagation does not optimize the modularity, but every pass over
+/// the nodes takes only **O(|V| + |E|)** time, and few passes are needed in
+/// practice. See [Raghavan, Albert and Kumara][1].
+///
+/// [1]: https://arxiv.org/abs/0709.2938
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::community::label_propagation;
+///
+/// // Two 5-cliques joined by an edge.
+/// let mut g = UnGraph::<(), ()>::new_undirected();
+/// let nodes: Vec<_> = (0..10).map(|_| g.add_node(())).collect();
+/// for i in 0..10 {
+///     for j in i + 1..10 {
+///         if i / 5 == j / 5 {
+///             g.add_edge(nodes[i], nodes[j], ());
+///         }
+///     }
+/// }
+/// g.add_edge(nodes[4], nodes[5], ());
+///
+/// let (communities, q) = label_propagation(&g, 1, |_| 1.);
+/// assert_eq!(communities[&nodes[0]], communities[&nodes[4]]);
+/// assert_eq!(communities[&nodes[5]], communities[&nodes[9]]);
+/// assert_ne!(communities[&nodes[4]], communities[&nodes[5]]);
+/// assert!(q > 0.4);
+/// ```
+pub fn label_propagation<G, F>(
+    graph: G,
+    seed: u64,
+    edge_weight: F,
+) -> (HashMap<G::NodeId, usize>, f64)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let network = Network::new(graph, edge_weight);
+    let length = network.len();
+    let mut community: Vec<_> = (0..length).collect();
+    let mut weights = Weights::new(length);
+    let mut random = XorShift::new(seed);
+    let mut order: Vec<_> = (0..length).collect();
+    let mut best = Vec::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        random.shuffle(&mut order);
+        for &v in &order {
+            for &(w, weight) in &network.neighbors[v] {
+                weights.add(community[w], weight);
+            }
+            let current = community[v];
+            let max = weights.iter().fold(0., |max: f64, &(_, w)| max.max(w));
+            if weights.get(current) < max {
+                best.extend(weights.iter().filter(|&&(_, w)| w == max).map(|&(c, _)| c));
+                community[v] = best[random.below(best.len())];
+                best.clear();
+                changed = true;
+            }
+            weights.clear();
+        }
+    }
+    network.into_result(community)
+}
+
+/// \[Generic\] Detect communities with the *Louvain method*.
+///
+/// The Louvain method greedily optimizes the modularity, see
+/// [`modularity`](fn.modularity.html). Every node starts in a community of
+/// its own, and the nodes are moved, one at a time, to the neighboring
+/// community that increases the modularity the most, until no move
+/// increases it. Then every community is merged into a single node, and the
+/// process is repeated on the merged graph, until no nodes move.
+///
+/// A move must increase the modularity by more than a tolerance of about
+/// `1e-10`. This rules out moves that only gain by floating point rounding,
+/// so that every move makes progress and the passes end.
+///
+/// Returns the community of every node and the modularity of the
+/// communities. The nodes are visited in order, so the result is
+/// deterministic.
+///
+/// Every pass over the nodes takes **O(|V| + |E|)** time; the number of
+/// passes is small in practice. See [Blondel et al.][1].
+///
+/// [1]: https://arxiv.org/abs/0803.0476
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::community::louvain;
+/// use petgraph::visit::EdgeRef;
+///
+/// // A square with a heavy diagonal 0 - 2, and a light one 1 - 3.
+/// let g = UnGraph::<(), f64>::from_edges(&[
+///     (0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (3, 0, 1.),
+///     (0, 2, 5.), (1, 3, 2.),
+/// ]);
+///
+/// let (communities, q) = louvain(&g, |e| *e.weight());
+/// assert_eq!(communities[&0.into()], communities[&2.into()]);
+/// assert_eq!(communities[&1.into()], communities[&3.into()]);
+/// assert_ne!(communities[&0.into()], communities[&1.into()]);
+/// assert!(q > 0.);
+/// ```
+pub fn louvain<G, F>(graph: G, edge_weight: F) -> (HashMap<G::NodeId, usize>, f64)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let network = Network::new(graph, edge_weight);
+    let mut community: Vec<_> = (0..network.len()).collect();
+    let mut level = network.aggregate(&community);
+    loop {
+        let moved = level.move_nodes();
+        if moved.iter().enumerate().all(|(v, &c)| v == c) {
+            break;
+        }
+        // Relabel the communities from zero before merging them.
+        let (moved, count) = relabel(&moved);
+        for c in &mut community {
+            *c = moved[*c];
+        }
+        if count == level.len() {
+            break;
+        }
+        level = level.aggregate(&moved);
+    }
+    network.into_result(community)
+}
+
+/// A weighted undirected graph, by compact node index.
+struct Network<N> {
+    nodes: Vec<N>,
+    /// The neighbors of each node, as `(neighbor, weight)`; every edge is
+    /// listed at both ends. Self loops are not included.
+    neighbors: Vec<Vec<(usize, f64)>>,
+    /// The total weight of the self loops of each node.
+    loops: Vec<f64>,
+}
+
+impl<N> Network<N>
+where
+    N: Copy + Eq + Hash,
+{
+    fn new<G, F>(graph: G, mut edge_weight: F) -> Self
+    where
+        G: IntoEdgeReferences<NodeId = N> + IntoNodeIdentifiers + NodeIndexable,
+        F: FnMut(G::EdgeRef) -> f64,
+    {
+        let mut index = vec![usize::MAX; graph.node_bound()];
+        let mut nodes = Vec::new();
+        for n in graph.node_identifiers() {
+            index[graph.to_index(n)] = nodes.len();
+            nodes.push(n);
+        }
+        let mut neighbors = vec![Vec::new(); nodes.len()];
+        let mut loops = vec![0.; nodes.len()];
+        for edge in graph.edge_references() {
+            let a = index[graph.to_index(edge.source())];
+            let b = index[graph.to_index(edge.target())];
+            let weight = edge_weight(edge);
+            if a == b {
+                loops[a] += weight;
+            } else {
+                neighbors[a].push((b, weight));
+                neighbors[b].push((a, weight));
+            }
+        }
+        Network {
+            nodes,
+            neighbors,
+            loops,
+        }
+    }
+
+    /// Relabel the communities from zero, and return them with their
+    /// modularity.
+    fn into_result(self, community: Vec<usize>) -> (HashMap<N, usize>, f64) {
+        let (community, _) = relabel(&community);
+        let q = self.modularity(&community);
+        (self.nodes.into_iter().zip(community).collect(), q)
+    }
+}
+
+impl<N> Network<N> {
+    fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// The weighted degree of `v`.
+    fn degree(&self, v: usize) -> f64 {
+        self.neighbors[v].iter().map(|&(_, w)| w).sum::<f64>() + 2. * self.loops[v]
+    }
+
+    /// The modularity of the communities, numbered below `len`.
+    fn modularity(&self, community: &[usize]) -> f64 {
+        let mut inside = vec![0.; self.len()];
+        let mut total = vec![0.; self.len()];
+        let mut weight = 0.;
+        for v in 0..self.len() {
+            let c = community[v];
+            let degree = self.degree(v);
+            total[c] += degree;
+            weight += degree;
+            inside[c] += 2. * self.loops[v];
+            for &(w, edge_weight) in &self.neighbors[v] {
+                if community[w] == c {
+                    inside[c] += edge_weight;
+                }
+            }
+        }
+        if weight == 0. {
+            return 0.;
+        }
+        // `weight` is twice the total edge weight, and `inside` counts every
+        // edge from both ends.
+        inside
+            .iter()
+            .zip(&total)
+            .map(|(&i, &t)| i / weight - (t / weight) * (t / weight))
+            .sum()
+    }
+
+    /// Merge the nodes of each community, numbered below `len`, into one
+    /// node.
+    fn aggregate(&self, community: &[usize]) -> Network<()> {
+        let count = community.iter().map(|&c| c + 1).max().unwrap_or(0);
+        let mut merged = vec![HashMap::new(); count];
+        let mut loops = vec![0.; count];
+        for v in 0..self.len() {
+            let c = community[v];
+            loops[c] += self.loops[v];
+            for &(w, weight) in &self.neighbors[v] {
+                let d = community[w];
+                if c == d {
+                    // Counted from both ends.
+                    loops[c] += weight / 2.;
+                } else {
+                    *merged[c].entry(d).or_insert(0.) += weight;
+                }
+            }
+        }
+        let neighbors = merged
+            .into_iter()
+            .map(|weights| {
+                let mut list: Vec<_> = weights.into_iter().collect();
+                list.sort_by_key(|&(d, _)| d);
+                list
+            })
+            .collect();
+        Network {
+            nodes: vec![(); count],
+            neighbors,
+            loops,
+        }
+    }
+
+    /// Move the nodes between communities until no move increases the
+    /// modularity by more than `EPSILON`, starting with a community of its
+    /// own for every node. Returns the community of every node.
+    fn move_nodes(&self) -> Vec<usize> {
+        let length = self.len();
+        let degree: Vec<_> = (0..length).map(|v| self.degree(v)).collect();
+        let weight: f64 = degree.iter().sum();
+        let mut community: Vec<_> = (0..length).collect();
+        // The total degree of each community.
+        let mut total = degree.clone();
+        let mut weights = Weights::new(length);
+        let epsilon = EPSILON * weight;
+        let mut moved = weight > 0.;
+        while moved {
+            moved = false;
+            for v in 0..length {
+                for &(w, edge_weight) in &self.neighbors[v] {
+                    weights.add(community[w], edge_weight);
+                }
+                let current = community[v];
+                total[current] -= degree[v];
+                // The modularity gain of adding `v` to a community, scaled
+                // by half the total edge weight.
+                let gain = |c: usize, to_c: f64| to_c - total[c] * degree[v] / weight;
+                let mut best = current;
+                let mut best_gain = gain(current, weights.get(current));
+                for &(c, to_c) in weights.iter() {
+                    let g = gain(c, to_c);
+                    if g > best_gain + epsilon {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+                weights.clear();
+                total[best] += degree[v];
+                if best != current {
+                    community[v] = best;
+                    moved = true;
+                }
+            }
+        }
+        community
+    }
+}
+
+/// Sparse accumulator of weights by community.
+struct Weights {
+    weight: Vec<f64>,
+    touched: Vec<(usize, f64)>,
+    position: Vec<usize>,
+}
+
+impl Weights {
+    fn new(length: usize) -> Self {
+        Weights {
+            weight: vec![0.; length],
+            touched: Vec::new(),
+            position: vec![usize::MAX; length],
+        }
+    }
+
+    fn add(&mut self, c: usize, weight: f64) {
+        if self.position[c] == usize::MAX {
+            self.position[c] = self.touched.len();
+            self.touched.push((c, 0.));
+        }
+        self.weight[c] += weight;
+        self.touched[self.position[c]].1 = self.weight[c];
+    }
+
+    fn get(&self, c: usize) -> f64 {
+        self.weight[c]
+    }
+
+    /// The communities with weights, in the order they were first added.
+    fn iter(&self) -> std::slice::Iter<'_, (usize, f64)> {
+        self.touched.iter()
+    }
+
+    fn clear(&mut self) {
+        for &(c, _) in &self.touched {
+            self.weight[c] = 0.;
+            self.position[c] = usize::MAX;
+        }
+        self.touched.clear();
+    }
+}
+
+/// Number the communities from zero, in order of their first node. Returns
+/// the new numbers and the number of communities.
+fn relabel(community: &[usize]) -> (Vec<usize>, usize) {
+    let mut label = HashMap::new();
+    let relabeled = community
+        .iter()
+        .map(|&c| {
+            let next = label.len();
+            *label.entry(c).or_insert(next)
+        })
+        .collect();
+    (relabeled, label.len())
+}
+
+/// A small pseudorandom number generator (xorshift64*).
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // The state must not be zero.
+        XorShift(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Return a number below `n`, which must not be zero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
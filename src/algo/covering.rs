@@ -0,0 +1,555 @@
+//! Independent sets, vertex covers and dominating sets.
+//!
+//! * An *independent set* is a set of nodes of which no two are adjacent.
+//! * A *vertex cover* is a set of nodes that contains at least one endpoint of
+//!   every edge. The complement of an independent set is a vertex cover.
+//! * A *dominating set* is a set of nodes such that every node is in the set
+//!   or adjacent to a node in the set.
+//!
+//! The edges of directed graphs are treated as if undirected. A node with a
+//! self loop is adjacent to itself: it is never part of an independent set,
+//! and always part of a vertex cover.
+//!
+//! Finding a maximum independent set, a minimum vertex cover or a minimum
+//! dominating set is NP-hard. For each problem there is a polynomial time
+//! heuristic and an exact branch and bound search, which takes exponential
+//! time and is only suitable for small graphs. The sets are returned as
+//! vectors of nodes, in the order of the graph's node identifiers.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::adjacency::Adjacency;
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Return a maximal independent set, found greedily.
+///
+/// The node with the fewest remaining neighbors is added to the set, and it
+/// and its neighbors are removed from the graph, until no nodes remain.
+///
+/// Runtime is **O((|V| + |E|) log |V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::covering::{greedy_independent_set, is_independent_set};
+///
+/// // A star with three leaves.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+///
+/// let set = greedy_independent_set(&g);
+/// assert_eq!(set, vec![1.into(), 2.into(), 3.into()]);
+/// assert!(is_independent_set(&g, &set));
+/// ```
+pub fn greedy_independent_set<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let problem = Problem::new(graph);
+    let length = problem.adjacency.len();
+    let mut removed = vec![false; length];
+    let mut degree: Vec<_> = (0..length).map(|v| problem.adjacency.degree(v)).collect();
+    let mut queue = BinaryHeap::new();
+    for v in 0..length {
+        if problem.self_loop[v] {
+            remove(&problem.adjacency, v, &mut removed, &mut degree, &mut queue);
+        }
+    }
+    for v in 0..length {
+        if !removed[v] {
+            queue.push((Reverse(degree[v]), Reverse(v)));
+        }
+    }
+
+    let mut chosen = vec![false; length];
+    while let Some((Reverse(d), Reverse(v))) = queue.pop() {
+        if removed[v] || d != degree[v] {
+            continue;
+        }
+        chosen[v] = true;
+        remove(&problem.adjacency, v, &mut removed, &mut degree, &mut queue);
+        for &w in &problem.adjacency.neighbors[v] {
+            if !removed[w] {
+                remove(&problem.adjacency, w, &mut removed, &mut degree, &mut queue);
+            }
+        }
+    }
+    problem.select(&chosen)
+}
+
+/// Remove `v` from the graph, updating the degrees of its neighbors.
+fn remove<N>(
+    adjacency: &Adjacency<N>,
+    v: usize,
+    removed: &mut [bool],
+    degree: &mut [usize],
+    queue: &mut BinaryHeap<(Reverse<usize>, Reverse<usize>)>,
+) {
+    removed[v] = true;
+    for &w in &adjacency.neighbors[v] {
+        if !removed[w] {
+            degree[w] -= 1;
+            queue.push((Reverse(degree[w]), Reverse(w)));
+        }
+    }
+}
+
+/// \[Generic\] Return a maximum independent set: an independent set with the
+/// largest number of nodes.
+///
+/// The exact search branches on the node with the most neighbors, and always
+/// takes nodes with at most one remaining neighbor. It takes exponential time
+/// in the worst case and is only suitable for small graphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::covering::maximum_independent_set;
+///
+/// // A cycle of length five.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+///
+/// assert_eq!(maximum_independent_set(&g).len(), 2);
+/// ```
+pub fn maximum_independent_set<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let problem = Problem::new(graph);
+    let chosen = problem.maximum_independent_set();
+    problem.select(&chosen)
+}
+
+/// \[Generic\] Return a vertex cover with at most twice as many nodes as a
+/// minimum vertex cover.
+///
+/// Both endpoints of every edge of a maximal matching are added to the cover.
+///
+/// Runtime is **O(|V| + |E| log |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::covering::{approx_vertex_cover, is_vertex_cover};
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+///
+/// let cover = approx_vertex_cover(&g);
+/// assert!(is_vertex_cover(&g, &cover));
+/// assert!(cover.len() <= 2 * 2);
+/// ```
+pub fn approx_vertex_cover<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let problem = Problem::new(graph);
+    let mut chosen = problem.self_loop.clone();
+    for (u, neighbors) in problem.adjacency.neighbors.iter().enumerate() {
+        for &v in neighbors {
+            if !chosen[u] && !chosen[v] {
+                chosen[u] = true;
+                chosen[v] = true;
+            }
+        }
+    }
+    problem.select(&chosen)
+}
+
+/// \[Generic\] Return a minimum vertex cover: a vertex cover with the fewest
+/// nodes.
+///
+/// This is the complement of a maximum independent set, see
+/// [`maximum_independent_set`](fn.maximum_independent_set.html). It takes
+/// exponential time in the worst case and is only suitable for small graphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::covering::minimum_vertex_cover;
+///
+/// // A star with three leaves is covered by its center.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+///
+/// assert_eq!(minimum_vertex_cover(&g), vec![0.into()]);
+/// ```
+pub fn minimum_vertex_cover<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let problem = Problem::new(graph);
+    let mut chosen = problem.maximum_independent_set();
+    for c in &mut chosen {
+        *c = !*c;
+    }
+    problem.select(&chosen)
+}
+
+/// \[Generic\] Return a dominating set, found greedily.
+///
+/// The node that dominates the most nodes that are not dominated yet is added
+/// to the set, until all nodes are dominated. The set has at most
+/// **ln(Δ + 1) + 1** times as many nodes as a minimum dominating set, where
+/// **Δ** is the maximum degree.
+///
+/// Runtime is **O(|V| Δ² log |V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::covering::{greedy_dominating_set, is_dominating_set};
+///
+/// // Two stars joined at a leaf.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (3, 4), (4, 5), (4, 6)]);
+///
+/// let set = greedy_dominating_set(&g);
+/// assert_eq!(set, vec![0.into(), 4.into()]);
+/// assert!(is_dominating_set(&g, &set));
+/// ```
+pub fn greedy_dominating_set<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let problem = Problem::new(graph);
+    let chosen = problem.greedy_dominating_set();
+    problem.select(&chosen)
+}
+
+/// \[Generic\] Return a minimum dominating set: a dominating set with the
+/// fewest nodes.
+///
+/// The exact search branches on the ways to dominate the node with the fewest
+/// options, starting from the greedy solution as an upper bound. It takes
+/// exponential time in the worst case and is only suitable for small graphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::covering::minimum_dominating_set;
+///
+/// // A path of six nodes.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)]);
+///
+/// assert_eq!(minimum_dominating_set(&g).len(), 2);
+/// ```
+pub fn minimum_dominating_set<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let problem = Problem::new(graph);
+    let length = problem.adjacency.len();
+    let best = problem.greedy_dominating_set();
+    let mut search = DominatingSearch {
+        adjacency: &problem.adjacency,
+        max_closed_degree: (0..length)
+            .map(|v| problem.adjacency.degree(v) + 1)
+            .max()
+            .unwrap_or(1),
+        best_count: best.iter().filter(|&&c| c).count(),
+        best,
+        chosen: vec![false; length],
+        dominated: vec![0; length],
+    };
+    search.search(0, length);
+    problem.select(&search.best)
+}
+
+/// \[Generic\] Return `true` if no two of `nodes` are adjacent.
+///
+/// A node with a self loop is adjacent to itself.
+pub fn is_independent_set<G>(graph: G, nodes: &[G::NodeId]) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let member = membership(graph, nodes);
+    nodes
+        .iter()
+        .all(|&u| graph.neighbors(u).all(|v| !member[graph.to_index(v)]))
+}
+
+/// \[Generic\] Return `true` if every edge has an endpoint in `nodes`.
+pub fn is_vertex_cover<G>(graph: G, nodes: &[G::NodeId]) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let member = membership(graph, nodes);
+    graph
+        .node_identifiers()
+        .all(|u| member[graph.to_index(u)] || graph.neighbors(u).all(|v| member[graph.to_index(v)]))
+}
+
+/// \[Generic\] Return `true` if every node is in `nodes` or adjacent to one of
+/// them.
+pub fn is_dominating_set<G>(graph: G, nodes: &[G::NodeId]) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut dominated = membership(graph, nodes);
+    for &u in nodes {
+        for v in graph.neighbors(u) {
+            dominated[graph.to_index(v)] = true;
+        }
+    }
+    let member = membership(graph, nodes);
+    for u in graph.node_identifiers() {
+        if graph.neighbors(u).any(|v| member[graph.to_index(v)]) {
+            dominated[graph.to_index(u)] = true;
+        }
+    }
+    graph
+        .node_identifiers()
+        .all(|u| dominated[graph.to_index(u)])
+}
+
+/// Mark `nodes`, by node index.
+fn membership<G>(graph: G, nodes: &[G::NodeId]) -> Vec<bool>
+where
+    G: NodeIndexable,
+{
+    let mut member = vec![false; graph.node_bound()];
+    for &n in nodes {
+        member[graph.to_index(n)] = true;
+    }
+    member
+}
+
+/// The graph as compact undirected adjacency lists, and its self loops.
+struct Problem<N> {
+    adjacency: Adjacency<N>,
+    self_loop: Vec<bool>,
+}
+
+impl<N> Problem<N>
+where
+    N: Copy + PartialEq,
+{
+    fn new<G>(graph: G) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + IntoNodeIdentifiers + NodeIndexable,
+    {
+        let adjacency = Adjacency::new(graph);
+        let self_loop = adjacency
+            .nodes
+            .iter()
+            .map(|&n| graph.neighbors(n).any(|m| m == n))
+            .collect();
+        Problem {
+            adjacency,
+            self_loop,
+        }
+    }
+
+    fn select(&self, chosen: &[bool]) -> Vec<N> {
+        self.adjacency
+            .nodes
+            .iter()
+            .zip(chosen)
+            .filter(|&(_, &c)| c)
+            .map(|(&n, _)| n)
+            .collect()
+    }
+
+    fn maximum_independent_set(&self) -> Vec<bool> {
+        let length = self.adjacency.len();
+        let mut search = IndependentSearch {
+            adjacency: &self.adjacency,
+            best: vec![false; length],
+            best_count: 0,
+            chosen: vec![false; length],
+        };
+        // Nodes with self loops can't be chosen.
+        search.search(self.self_loop.clone(), 0);
+        search.best
+    }
+
+    fn greedy_dominating_set(&self) -> Vec<bool> {
+        let adjacency = &self.adjacency;
+        let length = adjacency.len();
+        let mut dominated = vec![false; length];
+        let mut chosen = vec![false; length];
+        let gain = |v: usize, dominated: &[bool]| {
+            adjacency.neighbors[v]
+                .iter()
+                .chain(Some(&v))
+                .filter(|&&w| !dominated[w])
+                .count()
+        };
+        // Gains only decrease, so a popped entry whose gain is still current
+        // is the best choice.
+        let mut queue: BinaryHeap<_> = (0..length)
+            .map(|v| (adjacency.degree(v) + 1, Reverse(v)))
+            .collect();
+        while let Some((g, Reverse(v))) = queue.pop() {
+            let current = gain(v, &dominated);
+            if current == 0 {
+                continue;
+            }
+            if current < g {
+                queue.push((current, Reverse(v)));
+                continue;
+            }
+            chosen[v] = true;
+            dominated[v] = true;
+            for &w in &adjacency.neighbors[v] {
+                dominated[w] = true;
+            }
+        }
+        chosen
+    }
+}
+
+struct IndependentSearch<'a, N> {
+    adjacency: &'a Adjacency<N>,
+    best: Vec<bool>,
+    best_count: usize,
+    chosen: Vec<bool>,
+}
+
+impl<'a, N> IndependentSearch<'a, N> {
+    /// Extend the current independent set of `count` nodes with nodes that
+    /// are not `removed`.
+    fn search(&mut self, mut removed: Vec<bool>, mut count: usize) {
+        let neighbors = &self.adjacency.neighbors;
+        let degree =
+            |v: usize, removed: &[bool]| neighbors[v].iter().filter(|&&w| !removed[w]).count();
+        let mut taken = Vec::new();
+
+        // Nodes with at most one neighbor are always part of some maximum
+        // independent set.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for v in 0..removed.len() {
+                if !removed[v] && degree(v, &removed) <= 1 {
+                    taken.push(v);
+                    count += 1;
+                    removed[v] = true;
+                    for &w in &neighbors[v] {
+                        removed[w] = true;
+                    }
+                    changed = true;
+                }
+            }
+        }
+        for &v in &taken {
+            self.chosen[v] = true;
+        }
+
+        let remaining = removed.iter().filter(|&&r| !r).count();
+        if remaining == 0 {
+            if count > self.best_count {
+                self.best_count = count;
+                self.best.clone_from(&self.chosen);
+            }
+        } else if count + self.clique_cover(&removed) > self.best_count {
+            // Branch on the node with the most neighbors.
+            let v = (0..removed.len())
+                .filter(|&v| !removed[v])
+                .max_by_key(|&v| (degree(v, &removed), Reverse(v)))
+                .unwrap();
+
+            // Take `v`.
+            let mut with_v = removed.clone();
+            with_v[v] = true;
+            for &w in &neighbors[v] {
+                with_v[w] = true;
+            }
+            self.chosen[v] = true;
+            self.search(with_v, count + 1);
+            self.chosen[v] = false;
+
+            // Leave out `v`.
+            removed[v] = true;
+            self.search(removed, count);
+        }
+
+        for &v in &taken {
+            self.chosen[v] = false;
+        }
+    }
+}
+
+impl<'a, N> IndependentSearch<'a, N> {
+    /// Return the number of cliques in a greedy partition of the nodes that
+    /// are not `removed`, an upper bound on the size of their independent
+    /// sets.
+    fn clique_cover(&self, removed: &[bool]) -> usize {
+        let neighbors = &self.adjacency.neighbors;
+        let mut cliques: Vec<Vec<usize>> = Vec::new();
+        for v in (0..removed.len()).filter(|&v| !removed[v]) {
+            let clique = cliques.iter_mut().find(|clique| {
+                clique
+                    .iter()
+                    .all(|&u| neighbors[v].binary_search(&u).is_ok())
+            });
+            match clique {
+                Some(clique) => clique.push(v),
+                None => cliques.push(vec![v]),
+            }
+        }
+        cliques.len()
+    }
+}
+
+struct DominatingSearch<'a, N> {
+    adjacency: &'a Adjacency<N>,
+    max_closed_degree: usize,
+    best: Vec<bool>,
+    best_count: usize,
+    chosen: Vec<bool>,
+    /// The number of chosen nodes that dominate each node.
+    dominated: Vec<usize>,
+}
+
+impl<'a, N> DominatingSearch<'a, N> {
+    fn search(&mut self, count: usize, undominated: usize) {
+        if undominated == 0 {
+            if count < self.best_count {
+                self.best_count = count;
+                self.best.clone_from(&self.chosen);
+            }
+            return;
+        }
+        // An improvement can add at most `best_count - count - 1` nodes, each
+        // of which dominates at most `max_closed_degree` nodes.
+        if count + 1 >= self.best_count
+            || undominated > (self.best_count - count - 1) * self.max_closed_degree
+        {
+            return;
+        }
+
+        // Branch on the undominated node with the fewest ways to dominate it.
+        let neighbors = &self.adjacency.neighbors;
+        let u = (0..self.dominated.len())
+            .filter(|&u| self.dominated[u] == 0)
+            .min_by_key(|&u| neighbors[u].len())
+            .unwrap();
+        let mut options: Vec<_> = neighbors[u].iter().cloned().chain(Some(u)).collect();
+        options.sort_by_key(|&w| Reverse(self.gain(w)));
+        for w in options {
+            let gain = self.gain(w);
+            self.set(w, true);
+            self.search(count + 1, undominated - gain);
+            self.set(w, false);
+        }
+    }
+
+    /// Return the number of undominated nodes that `v` dominates.
+    fn gain(&self, v: usize) -> usize {
+        self.adjacency.neighbors[v]
+            .iter()
+            .chain(Some(&v))
+            .filter(|&&w| self.dominated[w] == 0)
+            .count()
+    }
+
+    fn set(&mut self, v: usize, chosen: bool) {
+        self.chosen[v] = chosen;
+        for &w in self.adjacency.neighbors[v].iter().chain(Some(&v)) {
+            if chosen {
+                self.dominated[w] += 1;
+            } else {
+                self.dominated[w] -= 1;
+            }
+        }
+    }
+}
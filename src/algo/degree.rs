@@ -0,0 +1,149 @@
+//! Degree sequence, degree histogram and degree assortativity.
+
+use crate::visit::{Degree, EdgeRef, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Return the *degree sequence* of the graph: the degrees of
+/// all nodes, in non-increasing order.
+///
+/// Degrees are counted as by [`Degree::degree`](../visit/trait.Degree.html#method.degree):
+/// the sum of the in-degree and out-degree in a directed graph, and with self
+/// loops counted twice in an undirected graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::degree_sequence;
+///
+/// // A path with a self loop at one end.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 2)]);
+///
+/// assert_eq!(degree_sequence(&g), vec![3, 2, 1]);
+/// ```
+pub fn degree_sequence<G>(graph: G) -> Vec<usize>
+where
+    G: Degree + IntoNodeIdentifiers,
+{
+    let mut degrees: Vec<usize> = graph.node_identifiers().map(|n| graph.degree(n)).collect();
+    degrees.sort_unstable_by(|a, b| b.cmp(a));
+    degrees
+}
+
+/// \[Generic\] Return the *degree histogram* of the graph.
+///
+/// Entry `d` of the histogram is the number of nodes of degree `d`, where
+/// degrees are counted as by [`degree_sequence`](fn.degree_sequence.html).
+/// The histogram ends with the maximum degree, and is empty if the graph has
+/// no nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::degree_histogram;
+///
+/// // A star with three leaves, and an isolated node.
+/// let mut g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+/// g.add_node(());
+///
+/// assert_eq!(degree_histogram(&g), vec![1, 3, 0, 1]);
+/// ```
+pub fn degree_histogram<G>(graph: G) -> Vec<usize>
+where
+    G: Degree + IntoNodeIdentifiers,
+{
+    let mut histogram = Vec::new();
+    for n in graph.node_identifiers() {
+        let degree = graph.degree(n);
+        if degree >= histogram.len() {
+            histogram.resize(degree + 1, 0);
+        }
+        histogram[degree] += 1;
+    }
+    histogram
+}
+
+/// \[Generic\] Compute the *degree assortativity* of the graph.
+///
+/// The degree assortativity is the Pearson correlation coefficient of the
+/// degrees of the nodes at either end of each edge, ranging from `-1.` when
+/// nodes of high degree only connect to nodes of low degree, to `1.` when
+/// nodes only connect to nodes of the same degree. The edges of undirected
+/// graphs are counted in both directions; in directed graphs, the out-degree
+/// of the source of each edge is correlated with the in-degree of its target.
+///
+/// Returns `None` if the graph has no edges, or if the degrees at either end
+/// of the edges are all the same, so that the correlation is not defined.
+///
+/// Runtime is **O(|V| + |E|)**, plus the time to compute the degree of every
+/// node. See [Assortativity][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Assortativity
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::degree_assortativity;
+///
+/// // In a star, the center only connects to leaves.
+/// let star = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+/// let r = degree_assortativity(&star).unwrap();
+/// assert!((r + 1.).abs() < 1e-9);
+///
+/// // All degrees of a cycle are the same.
+/// let cycle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+/// assert_eq!(degree_assortativity(&cycle), None);
+/// ```
+pub fn degree_assortativity<G>(graph: G) -> Option<f64>
+where
+    G: Degree + IntoNodeIdentifiers + NodeIndexable,
+{
+    let directed = graph.is_directed();
+    let mut source_degree = vec![0.; graph.node_bound()];
+    let mut target_degree = vec![0.; graph.node_bound()];
+    for n in graph.node_identifiers() {
+        let i = graph.to_index(n);
+        if directed {
+            source_degree[i] = graph.out_degree(n) as f64;
+            target_degree[i] = graph.in_degree(n) as f64;
+        } else {
+            source_degree[i] = graph.degree(n) as f64;
+            target_degree[i] = source_degree[i];
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for edge in graph.edge_references() {
+        let source = graph.to_index(edge.source());
+        let target = graph.to_index(edge.target());
+        pairs.push((source_degree[source], target_degree[target]));
+        if !directed {
+            pairs.push((source_degree[target], target_degree[source]));
+        }
+    }
+    correlation(&pairs)
+}
+
+/// The Pearson correlation coefficient of `pairs`, if it is defined.
+fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.is_empty() {
+        return None;
+    }
+    let len = pairs.len() as f64;
+    let (sum_x, sum_y) = pairs
+        .iter()
+        .fold((0., 0.), |(sx, sy), &(x, y)| (sx + x, sy + y));
+    let (mean_x, mean_y) = (sum_x / len, sum_y / len);
+    let mut covariance = 0.;
+    let mut variance_x = 0.;
+    let mut variance_y = 0.;
+    for &(x, y) in pairs {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        covariance += dx * dy;
+        variance_x += dx * dx;
+        variance_y += dy * dy;
+    }
+    if variance_x > 0. && variance_y > 0. {
+        Some(covariance / (variance_x * variance_y).sqrt())
+    } else {
+        None
+    }
+}
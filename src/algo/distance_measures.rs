@@ -0,0 +1,507 @@
+//! Distance measures: eccentricity, diameter, radius, center and periphery.
+//!
+//! The *eccentricity* of a node is its largest distance to any other node.
+//! The *diameter* and *radius* of a graph are the largest and smallest
+//! eccentricity of its nodes, and its *center* and *periphery* are the nodes
+//! whose eccentricity is the radius and the diameter.
+//!
+//! Distances are measured along outgoing edges, so a directed graph must be
+//! strongly connected for its eccentricities to be finite. The functions
+//! without a prefix count the edges of paths with a breadth first search,
+//! and the `weighted_` functions sum edge costs given by a closure with
+//! Dijkstra's algorithm; edge costs must be non-negative.
+//!
+//! For large sparse undirected graphs, [`diameter_csr`](fn.diameter_csr.html)
+//! computes the diameter without computing every eccentricity.
+
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::algo::Measure;
+use crate::csr::{Csr, IndexType};
+use crate::scored::MinScored;
+use crate::visit::{EdgeRef, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+use crate::Undirected;
+
+/// \[Generic\] Return the eccentricity of `node`: the number of edges of the
+/// longest shortest path from `node` to another node.
+///
+/// Returns `None` if some node can not be reached from `node`.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::distance_measures::eccentricity;
+///
+/// let mut g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert_eq!(eccentricity(&g, 0.into()), Some(3));
+/// assert_eq!(eccentricity(&g, 1.into()), Some(2));
+///
+/// g.add_node(());
+/// assert_eq!(eccentricity(&g, 1.into()), None);
+/// ```
+pub fn eccentricity<G>(graph: G, node: G::NodeId) -> Option<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let node_count = graph.node_identifiers().count();
+    breadth_first_eccentricity(graph, node, node_count)
+}
+
+/// \[Generic\] Return the diameter of the graph: the largest eccentricity of
+/// its nodes.
+///
+/// Returns `None` if the graph is empty or not (strongly) connected. See
+/// [`eccentricity`](fn.eccentricity.html).
+///
+/// Runtime is **O(|V| (|V| + |E|))**; see
+/// [`diameter_csr`](fn.diameter_csr.html) for a faster algorithm.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::distance_measures::{diameter, radius};
+///
+/// // A path with a triangle at one end.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 1)]);
+/// assert_eq!(diameter(&g), Some(2));
+/// assert_eq!(radius(&g), Some(1));
+/// ```
+pub fn diameter<G>(graph: G) -> Option<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    eccentricities(graph)?.into_iter().map(|(_, e)| e).max()
+}
+
+/// \[Generic\] Return the radius of the graph: the smallest eccentricity of
+/// its nodes.
+///
+/// Returns `None` if the graph is empty or not (strongly) connected. See
+/// [`eccentricity`](fn.eccentricity.html).
+///
+/// Runtime is **O(|V| (|V| + |E|))**.
+pub fn radius<G>(graph: G) -> Option<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    eccentricities(graph)?.into_iter().map(|(_, e)| e).min()
+}
+
+/// \[Generic\] Return the center of the graph: the nodes whose eccentricity
+/// is the radius.
+///
+/// Returns no nodes if the graph is not (strongly) connected. See
+/// [`eccentricity`](fn.eccentricity.html).
+///
+/// Runtime is **O(|V| (|V| + |E|))**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::distance_measures::{center, periphery};
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// assert_eq!(center(&g), vec![2.into()]);
+/// assert_eq!(periphery(&g), vec![0.into(), 4.into()]);
+/// ```
+pub fn center<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    extremes(eccentricities(graph), |e, best| e < best)
+}
+
+/// \[Generic\] Return the periphery of the graph: the nodes whose
+/// eccentricity is the diameter.
+///
+/// Returns no nodes if the graph is not (strongly) connected. See
+/// [`eccentricity`](fn.eccentricity.html).
+///
+/// Runtime is **O(|V| (|V| + |E|))**.
+pub fn periphery<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    extremes(eccentricities(graph), |e, best| e > best)
+}
+
+/// \[Generic\] Return the eccentricity of `node`, measured by the sum of the
+/// costs of the edges of paths.
+///
+/// See [`eccentricity`](fn.eccentricity.html). Runtime is
+/// **O((|V| + |E|) log |V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::distance_measures::weighted_eccentricity;
+/// use petgraph::visit::EdgeRef;
+///
+/// let g = UnGraph::<(), u32>::from_edges(&[(0, 1, 5), (1, 2, 1), (0, 2, 1)]);
+/// assert_eq!(weighted_eccentricity(&g, 0.into(), |e| *e.weight()), Some(2));
+/// ```
+pub fn weighted_eccentricity<G, F, K>(graph: G, node: G::NodeId, mut edge_cost: F) -> Option<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let node_count = graph.node_identifiers().count();
+    dijkstra_eccentricity(graph, node, node_count, &mut edge_cost)
+}
+
+/// \[Generic\] Return the diameter of the graph, measured by the sum of the
+/// costs of the edges of paths.
+///
+/// See [`diameter`](fn.diameter.html). Runtime is
+/// **O(|V| (|V| + |E|) log |V|)**.
+pub fn weighted_diameter<G, F, K>(graph: G, edge_cost: F) -> Option<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let eccentricities = weighted_eccentricities(graph, edge_cost)?;
+    extreme(eccentricities.into_iter().map(|(_, e)| e), |e, best| {
+        e > best
+    })
+}
+
+/// \[Generic\] Return the radius of the graph, measured by the sum of the
+/// costs of the edges of paths.
+///
+/// See [`radius`](fn.radius.html). Runtime is
+/// **O(|V| (|V| + |E|) log |V|)**.
+pub fn weighted_radius<G, F, K>(graph: G, edge_cost: F) -> Option<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let eccentricities = weighted_eccentricities(graph, edge_cost)?;
+    extreme(eccentricities.into_iter().map(|(_, e)| e), |e, best| {
+        e < best
+    })
+}
+
+/// \[Generic\] Return the center of the graph, measured by the sum of the
+/// costs of the edges of paths.
+///
+/// See [`center`](fn.center.html). Runtime is
+/// **O(|V| (|V| + |E|) log |V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::distance_measures::{weighted_center, weighted_periphery};
+/// use petgraph::visit::EdgeRef;
+///
+/// // A path, with a long edge at one end.
+/// let g = UnGraph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 2, 1.), (2, 3, 3.)]);
+/// assert_eq!(weighted_center(&g, |e| *e.weight()), vec![2.into()]);
+/// assert_eq!(weighted_periphery(&g, |e| *e.weight()), vec![0.into(), 3.into()]);
+/// ```
+pub fn weighted_center<G, F, K>(graph: G, edge_cost: F) -> Vec<G::NodeId>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    extremes(weighted_eccentricities(graph, edge_cost), |e, best| {
+        e < best
+    })
+}
+
+/// \[Generic\] Return the periphery of the graph, measured by the sum of the
+/// costs of the edges of paths.
+///
+/// See [`periphery`](fn.periphery.html). Runtime is
+/// **O(|V| (|V| + |E|) log |V|)**.
+pub fn weighted_periphery<G, F, K>(graph: G, edge_cost: F) -> Vec<G::NodeId>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    extremes(weighted_eccentricities(graph, edge_cost), |e, best| {
+        e > best
+    })
+}
+
+/// Return the diameter of an undirected `Csr`.
+///
+/// This is [`diameter`](fn.diameter.html) for an undirected `Csr`, with the
+/// iFUB algorithm of [Crescenzi et al.][1]: a double sweep finds a lower
+/// bound and a central node, and the eccentricities of the nodes farthest
+/// from the central node are computed until the bounds meet. This needs only
+/// a few breadth first searches on most sparse graphs, and **O(|V|)** in the
+/// worst case.
+///
+/// Returns `None` if the graph is empty or not connected.
+///
+/// [1]: https://doi.org/10.1016/j.tcs.2012.09.018
+///
+/// # Example
+/// ```rust
+/// use petgraph::csr::Csr;
+/// use petgraph::Undirected;
+/// use petgraph::algo::distance_measures::diameter_csr;
+///
+/// // A cycle of length 10.
+/// let mut g = Csr::<(), (), Undirected>::with_nodes(10);
+/// for i in 0..10 {
+///     g.add_edge(i, (i + 1) % 10, ());
+/// }
+/// assert_eq!(diameter_csr(&g), Some(5));
+/// ```
+pub fn diameter_csr<N, E, Ix>(graph: &Csr<N, E, Undirected, Ix>) -> Option<usize>
+where
+    Ix: IndexType,
+{
+    let mut search = CsrSearch::new(graph);
+    if search.distance.is_empty() {
+        return None;
+    }
+
+    // Double sweep from a node of largest degree: the farthest node `a` from
+    // it, and the farthest node `b` from `a`, give a lower bound on the
+    // diameter. Every search also gives a lower bound on the eccentricity of
+    // every node, and the node with the smallest bound is a candidate for
+    // the central node; if its eccentricity is larger than the bound, a
+    // search from its farthest node improves the bounds.
+    let length = graph.node_count();
+    let start = (0..length)
+        .max_by_key(|&v| graph.neighbors_slice(Ix::new(v)).len())
+        .unwrap();
+    let (a, _) = search.run(start)?;
+    let (_, mut lower) = search.run(a)?;
+    let mut middle = 0;
+    for _ in 0..4 {
+        middle = (0..length).min_by_key(|&v| search.bound[v]).unwrap();
+        let expected = search.bound[middle];
+        let (farthest, e) = search.run(middle)?;
+        lower = lower.max(e);
+        if e == expected {
+            break;
+        }
+        let (_, e) = search.run(farthest)?;
+        lower = lower.max(e);
+    }
+
+    // Any two nodes within distance `i` of the central node are within `2 i`
+    // of each other, so a longer shortest path ends farther than `i` from
+    // it. The eccentricities are computed by decreasing distance from the
+    // central node, until the longest path found is at least twice the
+    // distance of the remaining nodes.
+    let (_, mut i) = search.run(middle)?;
+    let mut levels = vec![Vec::new(); i + 1];
+    for v in 0..length {
+        levels[search.distance[v]].push(v);
+    }
+    while lower < 2 * i {
+        for &v in &levels[i] {
+            let (_, e) = search.run(v).unwrap();
+            lower = lower.max(e);
+            if lower >= 2 * i {
+                return Some(lower);
+            }
+        }
+        i -= 1;
+    }
+    Some(lower)
+}
+
+/// Breadth first search on an undirected `Csr`.
+struct CsrSearch<'a, N, E, Ix> {
+    graph: &'a Csr<N, E, Undirected, Ix>,
+    distance: Vec<usize>,
+    /// The largest distance of each node from the start of any search, a
+    /// lower bound on its eccentricity.
+    bound: Vec<usize>,
+    queue: VecDeque<usize>,
+}
+
+impl<'a, N, E, Ix> CsrSearch<'a, N, E, Ix>
+where
+    Ix: IndexType,
+{
+    fn new(graph: &'a Csr<N, E, Undirected, Ix>) -> Self {
+        CsrSearch {
+            graph,
+            distance: vec![usize::MAX; graph.node_count()],
+            bound: vec![0; graph.node_count()],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Search from `start`, and return the last node found and its distance,
+    /// or `None` if some node is not reached.
+    fn run(&mut self, start: usize) -> Option<(usize, usize)> {
+        for d in &mut self.distance {
+            *d = usize::MAX;
+        }
+        self.distance[start] = 0;
+        self.queue.push_back(start);
+        let mut last = start;
+        let mut reached = 0;
+        while let Some(v) = self.queue.pop_front() {
+            last = v;
+            reached += 1;
+            for &w in self.graph.neighbors_slice(Ix::new(v)) {
+                let w = w.index();
+                if self.distance[w] == usize::MAX {
+                    self.distance[w] = self.distance[v] + 1;
+                    self.queue.push_back(w);
+                }
+            }
+        }
+        if reached == self.distance.len() {
+            for (b, &d) in self.bound.iter_mut().zip(&self.distance) {
+                *b = (*b).max(d);
+            }
+            Some((last, self.distance[last]))
+        } else {
+            None
+        }
+    }
+}
+
+/// Return the eccentricity of every node, or `None` if the graph is empty or
+/// some eccentricity is infinite.
+fn eccentricities<G>(graph: G) -> Option<Vec<(G::NodeId, usize)>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let node_count = graph.node_identifiers().count();
+    if node_count == 0 {
+        return None;
+    }
+    graph
+        .node_identifiers()
+        .map(|n| breadth_first_eccentricity(graph, n, node_count).map(|e| (n, e)))
+        .collect()
+}
+
+fn weighted_eccentricities<G, F, K>(graph: G, mut edge_cost: F) -> Option<Vec<(G::NodeId, K)>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let node_count = graph.node_identifiers().count();
+    if node_count == 0 {
+        return None;
+    }
+    graph
+        .node_identifiers()
+        .map(|n| dijkstra_eccentricity(graph, n, node_count, &mut edge_cost).map(|e| (n, e)))
+        .collect()
+}
+
+fn breadth_first_eccentricity<G>(graph: G, start: G::NodeId, node_count: usize) -> Option<usize>
+where
+    G: IntoNeighbors + NodeIndexable,
+{
+    let mut distance = vec![usize::MAX; graph.node_bound()];
+    let mut queue = VecDeque::new();
+    distance[graph.to_index(start)] = 0;
+    queue.push_back(start);
+    let mut reached = 0;
+    let mut eccentricity = 0;
+    while let Some(v) = queue.pop_front() {
+        reached += 1;
+        eccentricity = distance[graph.to_index(v)];
+        for w in graph.neighbors(v) {
+            let i = graph.to_index(w);
+            if distance[i] == usize::MAX {
+                distance[i] = eccentricity + 1;
+                queue.push_back(w);
+            }
+        }
+    }
+    if reached == node_count {
+        Some(eccentricity)
+    } else {
+        None
+    }
+}
+
+fn dijkstra_eccentricity<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    node_count: usize,
+    edge_cost: &mut F,
+) -> Option<K>
+where
+    G: IntoEdges + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let mut distance: Vec<Option<K>> = vec![None; graph.node_bound()];
+    let mut finished = vec![false; graph.node_bound()];
+    let mut heap = BinaryHeap::new();
+    distance[graph.to_index(start)] = Some(K::default());
+    heap.push(MinScored(K::default(), start));
+    let mut reached = 0;
+    let mut eccentricity = K::default();
+    while let Some(MinScored(d, v)) = heap.pop() {
+        if finished[graph.to_index(v)] {
+            continue;
+        }
+        finished[graph.to_index(v)] = true;
+        reached += 1;
+        eccentricity = d;
+        for edge in graph.edges(v) {
+            let w = graph.to_index(edge.target());
+            let next = d + edge_cost(edge);
+            let shorter = match distance[w] {
+                None => true,
+                Some(current) => next < current,
+            };
+            if !finished[w] && shorter {
+                distance[w] = Some(next);
+                heap.push(MinScored(next, edge.target()));
+            }
+        }
+    }
+    if reached == node_count {
+        Some(eccentricity)
+    } else {
+        None
+    }
+}
+
+/// Return the value that is `better` than all others.
+fn extreme<K, I, F>(values: I, better: F) -> Option<K>
+where
+    I: IntoIterator<Item = K>,
+    F: Fn(&K, &K) -> bool,
+{
+    values.into_iter().fold(None, |best, e| match best {
+        Some(ref b) if !better(&e, b) => best,
+        _ => Some(e),
+    })
+}
+
+/// Return the nodes whose eccentricity is the one that is `better` than all
+/// others.
+fn extremes<N, K, F>(eccentricities: Option<Vec<(N, K)>>, better: F) -> Vec<N>
+where
+    K: PartialOrd + Copy,
+    F: Fn(&K, &K) -> bool,
+{
+    let eccentricities = match eccentricities {
+        Some(eccentricities) => eccentricities,
+        None => return Vec::new(),
+    };
+    match extreme(eccentricities.iter().map(|&(_, e)| e), better) {
+        Some(best) => eccentricities
+            .into_iter()
+            .filter(|&(_, e)| e == best)
+            .map(|(n, _)| n)
+            .collect(),
+        None => Vec::new(),
+    }
+}
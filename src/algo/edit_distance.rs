@@ -0,0 +1,709 @@
+//! Graph edit distance, exact and approximate.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::scored::MinScored;
+use crate::visit::{
+    EdgeRef, GraphBase, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+};
+
+/// One step of an edit path, which turns the first graph into the second.
+///
+/// Nodes and edges of the first graph are deleted or substituted, and nodes
+/// and edges of the second graph are inserted or substituted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EditOperation<N0, N1, E0, E1> {
+    /// Replace a node of the first graph with a node of the second graph.
+    NodeSubstitution(N0, N1),
+    /// Delete a node of the first graph.
+    NodeDeletion(N0),
+    /// Insert a node of the second graph.
+    NodeInsertion(N1),
+    /// Replace an edge of the first graph with an edge of the second graph.
+    EdgeSubstitution(E0, E1),
+    /// Delete an edge of the first graph.
+    EdgeDeletion(E0),
+    /// Insert an edge of the second graph.
+    EdgeInsertion(E1),
+}
+
+/// The operations of an edit path from `G0` to `G1`.
+pub type EditPath<G0, G1> = Vec<
+    EditOperation<
+        <G0 as GraphBase>::NodeId,
+        <G1 as GraphBase>::NodeId,
+        <G0 as GraphBase>::EdgeId,
+        <G1 as GraphBase>::EdgeId,
+    >,
+>;
+
+/// \[Generic\] Compute the graph edit distance between `g0` and `g1`: the
+/// least total cost of a sequence of node and edge deletions, insertions and
+/// substitutions that turns `g0` into a graph isomorphic to `g1`.
+///
+/// `node_cost` is called with `(Some(a), Some(b))` for the cost of
+/// substituting node weight `a` with `b`, with `(Some(a), None)` for the
+/// cost of deleting `a`, and with `(None, Some(b))` for the cost of inserting
+/// `b`; `edge_cost` likewise for edge weights. Deleting a node also deletes
+/// its edges, which are charged separately. Costs must be finite and
+/// non-negative. Parallel edges are matched among each other, and the graphs
+/// must be both directed or both undirected.
+///
+/// Returns the distance and an optimal edit path: the node operations, in the
+/// order of the nodes of `g0` and then of the inserted nodes of `g1`,
+/// followed by the edge operations in the same order.
+///
+/// The distance is found with an A* search over the assignments of the nodes
+/// of `g0` to nodes of `g1`, so it is only practical for small graphs,
+/// with a dozen nodes or so. Use
+/// [`approximate_graph_edit_distance`](fn.approximate_graph_edit_distance.html)
+/// for larger graphs.
+///
+/// Runtime is exponential in the number of nodes.
+///
+/// **Panics** if a cost is negative or not finite.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::{graph_edit_distance, EditOperation};
+///
+/// // Two versions of a workflow: a lint step runs before the tests.
+/// let mut v1 = Graph::<&str, ()>::new();
+/// let fetch = v1.add_node("fetch");
+/// let build = v1.add_node("build");
+/// let test = v1.add_node("test");
+/// v1.extend_with_edges(&[(fetch, build), (build, test)]);
+///
+/// let mut v2 = v1.clone();
+/// let lint = v2.add_node("lint");
+/// let old = v2.find_edge(build, test).unwrap();
+/// v2.remove_edge(old);
+/// v2.extend_with_edges(&[(build, lint), (lint, test)]);
+///
+/// // Adding or removing a step or a dependency costs 1, and steps are only
+/// // matched by name.
+/// let node_cost = |a: Option<&&str>, b: Option<&&str>| match (a, b) {
+///     (Some(a), Some(b)) if a != b => 10.,
+///     (Some(_), Some(_)) => 0.,
+///     _ => 1.,
+/// };
+/// let edge_cost = |a: Option<&()>, b: Option<&()>| match (a, b) {
+///     (Some(_), Some(_)) => 0.,
+///     _ => 1.,
+/// };
+///
+/// let (cost, path) = graph_edit_distance(&v1, &v2, node_cost, edge_cost);
+/// assert_eq!(cost, 4.);
+/// assert!(path.contains(&EditOperation::NodeInsertion(lint)));
+/// assert!(path.contains(&EditOperation::EdgeDeletion(old)));
+/// ```
+pub fn graph_edit_distance<G0, G1, NC, EC>(
+    g0: G0,
+    g1: G1,
+    node_cost: NC,
+    edge_cost: EC,
+) -> (f64, EditPath<G0, G1>)
+where
+    G0: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    NC: FnMut(Option<&G0::NodeWeight>, Option<&G1::NodeWeight>) -> f64,
+    EC: FnMut(Option<&G0::EdgeWeight>, Option<&G1::EdgeWeight>) -> f64,
+{
+    let mut model = EditModel::new(g0, g1, node_cost, edge_cost);
+    let images = model.exact();
+    model.edit_path(&images)
+}
+
+/// \[Generic\] Compute an upper bound of the graph edit distance between
+/// `g0` and `g1`, with the bipartite approximation of Riesen and Bunke [1].
+///
+/// The costs are given like for
+/// [`graph_edit_distance`](fn.graph_edit_distance.html). Each node is
+/// assigned to a node of the other graph, or deleted or inserted, by solving
+/// a linear assignment problem on the costs of the nodes and their incident
+/// edges. Returns the cost of the edit path that this node assignment
+/// implies, and the path.
+///
+/// The result is often close to the distance, and it is exact when the best
+/// node assignment can be read off the neighborhoods of the nodes, as for
+/// isomorphic graphs with distinct node weights.
+///
+/// Runtime is **O((|V₀| + |V₁|)³ + |V₀| |V₁| d³)**, where **d** is the
+/// largest degree.
+///
+/// **Panics** if a cost is negative or not finite.
+///
+/// [1]: https://doi.org/10.1016/j.imavis.2008.04.004
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::approximate_graph_edit_distance;
+///
+/// // A path of four nodes, and the same path closed to a cycle.
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// let cycle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+///
+/// let node_cost = |a: Option<&()>, b: Option<&()>| if a.is_some() && b.is_some() { 0. } else { 1. };
+/// let edge_cost = node_cost;
+/// let (cost, path) = approximate_graph_edit_distance(&path, &cycle, node_cost, edge_cost);
+/// assert!(cost >= 1.);
+/// assert_eq!(path.len(), 4 + 4);
+/// ```
+pub fn approximate_graph_edit_distance<G0, G1, NC, EC>(
+    g0: G0,
+    g1: G1,
+    node_cost: NC,
+    edge_cost: EC,
+) -> (f64, EditPath<G0, G1>)
+where
+    G0: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    NC: FnMut(Option<&G0::NodeWeight>, Option<&G1::NodeWeight>) -> f64,
+    EC: FnMut(Option<&G0::EdgeWeight>, Option<&G1::EdgeWeight>) -> f64,
+{
+    let mut model = EditModel::new(g0, g1, node_cost, edge_cost);
+    let images = model.bipartite();
+    model.edit_path(&images)
+}
+
+type Operations<R0, R1, E0, E1> = Vec<
+    EditOperation<
+        <R0 as NodeRef>::NodeId,
+        <R1 as NodeRef>::NodeId,
+        <E0 as EdgeRef>::EdgeId,
+        <E1 as EdgeRef>::EdgeId,
+    >,
+>;
+
+/// A graph with compact node and edge indices.
+struct EditGraph<R, E> {
+    nodes: Vec<R>,
+    edges: Vec<E>,
+    endpoints: Vec<[usize; 2]>,
+    /// The outgoing edges of each node; all incident edges if undirected.
+    out: Vec<Vec<usize>>,
+    /// The incoming edges of each node; empty if undirected.
+    ins: Vec<Vec<usize>>,
+    /// The edges between each pair of nodes, in order if undirected.
+    pairs: HashMap<[usize; 2], Vec<usize>>,
+    directed: bool,
+}
+
+fn edit_graph<G>(g: G) -> EditGraph<G::NodeRef, G::EdgeRef>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    let mut nodes: Vec<_> = g.node_references().collect();
+    nodes.sort_by_key(|n| g.to_index(n.id()));
+    let mut index = vec![0; g.node_bound()];
+    for (i, n) in nodes.iter().enumerate() {
+        index[g.to_index(n.id())] = i;
+    }
+
+    let directed = g.is_directed();
+    let mut graph = EditGraph {
+        out: vec![Vec::new(); nodes.len()],
+        ins: vec![Vec::new(); nodes.len()],
+        nodes,
+        edges: Vec::new(),
+        endpoints: Vec::new(),
+        pairs: HashMap::new(),
+        directed,
+    };
+    for edge in g.edge_references() {
+        let e = graph.edges.len();
+        let a = index[g.to_index(edge.source())];
+        let b = index[g.to_index(edge.target())];
+        graph.edges.push(edge);
+        graph.endpoints.push([a, b]);
+        graph.out[a].push(e);
+        if directed {
+            graph.ins[b].push(e);
+        } else if a != b {
+            graph.out[b].push(e);
+        }
+        graph.pairs.entry(graph.key(a, b)).or_default().push(e);
+    }
+    graph
+}
+
+impl<R, E> EditGraph<R, E> {
+    fn key(&self, a: usize, b: usize) -> [usize; 2] {
+        if self.directed || a <= b {
+            [a, b]
+        } else {
+            [b, a]
+        }
+    }
+
+    /// Return the edges from `a` to `b`.
+    fn edges_between(&self, a: usize, b: usize) -> &[usize] {
+        self.pairs.get(&self.key(a, b)).map_or(&[], |es| &es[..])
+    }
+}
+
+/// The two graphs and the costs of editing them.
+struct EditModel<R0, R1, E0, E1, NC, EC> {
+    g0: EditGraph<R0, E0>,
+    g1: EditGraph<R1, E1>,
+    node_cost: NC,
+    edge_cost: EC,
+}
+
+/// A node of the A* search tree: the first `depth` nodes of the search order
+/// are assigned, the last of them to `image`. One level below the last node,
+/// the remaining nodes of `g1` are inserted.
+struct Partial {
+    parent: usize,
+    image: Option<usize>,
+    depth: usize,
+    cost: f64,
+}
+
+impl<R0, R1, E0, E1, NC, EC> EditModel<R0, R1, E0, E1, NC, EC>
+where
+    R0: NodeRef,
+    R1: NodeRef,
+    E0: EdgeRef,
+    E1: EdgeRef,
+    NC: FnMut(Option<&R0::Weight>, Option<&R1::Weight>) -> f64,
+    EC: FnMut(Option<&E0::Weight>, Option<&E1::Weight>) -> f64,
+{
+    fn new<G0, G1>(g0: G0, g1: G1, node_cost: NC, edge_cost: EC) -> Self
+    where
+        G0: IntoNodeReferences<NodeRef = R0>
+            + IntoEdgeReferences<EdgeRef = E0>
+            + NodeIndexable
+            + GraphProp,
+        G1: IntoNodeReferences<NodeRef = R1>
+            + IntoEdgeReferences<EdgeRef = E1>
+            + NodeIndexable
+            + GraphProp,
+    {
+        EditModel {
+            g0: edit_graph(g0),
+            g1: edit_graph(g1),
+            node_cost,
+            edge_cost,
+        }
+    }
+
+    fn node_cost(&mut self, u: Option<usize>, v: Option<usize>) -> f64 {
+        let EditModel {
+            ref g0,
+            ref g1,
+            ref mut node_cost,
+            ..
+        } = *self;
+        let a = u.map(|u| g0.nodes[u].weight());
+        let b = v.map(|v| g1.nodes[v].weight());
+        checked(node_cost(a, b))
+    }
+
+    /// Return the cost of the best assignment between the edges `es0` of `g0`
+    /// and `es1` of `g1`, and the edge of `es1` assigned to each of `es0`.
+    fn assign_edges(&mut self, es0: &[usize], es1: &[usize]) -> (f64, Vec<Option<usize>>) {
+        let EditModel {
+            ref g0,
+            ref g1,
+            ref mut edge_cost,
+            ..
+        } = *self;
+        let (w0, w1) = (&g0.edges, &g1.edges);
+        let (cost, images) = edit_assignment(es0.len(), es1.len(), |i, j| {
+            let a = i.map(|i| w0[es0[i]].weight());
+            let b = j.map(|j| w1[es1[j]].weight());
+            checked(edge_cost(a, b))
+        });
+        let images = images.into_iter().map(|j| j.map(|j| es1[j])).collect();
+        (cost, images)
+    }
+
+    /// Return the cost of editing the edges between `a` and `b` in `g0` into
+    /// the edges between their images.
+    fn pair_cost(&mut self, [a, b]: [usize; 2], [x, y]: [Option<usize>; 2]) -> f64 {
+        let es0 = self.g0.edges_between(a, b).to_vec();
+        let es1 = match (x, y) {
+            (Some(x), Some(y)) => self.g1.edges_between(x, y).to_vec(),
+            _ => Vec::new(),
+        };
+        self.assign_edges(&es0, &es1).0
+    }
+
+    /// Return the cost of editing the edges incident to `u` into the edges
+    /// incident to `v`, regardless of their other endpoints.
+    fn star_cost(&mut self, u: Option<usize>, v: Option<usize>) -> f64 {
+        let (out0, ins0) = match u {
+            Some(u) => (self.g0.out[u].clone(), self.g0.ins[u].clone()),
+            None => (Vec::new(), Vec::new()),
+        };
+        let (out1, ins1) = match v {
+            Some(v) => (self.g1.out[v].clone(), self.g1.ins[v].clone()),
+            None => (Vec::new(), Vec::new()),
+        };
+        self.assign_edges(&out0, &out1).0 + self.assign_edges(&ins0, &ins1).0
+    }
+
+    /// Return the costs of assigning each node of `g0` to each node of `g1`,
+    /// of deleting each node of `g0` and of inserting each node of `g1`,
+    /// including `edge_weight` times the costs of editing their edges.
+    fn node_costs(&mut self, edge_weight: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let (n0, n1) = (self.g0.nodes.len(), self.g1.nodes.len());
+        let mut sub = Vec::with_capacity(n0 * n1);
+        for u in 0..n0 {
+            for v in 0..n1 {
+                let cost = self.node_cost(Some(u), Some(v))
+                    + edge_weight * self.star_cost(Some(u), Some(v));
+                sub.push(cost);
+            }
+        }
+        let del = (0..n0)
+            .map(|u| self.node_cost(Some(u), None) + edge_weight * self.star_cost(Some(u), None))
+            .collect();
+        let ins = (0..n1)
+            .map(|v| self.node_cost(None, Some(v)) + edge_weight * self.star_cost(None, Some(v)))
+            .collect();
+        (sub, del, ins)
+    }
+
+    /// Return the image of each node of `g0` in the Riesen–Bunke assignment.
+    fn bipartite(&mut self) -> Vec<Option<usize>> {
+        let (n0, n1) = (self.g0.nodes.len(), self.g1.nodes.len());
+        let (sub, del, ins) = self.node_costs(1.);
+        edit_assignment(n0, n1, |u, v| match (u, v) {
+            (Some(u), Some(v)) => sub[u * n1 + v],
+            (Some(u), None) => del[u],
+            (None, Some(v)) => ins[v],
+            (None, None) => 0.,
+        })
+        .1
+    }
+
+    /// Return the image of each node of `g0` in an optimal edit path.
+    fn exact(&mut self) -> Vec<Option<usize>> {
+        let (n0, n1) = (self.g0.nodes.len(), self.g1.nodes.len());
+        // The bipartite solution bounds the search from above.
+        let upper_images = self.bipartite();
+        let upper_bound = self.edit_path(&upper_images).0;
+
+        // Each edge has two endpoints, so half the cost of editing the edges
+        // incident to the remaining nodes is a lower bound.
+        let (sub, del, ins) = self.node_costs(0.5);
+        let heuristic = |rows: &[usize], used: &[bool]| {
+            let cols: Vec<usize> = (0..n1).filter(|&v| !used[v]).collect();
+            edit_assignment(rows.len(), cols.len(), |i, j| match (i, j) {
+                (Some(i), Some(j)) => sub[rows[i] * n1 + cols[j]],
+                (Some(i), None) => del[rows[i]],
+                (None, Some(j)) => ins[cols[j]],
+                (None, None) => 0.,
+            })
+            .0
+        };
+
+        // Assign nodes with many edges first.
+        let mut order: Vec<usize> = (0..n0).collect();
+        order.sort_by_key(|&u| Reverse(self.g0.out[u].len() + self.g0.ins[u].len()));
+
+        let mut tree = vec![Partial {
+            parent: usize::MAX,
+            image: None,
+            depth: 0,
+            cost: 0.,
+        }];
+        let mut heap = BinaryHeap::new();
+        heap.push(MinScored(0., 0));
+        while let Some(MinScored(_, ix)) = heap.pop() {
+            let depth = tree[ix].depth;
+            let mut images = vec![None; depth.min(n0)];
+            let mut node = ix;
+            if depth > n0 {
+                node = tree[node].parent;
+            }
+            for image in images.iter_mut().rev() {
+                *image = tree[node].image;
+                node = tree[node].parent;
+            }
+            if depth > n0 {
+                let mut result = vec![None; n0];
+                for (&u, &image) in order.iter().zip(&images) {
+                    result[u] = image;
+                }
+                return result;
+            }
+
+            let mut used = vec![false; n1];
+            for &v in images.iter().flatten() {
+                used[v] = true;
+            }
+            let cost = tree[ix].cost;
+            if depth == n0 {
+                // Insert the remaining nodes of g1 and their edges.
+                let mut rest = 0.;
+                for v in (0..n1).filter(|&v| !used[v]) {
+                    rest += self.node_cost(None, Some(v));
+                }
+                for e in 0..self.g1.edges.len() {
+                    let [x, y] = self.g1.endpoints[e];
+                    if !used[x] || !used[y] {
+                        rest += checked((self.edge_cost)(None, Some(self.g1.edges[e].weight())));
+                    }
+                }
+                tree.push(Partial {
+                    parent: ix,
+                    image: None,
+                    depth: depth + 1,
+                    cost: cost + rest,
+                });
+                heap.push(MinScored(cost + rest, tree.len() - 1));
+                continue;
+            }
+
+            let u = order[depth];
+            let candidates: Vec<_> = (0..n1).filter(|&v| !used[v]).map(Some).collect();
+            for x in candidates.into_iter().chain(Some(None)) {
+                let mut g = cost + self.node_cost(Some(u), x);
+                for (&w, &y) in order[..depth].iter().zip(&images) {
+                    g += self.pair_cost([u, w], [x, y]);
+                    if self.g0.directed {
+                        g += self.pair_cost([w, u], [y, x]);
+                    }
+                }
+                g += self.pair_cost([u, u], [x, x]);
+                if let Some(x) = x {
+                    used[x] = true;
+                }
+                let f = g + heuristic(&order[depth + 1..], &used);
+                if let Some(x) = x {
+                    used[x] = false;
+                }
+                if f <= upper_bound {
+                    tree.push(Partial {
+                        parent: ix,
+                        image: x,
+                        depth: depth + 1,
+                        cost: g,
+                    });
+                    heap.push(MinScored(f, tree.len() - 1));
+                }
+            }
+        }
+        // Rounding pruned every path as good as the upper bound.
+        upper_images
+    }
+
+    /// Return the cost and the operations of the edit path where each node
+    /// of `g0` is substituted with its image, or deleted.
+    fn edit_path(&mut self, images: &[Option<usize>]) -> (f64, Operations<R0, R1, E0, E1>) {
+        let (n1, m0, m1) = (
+            self.g1.nodes.len(),
+            self.g0.edges.len(),
+            self.g1.edges.len(),
+        );
+        let mut preimages = vec![None; n1];
+        for (u, &image) in images.iter().enumerate() {
+            if let Some(v) = image {
+                preimages[v] = Some(u);
+            }
+        }
+
+        let mut edge_images = vec![None; m0];
+        let mut covered = vec![false; m1];
+        let pairs: Vec<_> = self
+            .g0
+            .pairs
+            .iter()
+            .map(|(&key, es0)| (key, es0.clone()))
+            .collect();
+        for ([a, b], es0) in pairs {
+            let es1 = match (images[a], images[b]) {
+                (Some(x), Some(y)) => self.g1.edges_between(x, y).to_vec(),
+                _ => continue,
+            };
+            let (_, assigned) = self.assign_edges(&es0, &es1);
+            for (&e, f) in es0.iter().zip(assigned) {
+                edge_images[e] = f;
+                if let Some(f) = f {
+                    covered[f] = true;
+                }
+            }
+        }
+
+        let mut cost = 0.;
+        let mut path = Vec::new();
+        for (u, &image) in images.iter().enumerate() {
+            cost += self.node_cost(Some(u), image);
+            let id = self.g0.nodes[u].id();
+            path.push(match image {
+                Some(v) => EditOperation::NodeSubstitution(id, self.g1.nodes[v].id()),
+                None => EditOperation::NodeDeletion(id),
+            });
+        }
+        for (v, preimage) in preimages.into_iter().enumerate() {
+            if preimage.is_none() {
+                cost += self.node_cost(None, Some(v));
+                path.push(EditOperation::NodeInsertion(self.g1.nodes[v].id()));
+            }
+        }
+        for (e, image) in edge_images.into_iter().enumerate() {
+            let edge = self.g0.edges[e];
+            match image {
+                Some(f) => {
+                    let other = self.g1.edges[f];
+                    cost += checked((self.edge_cost)(Some(edge.weight()), Some(other.weight())));
+                    path.push(EditOperation::EdgeSubstitution(edge.id(), other.id()));
+                }
+                None => {
+                    cost += checked((self.edge_cost)(Some(edge.weight()), None));
+                    path.push(EditOperation::EdgeDeletion(edge.id()));
+                }
+            }
+        }
+        for (f, covered) in covered.into_iter().enumerate() {
+            if !covered {
+                let other = self.g1.edges[f];
+                cost += checked((self.edge_cost)(None, Some(other.weight())));
+                path.push(EditOperation::EdgeInsertion(other.id()));
+            }
+        }
+        (cost, path)
+    }
+}
+
+fn checked(cost: f64) -> f64 {
+    assert!(
+        cost >= 0. && cost.is_finite(),
+        "edit costs must be finite and non-negative"
+    );
+    cost
+}
+
+/// Assign each of `n` items to one of `m` others, or delete it, where each
+/// of the others that is not assigned to is inserted, at the least total cost.
+/// `cost` is called like the public cost functions, with item indices.
+/// Return the cost and the item assigned to each of the `n` items.
+fn edit_assignment<C>(n: usize, m: usize, mut cost: C) -> (f64, Vec<Option<usize>>)
+where
+    C: FnMut(Option<usize>, Option<usize>) -> f64,
+{
+    if n == 0 {
+        return ((0..m).map(|j| cost(None, Some(j))).sum(), Vec::new());
+    }
+    if m == 0 {
+        return ((0..n).map(|i| cost(Some(i), None)).sum(), vec![None; n]);
+    }
+    if n == 1 && m == 1 {
+        let sub = cost(Some(0), Some(0));
+        let indel = cost(Some(0), None) + cost(None, Some(0));
+        return if sub <= indel {
+            (sub, vec![Some(0)])
+        } else {
+            (indel, vec![None])
+        };
+    }
+
+    // The square matrix of Riesen and Bunke: substitutions on the top left,
+    // deletions on the diagonal of the top right, insertions on the diagonal
+    // of the bottom left, and zeros on the bottom right.
+    let size = n + m;
+    let mut costs = vec![0.; size * size];
+    for i in 0..n {
+        let row = &mut costs[i * size..(i + 1) * size];
+        for (j, c) in row[..m].iter_mut().enumerate() {
+            *c = cost(Some(i), Some(j));
+        }
+        for c in &mut row[m..] {
+            *c = f64::INFINITY;
+        }
+        row[m + i] = cost(Some(i), None);
+    }
+    for j in 0..m {
+        let row = &mut costs[(n + j) * size..(n + j + 1) * size];
+        for c in &mut row[..m] {
+            *c = f64::INFINITY;
+        }
+        row[j] = cost(None, Some(j));
+    }
+
+    let assignment = min_cost_assignment(size, &costs);
+    let total = assignment
+        .iter()
+        .enumerate()
+        .map(|(i, &j)| costs[i * size + j])
+        .sum();
+    let images = assignment[..n]
+        .iter()
+        .map(|&j| if j < m { Some(j) } else { None })
+        .collect();
+    (total, images)
+}
+
+/// Solve the linear assignment problem for the `n × n` matrix `costs`, stored
+/// by rows, with the Hungarian method. Return the column assigned to each row.
+///
+/// Costs may be infinite, as long as some assignment has a finite cost.
+fn min_cost_assignment(n: usize, costs: &[f64]) -> Vec<usize> {
+    // Rows and columns are numbered from 1; column 0 is a sentinel.
+    let mut row_potential = vec![0.; n + 1];
+    let mut col_potential = vec![0.; n + 1];
+    let mut matched = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+    for i in 1..=n {
+        matched[0] = i;
+        let mut j0 = 0;
+        let mut slack = vec![f64::INFINITY; n + 1];
+        let mut visited = vec![false; n + 1];
+        loop {
+            visited[j0] = true;
+            let i0 = matched[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if visited[j] {
+                    continue;
+                }
+                let cost = costs[(i0 - 1) * n + j - 1] - row_potential[i0] - col_potential[j];
+                if cost < slack[j] {
+                    slack[j] = cost;
+                    way[j] = j0;
+                }
+                if slack[j] < delta {
+                    delta = slack[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=n {
+                if visited[j] {
+                    row_potential[matched[j]] += delta;
+                    col_potential[j] -= delta;
+                } else {
+                    slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if matched[j0] == 0 {
+                break;
+            }
+        }
+        // Flip the augmenting path.
+        while j0 != 0 {
+            let j1 = way[j0];
+            matched[j0] = matched[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for (j, &i) in matched.iter().enumerate().skip(1) {
+        assignment[i - 1] = j - 1;
+    }
+    assignment
+}
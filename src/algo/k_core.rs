@@ -0,0 +1,204 @@
+//! Core decomposition (Batagelj–Zaversnik).
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::visit::{GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeFiltered, NodeIndexable};
+
+/// \[Generic\] Return the *core number* of every node.
+///
+/// The *k-core* of a graph is its largest subgraph in which every node has
+/// degree at least **k**, and the core number of a node is the largest **k**
+/// such that the node is part of the k-core.
+///
+/// The degree of a node in a directed graph is the sum of its in-degree and
+/// out-degree. Self loops are ignored, and parallel edges are counted with
+/// their multiplicity.
+///
+/// This uses the bin sort algorithm of [Batagelj and Zaversnik][1], with a
+/// runtime of **O(|V| + |E|)**.
+///
+/// [1]: https://arxiv.org/abs/cs/0310049
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::core_number;
+///
+/// // A triangle with a pendant edge.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+///
+/// let cores = core_number(&g);
+/// assert_eq!(cores[&0.into()], 2);
+/// assert_eq!(cores[&2.into()], 2);
+/// assert_eq!(cores[&3.into()], 1);
+/// ```
+pub fn core_number<G>(graph: G) -> HashMap<G::NodeId, usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let cores = Cores::new(graph);
+    cores.nodes.into_iter().zip(cores.core).collect()
+}
+
+/// \[Generic\] Return a *degeneracy ordering* of the nodes.
+///
+/// The nodes are in the order in which they are removed by repeatedly
+/// removing a node of smallest degree, so that every node has at most
+/// **d** neighbors later in the order, where **d** is the degeneracy of the
+/// graph (its largest core number). See
+/// [`core_number`](fn.core_number.html) for how degrees are counted.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::degeneracy_ordering;
+///
+/// // A triangle with a pendant edge: the pendant node goes first.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+///
+/// let order = degeneracy_ordering(&g);
+/// assert_eq!(order.len(), 4);
+/// assert_eq!(order[0], 3.into());
+/// ```
+pub fn degeneracy_ordering<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let cores = Cores::new(graph);
+    cores.vert.iter().map(|&v| cores.nodes[v]).collect()
+}
+
+/// \[Generic\] Return the *k-core* of the graph, as a view of the nodes with
+/// a core number of at least `k`.
+///
+/// See [`core_number`](fn.core_number.html) for how degrees are counted.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::k_core;
+/// use petgraph::visit::IntoNodeIdentifiers;
+///
+/// // A triangle with a pendant edge.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+///
+/// let core = k_core(&g, 2);
+/// let nodes: Vec<_> = core.node_identifiers().collect();
+/// assert_eq!(nodes, vec![0.into(), 1.into(), 2.into()]);
+/// ```
+pub fn k_core<G>(graph: G, k: usize) -> NodeFiltered<G, HashSet<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let cores = Cores::new(graph);
+    let nodes = cores
+        .nodes
+        .iter()
+        .zip(&cores.core)
+        .filter(|&(_, &c)| c >= k)
+        .map(|(&n, _)| n)
+        .collect();
+    NodeFiltered(graph, nodes)
+}
+
+struct Cores<N> {
+    nodes: Vec<N>,
+    /// The core number of each node.
+    core: Vec<usize>,
+    /// The nodes sorted by core number, which is a degeneracy ordering.
+    vert: Vec<usize>,
+}
+
+impl<N> Cores<N>
+where
+    N: Copy,
+{
+    fn new<G>(graph: G) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    {
+        let mut index = vec![::std::usize::MAX; graph.node_bound()];
+        let mut nodes = Vec::new();
+        for n in graph.node_identifiers() {
+            index[graph.to_index(n)] = nodes.len();
+            nodes.push(n);
+        }
+        let length = nodes.len();
+        let mut neighbors = vec![Vec::new(); length];
+        for (u, &n) in nodes.iter().enumerate() {
+            for m in graph.neighbors(n) {
+                let v = index[graph.to_index(m)];
+                if u == v {
+                    continue;
+                }
+                neighbors[u].push(v);
+                // Undirected graphs report every edge from both endpoints.
+                if graph.is_directed() {
+                    neighbors[v].push(u);
+                }
+            }
+        }
+
+        // Sort the nodes into bins by degree: `bin[d]` is the position in
+        // `vert` of the first node of degree `d`, and `pos[v]` is the
+        // position of `v` in `vert`.
+        let mut degree: Vec<_> = neighbors.iter().map(Vec::len).collect();
+        let max_degree = degree.iter().cloned().max().unwrap_or(0);
+        let mut bin = vec![0; max_degree + 1];
+        for &d in &degree {
+            bin[d] += 1;
+        }
+        let mut start = 0;
+        for b in &mut bin {
+            let count = *b;
+            *b = start;
+            start += count;
+        }
+        let mut vert = vec![0; length];
+        let mut pos = vec![0; length];
+        for v in 0..length {
+            pos[v] = bin[degree[v]];
+            vert[pos[v]] = v;
+            bin[degree[v]] += 1;
+        }
+        for d in (1..bin.len()).rev() {
+            bin[d] = bin[d - 1];
+        }
+        bin[0] = 0;
+
+        // Remove the nodes in order. Decrementing the degree of a neighbor
+        // swaps it with the first node of its bin, and then moves the start
+        // of that bin past it, into the bin below.
+        for i in 0..length {
+            let v = vert[i];
+            for &u in &neighbors[v] {
+                if degree[u] > degree[v] {
+                    let du = degree[u];
+                    let pu = pos[u];
+                    let pw = bin[du];
+                    let w = vert[pw];
+                    if u != w {
+                        vert.swap(pu, pw);
+                        pos[u] = pw;
+                        pos[w] = pu;
+                    }
+                    bin[du] += 1;
+                    degree[u] -= 1;
+                }
+            }
+        }
+
+        Cores {
+            nodes,
+            core: degree,
+            vert,
+        }
+    }
+}
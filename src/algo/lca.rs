@@ -0,0 +1,256 @@
+//! Lowest common ancestors in rooted trees and directed acyclic graphs.
+//!
+//! In a rooted tree, the *lowest common ancestor* of the nodes **A** and **B**
+//! is the node farthest from the root that has both **A** and **B** as
+//! descendants, where every node is a descendant of itself.
+//!
+//! In a directed acyclic graph two nodes can have several lowest common
+//! ancestors: common ancestors that have no other common ancestor as a
+//! descendant.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::visit::{IntoNeighbors, IntoNeighborsDirected, VisitMap, Visitable};
+use crate::Direction::{Incoming, Outgoing};
+
+/// Lowest common ancestor queries on a rooted tree.
+///
+/// The tree is preprocessed into an Euler tour with a sparse table of range
+/// minima, in **O(|V| log |V|)** time and space, after which each query
+/// takes **O(1)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::lca::LowestCommonAncestors;
+///
+/// //       r
+/// //      / \
+/// //     a   b
+/// //    / \
+/// //   c   d
+/// let mut g = Graph::<&str, ()>::new();
+/// let r = g.add_node("r");
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// let d = g.add_node("d");
+/// g.extend_with_edges(&[(r, a), (r, b), (a, c), (a, d)]);
+///
+/// let lca = LowestCommonAncestors::new(&g, r);
+/// assert_eq!(lca.lca(c, d), Some(a));
+/// assert_eq!(lca.lca(c, b), Some(r));
+/// assert_eq!(lca.lca(a, d), Some(a));
+/// assert_eq!(lca.depth(d), Some(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LowestCommonAncestors<N>
+where
+    N: Copy + Eq + Hash,
+{
+    root: N,
+    /// The nodes in the order of the Euler tour.
+    tour: Vec<N>,
+    /// The depth of each entry of the tour.
+    depths: Vec<usize>,
+    /// Position of the first occurrence of each node in the tour.
+    first: HashMap<N, usize>,
+    /// `table[k][i]` is the position of the shallowest entry of
+    /// `tour[i..i + 2^k]`.
+    table: Vec<Vec<usize>>,
+}
+
+impl<N> LowestCommonAncestors<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Preprocess the tree of all nodes reachable from `root`.
+    ///
+    /// The edges of a directed graph should point from parents to children.
+    /// Undirected graphs can be used as well. If the reachable part of the
+    /// graph is not a tree, the queries answer for the depth-first search tree
+    /// from `root` instead.
+    pub fn new<G>(graph: G, root: N) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + Visitable,
+    {
+        let mut tour = Vec::new();
+        let mut depths = Vec::new();
+        let mut first = HashMap::new();
+        let mut discovered = graph.visit_map();
+
+        discovered.visit(root);
+        first.insert(root, 0);
+        tour.push(root);
+        depths.push(0);
+        let mut stack = vec![(root, graph.neighbors(root))];
+        while !stack.is_empty() {
+            let depth = stack.len();
+            let child = stack.last_mut().unwrap().1.find(|&n| discovered.visit(n));
+            match child {
+                Some(child) => {
+                    first.insert(child, tour.len());
+                    tour.push(child);
+                    depths.push(depth);
+                    stack.push((child, graph.neighbors(child)));
+                }
+                None => {
+                    stack.pop();
+                    if let Some(&(parent, _)) = stack.last() {
+                        tour.push(parent);
+                        depths.push(depth - 2);
+                    }
+                }
+            }
+        }
+
+        let mut table = vec![(0..tour.len()).collect::<Vec<_>>()];
+        let mut width = 1;
+        while 2 * width <= tour.len() {
+            let next = {
+                let prev = table.last().unwrap();
+                (0..=tour.len() - 2 * width)
+                    .map(|i| {
+                        let (a, b) = (prev[i], prev[i + width]);
+                        if depths[b] < depths[a] {
+                            b
+                        } else {
+                            a
+                        }
+                    })
+                    .collect()
+            };
+            table.push(next);
+            width *= 2;
+        }
+
+        LowestCommonAncestors {
+            root,
+            tour,
+            depths,
+            first,
+            table,
+        }
+    }
+
+    /// Get the root node used to construct the tree.
+    pub fn root(&self) -> N {
+        self.root
+    }
+
+    /// Return the lowest common ancestor of `a` and `b`.
+    ///
+    /// Returns `None` if either node is not reachable from the root.
+    pub fn lca(&self, a: N, b: N) -> Option<N> {
+        let (mut i, mut j) = (*self.first.get(&a)?, *self.first.get(&b)?);
+        if i > j {
+            ::std::mem::swap(&mut i, &mut j);
+        }
+        let level = log2(j - i + 1);
+        let (x, y) = (
+            self.table[level][i],
+            self.table[level][j + 1 - (1 << level)],
+        );
+        let pos = if self.depths[y] < self.depths[x] {
+            y
+        } else {
+            x
+        };
+        Some(self.tour[pos])
+    }
+
+    /// Return the depth of `node`, which is its distance from the root.
+    ///
+    /// Returns `None` if the node is not reachable from the root.
+    pub fn depth(&self, node: N) -> Option<usize> {
+        self.first.get(&node).map(|&i| self.depths[i])
+    }
+
+    /// Return `true` if `ancestor` is an ancestor of `node`, or the node
+    /// itself.
+    pub fn is_ancestor(&self, ancestor: N, node: N) -> bool {
+        self.lca(ancestor, node) == Some(ancestor)
+    }
+}
+
+/// Return the floor of the binary logarithm of `x`, which must be positive.
+fn log2(x: usize) -> usize {
+    ::std::mem::size_of::<usize>() * 8 - 1 - x.leading_zeros() as usize
+}
+
+/// \[Generic\] Return all lowest common ancestors of `a` and `b` in a
+/// directed acyclic graph.
+///
+/// A common ancestor of `a` and `b` is a node with a path to both of them
+/// (every node is an ancestor of itself). The lowest common ancestors are
+/// the common ancestors that do not have a path to any other common ancestor.
+/// They are returned in breadth first order of the ancestors of `a`.
+///
+/// The graph should be acyclic; common ancestors on a cycle are never
+/// reported. Each query takes **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::lca::all_lowest_common_ancestors;
+///
+/// // Two merge commits of the same two branches.
+/// let mut g = Graph::<&str, ()>::new();
+/// let root = g.add_node("root");
+/// let x = g.add_node("x");
+/// let y = g.add_node("y");
+/// let m1 = g.add_node("m1");
+/// let m2 = g.add_node("m2");
+/// g.extend_with_edges(&[(root, x), (root, y), (x, m1), (y, m1), (x, m2), (y, m2)]);
+///
+/// let mut lcas = all_lowest_common_ancestors(&g, m1, m2);
+/// lcas.sort();
+/// assert_eq!(lcas, vec![x, y]);
+/// assert_eq!(all_lowest_common_ancestors(&g, x, m2), vec![x]);
+/// ```
+pub fn all_lowest_common_ancestors<G>(graph: G, a: G::NodeId, b: G::NodeId) -> Vec<G::NodeId>
+where
+    G: IntoNeighborsDirected + Visitable,
+{
+    let (_, ancestors_of_b) = ancestors(graph, b);
+    let (ancestors_of_a, _) = ancestors(graph, a);
+    let mut common = graph.visit_map();
+    let common_ancestors: Vec<_> = ancestors_of_a
+        .into_iter()
+        .filter(|n| ancestors_of_b.is_visited(n))
+        .inspect(|&n| {
+            common.visit(n);
+        })
+        .collect();
+    common_ancestors
+        .into_iter()
+        .filter(|&n| {
+            !graph
+                .neighbors_directed(n, Outgoing)
+                .any(|succ| common.is_visited(&succ))
+        })
+        .collect()
+}
+
+/// Return `node` and all its ancestors in breadth first order, and the map of
+/// visited nodes.
+fn ancestors<G>(graph: G, node: G::NodeId) -> (Vec<G::NodeId>, G::Map)
+where
+    G: IntoNeighborsDirected + Visitable,
+{
+    let mut discovered = graph.visit_map();
+    discovered.visit(node);
+    let mut order = vec![node];
+    let mut queue = VecDeque::new();
+    queue.push_back(node);
+    while let Some(n) = queue.pop_front() {
+        for pred in graph.neighbors_directed(n, Incoming) {
+            if discovered.visit(pred) {
+                order.push(pred);
+                queue.push_back(pred);
+            }
+        }
+    }
+    (order, discovered)
+}
@@ -0,0 +1,562 @@
+//! Link prediction: similarity scores for pairs of nodes.
+//!
+//! The neighborhood scores rate how likely an edge between two nodes is,
+//! from the neighbors the nodes have in common. They are computed for the
+//! pairs of nodes given by an iterator, and returned along with each pair.
+//! Neighbors are given by [`IntoNeighbors`](../../visit/trait.IntoNeighbors.html),
+//! so they are the targets of the outgoing edges in directed graphs; parallel
+//! edges are ignored, and a node with a self loop is its own neighbor.
+//!
+//! Each score has a variant for `Csr`, which works on its sorted neighbor
+//! slices directly, instead of collecting and sorting the neighbors of each
+//! node. [`simrank`](fn.simrank.html) computes the similarity of all pairs
+//! of nodes from the structure of the whole graph.
+
+use crate::csr::{Csr, IndexType, NodeIndex};
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+use crate::EdgeType;
+
+/// \[Generic\] Return the number of common neighbors of each pair of nodes.
+///
+/// Runtime is **O(d log d)** per pair, where **d** is the larger degree of
+/// the two nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::link_prediction::common_neighbors;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+/// let (a, b, c) = (0.into(), 1.into(), 3.into());
+///
+/// let scores = common_neighbors(&g, vec![(a, c), (a, b)]);
+/// assert_eq!(scores, vec![(a, c, 2), (a, b, 1)]);
+/// ```
+pub fn common_neighbors<G, I>(graph: G, pairs: I) -> Vec<(G::NodeId, G::NodeId, usize)>
+where
+    G: IntoNeighbors + NodeIndexable,
+    I: IntoIterator<Item = (G::NodeId, G::NodeId)>,
+{
+    let mut sets = GraphNeighborhoods::new(graph);
+    score_pairs(&mut sets, pairs, common_neighbors_score)
+}
+
+/// \[Generic\] Return the *Jaccard coefficient* of each pair of nodes.
+///
+/// The Jaccard coefficient of two nodes is the number of their common
+/// neighbors, divided by the number of nodes that are a neighbor of either,
+/// or `0.` if neither has a neighbor.
+///
+/// Runtime is **O(d log d)** per pair, where **d** is the larger degree of
+/// the two nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::link_prediction::jaccard_coefficient;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+/// let (a, b, c) = (0.into(), 1.into(), 3.into());
+///
+/// let scores = jaccard_coefficient(&g, vec![(a, c), (a, b)]);
+/// assert_eq!(scores, vec![(a, c, 1.), (a, b, 0.25)]);
+/// ```
+pub fn jaccard_coefficient<G, I>(graph: G, pairs: I) -> Vec<(G::NodeId, G::NodeId, f64)>
+where
+    G: IntoNeighbors + NodeIndexable,
+    I: IntoIterator<Item = (G::NodeId, G::NodeId)>,
+{
+    let mut sets = GraphNeighborhoods::new(graph);
+    score_pairs(&mut sets, pairs, jaccard_score)
+}
+
+/// \[Generic\] Return the *Adamic–Adar index* of each pair of nodes.
+///
+/// The Adamic–Adar index of two nodes is the sum of `1. / ln(d)` over their
+/// common neighbors, where `d` is the degree of the common neighbor. Common
+/// neighbors with a single neighbor would divide by zero and do not
+/// contribute.
+///
+/// Runtime is **O(d log d)** per pair, where **d** is the largest degree of
+/// the two nodes and their common neighbors. See [Adamic and Adar][1].
+///
+/// [1]: https://doi.org/10.1016/S0378-8733(03)00009-1
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::link_prediction::adamic_adar_index;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+///
+/// let scores = adamic_adar_index(&g, vec![(0.into(), 3.into())]);
+/// assert!((scores[0].2 - 2. / 3f64.ln()).abs() < 1e-9);
+/// ```
+pub fn adamic_adar_index<G, I>(graph: G, pairs: I) -> Vec<(G::NodeId, G::NodeId, f64)>
+where
+    G: IntoNeighbors + NodeIndexable,
+    I: IntoIterator<Item = (G::NodeId, G::NodeId)>,
+{
+    let mut sets = GraphNeighborhoods::new(graph);
+    score_pairs(&mut sets, pairs, adamic_adar_score)
+}
+
+/// \[Generic\] Return the *resource allocation index* of each pair of nodes.
+///
+/// The resource allocation index of two nodes is the sum of `1. / d` over
+/// their common neighbors, where `d` is the degree of the common neighbor.
+///
+/// Runtime is **O(d log d)** per pair, where **d** is the largest degree of
+/// the two nodes and their common neighbors. See [Zhou, Lü and Zhang][1].
+///
+/// [1]: https://arxiv.org/abs/0901.0553
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::link_prediction::resource_allocation_index;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+///
+/// let scores = resource_allocation_index(&g, vec![(0.into(), 3.into())]);
+/// assert_eq!(scores[0].2, 2. / 3.);
+/// ```
+pub fn resource_allocation_index<G, I>(graph: G, pairs: I) -> Vec<(G::NodeId, G::NodeId, f64)>
+where
+    G: IntoNeighbors + NodeIndexable,
+    I: IntoIterator<Item = (G::NodeId, G::NodeId)>,
+{
+    let mut sets = GraphNeighborhoods::new(graph);
+    score_pairs(&mut sets, pairs, resource_allocation_score)
+}
+
+/// \[Generic\] Return the *preferential attachment* score of each pair of
+/// nodes.
+///
+/// The preferential attachment score of two nodes is the product of their
+/// degrees, counting distinct neighbors.
+///
+/// Runtime is **O(d log d)** per pair, where **d** is the larger degree of
+/// the two nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::link_prediction::preferential_attachment;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+/// let (a, b, c) = (0.into(), 1.into(), 3.into());
+///
+/// let scores = preferential_attachment(&g, vec![(a, c), (a, b)]);
+/// assert_eq!(scores, vec![(a, c, 4), (a, b, 6)]);
+/// ```
+pub fn preferential_attachment<G, I>(graph: G, pairs: I) -> Vec<(G::NodeId, G::NodeId, usize)>
+where
+    G: IntoNeighbors + NodeIndexable,
+    I: IntoIterator<Item = (G::NodeId, G::NodeId)>,
+{
+    let mut sets = GraphNeighborhoods::new(graph);
+    score_pairs(&mut sets, pairs, preferential_attachment_score)
+}
+
+/// Return the number of common neighbors of each pair of nodes of a `Csr`.
+///
+/// This is [`common_neighbors`](fn.common_neighbors.html) for a `Csr`.
+/// Runtime is **O(d)** per pair.
+///
+/// **Panics** if a node does not exist.
+///
+/// # Example
+/// ```rust
+/// use petgraph::csr::Csr;
+/// use petgraph::Undirected;
+/// use petgraph::algo::link_prediction::common_neighbors_csr;
+///
+/// let mut g = Csr::<(), (), Undirected>::with_nodes(4);
+/// for &(a, b) in &[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)] {
+///     g.add_edge(a, b, ());
+/// }
+///
+/// assert_eq!(common_neighbors_csr(&g, vec![(0, 3)]), vec![(0, 3, 2)]);
+/// ```
+pub fn common_neighbors_csr<N, E, Ty, Ix, I>(
+    graph: &Csr<N, E, Ty, Ix>,
+    pairs: I,
+) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, usize)>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(&mut CsrNeighborhoods(graph), pairs, common_neighbors_score)
+}
+
+/// Return the Jaccard coefficient of each pair of nodes of a `Csr`.
+///
+/// This is [`jaccard_coefficient`](fn.jaccard_coefficient.html) for a `Csr`.
+/// Runtime is **O(d)** per pair.
+///
+/// **Panics** if a node does not exist.
+pub fn jaccard_coefficient_csr<N, E, Ty, Ix, I>(
+    graph: &Csr<N, E, Ty, Ix>,
+    pairs: I,
+) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(&mut CsrNeighborhoods(graph), pairs, jaccard_score)
+}
+
+/// Return the Adamic–Adar index of each pair of nodes of a `Csr`.
+///
+/// This is [`adamic_adar_index`](fn.adamic_adar_index.html) for a `Csr`.
+/// Runtime is **O(d)** per pair.
+///
+/// **Panics** if a node does not exist.
+pub fn adamic_adar_index_csr<N, E, Ty, Ix, I>(
+    graph: &Csr<N, E, Ty, Ix>,
+    pairs: I,
+) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(&mut CsrNeighborhoods(graph), pairs, adamic_adar_score)
+}
+
+/// Return the resource allocation index of each pair of nodes of a `Csr`.
+///
+/// This is [`resource_allocation_index`](fn.resource_allocation_index.html)
+/// for a `Csr`. Runtime is **O(d)** per pair.
+///
+/// **Panics** if a node does not exist.
+pub fn resource_allocation_index_csr<N, E, Ty, Ix, I>(
+    graph: &Csr<N, E, Ty, Ix>,
+    pairs: I,
+) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(
+        &mut CsrNeighborhoods(graph),
+        pairs,
+        resource_allocation_score,
+    )
+}
+
+/// Return the preferential attachment score of each pair of nodes of a
+/// `Csr`.
+///
+/// This is [`preferential_attachment`](fn.preferential_attachment.html) for a
+/// `Csr`. Runtime is **O(1)** per pair.
+///
+/// **Panics** if a node does not exist.
+pub fn preferential_attachment_csr<N, E, Ty, Ix, I>(
+    graph: &Csr<N, E, Ty, Ix>,
+    pairs: I,
+) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, usize)>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(
+        &mut CsrNeighborhoods(graph),
+        pairs,
+        preferential_attachment_score,
+    )
+}
+
+/// \[Generic\] Compute the *SimRank* similarity of every pair of nodes.
+///
+/// Two nodes are similar if they are referenced by similar nodes: the
+/// similarity of a node to itself is `1.`, and the similarity of two
+/// different nodes is `decay` times the average similarity of their
+/// in-neighbors, or `0.` if either has no in-neighbors. The in-neighbors of
+/// a node are the sources of its incoming edges in a directed graph, and its
+/// neighbors in an undirected graph; parallel edges are ignored.
+///
+/// The similarities are computed by iteration, starting from the identity,
+/// until no similarity changes by more than `tolerance`, or for at most
+/// `max_iter` iterations.
+///
+/// Returns the similarities as a matrix indexed by
+/// [`NodeIndexable::to_index`](../../visit/trait.NodeIndexable.html#tymethod.to_index)
+/// in both dimensions; indices that are not in use have a similarity of zero.
+///
+/// **Panics** if `decay` is not in the range `0. ..= 1.`.
+///
+/// Runtime is **O(|V| |E|)** per iteration, using the partial sums of
+/// [Lizorkin et al.][2], and memory use is **O(|V|²)**. See
+/// [Jeh and Widom][1].
+///
+/// [1]: https://doi.org/10.1145/775047.775126
+/// [2]: https://doi.org/10.1007/s00778-009-0168-8
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::link_prediction::simrank;
+///
+/// // Nodes 1 and 2 are both referenced by node 0 only.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3)]);
+///
+/// let sim = simrank(&g, 0.8, 1e-9, 100);
+/// assert_eq!(sim[1][2], 0.8);
+/// assert_eq!(sim[0][1], 0.);
+/// assert_eq!(sim[3][3], 1.);
+/// ```
+pub fn simrank<G>(graph: G, decay: f64, tolerance: f64, max_iter: usize) -> Vec<Vec<f64>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    assert!(
+        (0. ..=1.).contains(&decay),
+        "SimRank decay factor must be in 0. ..= 1."
+    );
+    let bound = graph.node_bound();
+    let nodes: Vec<usize> = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .collect();
+    let mut incoming = vec![Vec::new(); bound];
+    for n in graph.node_identifiers() {
+        let source = graph.to_index(n);
+        for m in graph.neighbors(n) {
+            incoming[graph.to_index(m)].push(source);
+        }
+    }
+    for list in &mut incoming {
+        list.sort();
+        list.dedup();
+    }
+
+    let mut sim = vec![vec![0.; bound]; bound];
+    for &a in &nodes {
+        sim[a][a] = 1.;
+    }
+    let mut next = vec![vec![0.; bound]; bound];
+    // `partial[j]` is the sum of the similarities of `j` to the in-neighbors
+    // of the current node `a`.
+    let mut partial = vec![0.; bound];
+    for _ in 0..max_iter {
+        let mut change: f64 = 0.;
+        for &a in &nodes {
+            partial.clear();
+            partial.resize(bound, 0.);
+            for &i in &incoming[a] {
+                for (p, &s) in partial.iter_mut().zip(&sim[i]) {
+                    *p += s;
+                }
+            }
+            for &b in &nodes {
+                let value = if a == b {
+                    1.
+                } else if incoming[a].is_empty() || incoming[b].is_empty() {
+                    0.
+                } else {
+                    let total = incoming[b].iter().fold(0., |acc, &j| acc + partial[j]);
+                    decay * total / (incoming[a].len() * incoming[b].len()) as f64
+                };
+                change = change.max((value - sim[a][b]).abs());
+                next[a][b] = value;
+            }
+        }
+        std::mem::swap(&mut sim, &mut next);
+        if change <= tolerance {
+            break;
+        }
+    }
+    sim
+}
+
+/// Sorted neighbor lists of nodes, by node index.
+trait Neighborhoods {
+    type NodeId: Copy;
+    type Item: Copy + Ord;
+
+    fn to_index(&self, a: Self::NodeId) -> usize;
+    fn item_index(&self, a: Self::Item) -> usize;
+    /// Make the neighbors of the node with index `a` available.
+    fn load(&mut self, a: usize);
+    /// Return the sorted neighbors of the node with index `a`, which must have
+    /// been loaded.
+    fn get(&self, a: usize) -> &[Self::Item];
+}
+
+/// The neighborhoods of a generic graph, collected and sorted on demand.
+struct GraphNeighborhoods<G> {
+    graph: G,
+    neighbors: Vec<Option<Vec<usize>>>,
+}
+
+impl<G> GraphNeighborhoods<G>
+where
+    G: IntoNeighbors + NodeIndexable,
+{
+    fn new(graph: G) -> Self {
+        GraphNeighborhoods {
+            neighbors: vec![None; graph.node_bound()],
+            graph,
+        }
+    }
+}
+
+impl<G> Neighborhoods for GraphNeighborhoods<G>
+where
+    G: IntoNeighbors + NodeIndexable,
+{
+    type NodeId = G::NodeId;
+    type Item = usize;
+
+    fn to_index(&self, a: G::NodeId) -> usize {
+        self.graph.to_index(a)
+    }
+
+    fn item_index(&self, a: usize) -> usize {
+        a
+    }
+
+    fn load(&mut self, a: usize) {
+        if self.neighbors[a].is_none() {
+            let graph = self.graph;
+            let mut list: Vec<usize> = graph
+                .neighbors(graph.from_index(a))
+                .map(|n| graph.to_index(n))
+                .collect();
+            list.sort();
+            list.dedup();
+            self.neighbors[a] = Some(list);
+        }
+    }
+
+    fn get(&self, a: usize) -> &[usize] {
+        self.neighbors[a].as_ref().unwrap()
+    }
+}
+
+/// The neighborhoods of a `Csr`, which are stored sorted.
+struct CsrNeighborhoods<'a, N, E, Ty, Ix>(&'a Csr<N, E, Ty, Ix>);
+
+impl<'a, N, E, Ty, Ix> Neighborhoods for CsrNeighborhoods<'a, N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type NodeId = NodeIndex<Ix>;
+    type Item = NodeIndex<Ix>;
+
+    fn to_index(&self, a: NodeIndex<Ix>) -> usize {
+        a.index()
+    }
+
+    fn item_index(&self, a: NodeIndex<Ix>) -> usize {
+        a.index()
+    }
+
+    fn load(&mut self, _: usize) {}
+
+    fn get(&self, a: usize) -> &[NodeIndex<Ix>] {
+        self.0.neighbors_slice(Ix::new(a))
+    }
+}
+
+fn score_pairs<S, I, T, F>(sets: &mut S, pairs: I, mut score: F) -> Vec<(S::NodeId, S::NodeId, T)>
+where
+    S: Neighborhoods,
+    I: IntoIterator<Item = (S::NodeId, S::NodeId)>,
+    F: FnMut(&mut S, usize, usize) -> T,
+{
+    pairs
+        .into_iter()
+        .map(|(a, b)| {
+            let (u, v) = (sets.to_index(a), sets.to_index(b));
+            sets.load(u);
+            sets.load(v);
+            (a, b, score(sets, u, v))
+        })
+        .collect()
+}
+
+/// Return the common elements of two sorted slices.
+fn intersection<'a, T: Ord>(mut xs: &'a [T], mut ys: &'a [T]) -> impl Iterator<Item = &'a T> {
+    std::iter::from_fn(move || {
+        while let (Some(x), Some(y)) = (xs.first(), ys.first()) {
+            if x < y {
+                xs = &xs[1..];
+            } else if y < x {
+                ys = &ys[1..];
+            } else {
+                xs = &xs[1..];
+                ys = &ys[1..];
+                return Some(x);
+            }
+        }
+        None
+    })
+}
+
+fn common_neighbors_score<S: Neighborhoods>(sets: &mut S, u: usize, v: usize) -> usize {
+    intersection(sets.get(u), sets.get(v)).count()
+}
+
+fn jaccard_score<S: Neighborhoods>(sets: &mut S, u: usize, v: usize) -> f64 {
+    let common = common_neighbors_score(sets, u, v);
+    let union = sets.get(u).len() + sets.get(v).len() - common;
+    if union == 0 {
+        0.
+    } else {
+        common as f64 / union as f64
+    }
+}
+
+/// Sum `weight(d)` over the degrees `d` of the common neighbors of `u` and
+/// `v`.
+fn common_neighbor_sum<S, F>(sets: &mut S, u: usize, v: usize, weight: F) -> f64
+where
+    S: Neighborhoods,
+    F: Fn(usize) -> f64,
+{
+    let common: Vec<usize> = intersection(sets.get(u), sets.get(v))
+        .map(|&w| sets.item_index(w))
+        .collect();
+    let mut total = 0.;
+    for w in common {
+        sets.load(w);
+        total += weight(sets.get(w).len());
+    }
+    total
+}
+
+fn adamic_adar_score<S: Neighborhoods>(sets: &mut S, u: usize, v: usize) -> f64 {
+    common_neighbor_sum(
+        sets,
+        u,
+        v,
+        |d| {
+            if d > 1 {
+                1. / (d as f64).ln()
+            } else {
+                0.
+            }
+        },
+    )
+}
+
+fn resource_allocation_score<S: Neighborhoods>(sets: &mut S, u: usize, v: usize) -> f64 {
+    common_neighbor_sum(sets, u, v, |d| 1. / d as f64)
+}
+
+fn preferential_attachment_score<S: Neighborhoods>(sets: &mut S, u: usize, v: usize) -> usize {
+    sets.get(u).len() * sets.get(v).len()
+}
@@ -0,0 +1,410 @@
+//! Loop nesting forests of control-flow graphs.
+//!
+//! A *loop* is a strongly connected set of nodes. The nodes of a loop that can
+//! be entered from outside of it are its *entries*; a loop with a single entry
+//! is *reducible* (a natural loop), and the entry is its *header*. Edges from
+//! inside a loop back to one of its entries are the loop's *back edges*.
+//!
+//! Removing the back edges of a loop can leave smaller strongly connected sets
+//! inside it: the nested loops. All loops of a graph together form the *loop
+//! nesting forest*.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::dominators::{semi_nca, Dominators, Preorder};
+use crate::visit::{GraphBase, IntoNeighbors};
+
+/// A loop identifier in a [`LoopNestingForest`](struct.LoopNestingForest.html).
+///
+/// Outer loops have smaller identifiers than the loops nested in them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Loop(usize);
+
+impl Loop {
+    /// Return the loop's index, in the range `0..forest.loop_count()`.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+struct LoopData<N> {
+    /// The entries of the loop, in depth first order; the first is the header.
+    headers: Vec<N>,
+    parent: Option<Loop>,
+    depth: usize,
+    nodes: Vec<N>,
+    back_edges: Vec<(N, N)>,
+}
+
+/// The loops of a graph and how they are nested, as computed by
+/// [`loop_nesting_forest`](fn.loop_nesting_forest.html).
+#[derive(Clone, Debug)]
+pub struct LoopNestingForest<N>
+where
+    N: Copy + Eq + Hash,
+{
+    dominators: Dominators<N>,
+    loops: Vec<LoopData<N>>,
+    innermost: HashMap<N, Loop>,
+}
+
+impl<N> LoopNestingForest<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Return the number of loops.
+    pub fn loop_count(&self) -> usize {
+        self.loops.len()
+    }
+
+    /// Iterate over all loops, outer loops before the loops nested in them.
+    pub fn loops(&self) -> Loops {
+        Loops {
+            range: 0..self.loops.len(),
+        }
+    }
+
+    /// Return the innermost loop containing `node`, or `None` if the node is
+    /// not part of any loop (or not reachable from the entry).
+    pub fn loop_of(&self, node: N) -> Option<Loop> {
+        self.innermost.get(&node).cloned()
+    }
+
+    /// Return the loop nesting depth of `node`: the number of loops that
+    /// contain it, which is `0` for nodes outside of any loop.
+    pub fn depth(&self, node: N) -> usize {
+        self.loop_of(node).map_or(0, |l| self.loop_depth(l))
+    }
+
+    /// Return the header of the loop.
+    ///
+    /// For an irreducible loop, this is the entry that is visited first by a
+    /// depth first search from the entry node of the graph.
+    pub fn header(&self, l: Loop) -> N {
+        self.loops[l.0].headers[0]
+    }
+
+    /// Return all entries of the loop; the first is the loop's header.
+    ///
+    /// Reducible loops have exactly one entry.
+    pub fn headers(&self, l: Loop) -> &[N] {
+        &self.loops[l.0].headers
+    }
+
+    /// Return `true` if the loop is reducible, i.e. its header dominates
+    /// every node of the loop.
+    pub fn is_reducible(&self, l: Loop) -> bool {
+        self.loops[l.0].headers.len() == 1
+    }
+
+    /// Return the loop that immediately contains `l`, if any.
+    pub fn parent(&self, l: Loop) -> Option<Loop> {
+        self.loops[l.0].parent
+    }
+
+    /// Return the nesting depth of the loop, which is `1` for outermost loops.
+    pub fn loop_depth(&self, l: Loop) -> usize {
+        self.loops[l.0].depth
+    }
+
+    /// Return all nodes of the loop, including the nodes of nested loops.
+    pub fn nodes(&self, l: Loop) -> &[N] {
+        &self.loops[l.0].nodes
+    }
+
+    /// Return the back edges of the loop: the edges from nodes of the loop to
+    /// its entries, as `(source, target)` pairs.
+    pub fn back_edges(&self, l: Loop) -> &[(N, N)] {
+        &self.loops[l.0].back_edges
+    }
+
+    /// Return `true` if `l` contains `node`, directly or in a nested loop.
+    pub fn contains(&self, l: Loop, node: N) -> bool {
+        let mut current = self.loop_of(node);
+        while let Some(inner) = current {
+            if inner == l {
+                return true;
+            }
+            current = self.parent(inner);
+        }
+        false
+    }
+
+    /// Return the dominance relation that was computed for the graph.
+    pub fn dominators(&self) -> &Dominators<N> {
+        &self.dominators
+    }
+}
+
+/// Iterator over the loops of a `LoopNestingForest`.
+#[derive(Clone, Debug)]
+pub struct Loops {
+    range: ::std::ops::Range<usize>,
+}
+
+impl Iterator for Loops {
+    type Item = Loop;
+
+    fn next(&mut self) -> Option<Loop> {
+        self.range.next().map(Loop)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+/// \[Generic\] Compute the loop nesting forest of the nodes reachable from
+/// `entry`.
+///
+/// Loops are found as strongly connected components; the back edges of each
+/// loop are removed and the components inside it are searched for nested
+/// loops, following Steensgaard's construction. Irreducible loops with more
+/// than one entry are supported.
+///
+/// Dominators are computed with
+/// [`lengauer_tarjan`](dominators/fn.lengauer_tarjan.html) and are available
+/// from the result. The header of a reducible loop dominates all of its
+/// nodes, and its back edges are exactly the edges whose target dominates
+/// their source.
+///
+/// Runtime is **O((|V| + |E|) d)**, where **d** is the maximum loop depth,
+/// plus the time to compute the dominators.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::loops::loop_nesting_forest;
+///
+/// // entry -> outer -> inner -> latch -> exit
+/// //            ^        ^  |     |
+/// //            |        '--'     |
+/// //            '-----------------'
+/// let mut g = Graph::<&str, ()>::new();
+/// let entry = g.add_node("entry");
+/// let outer = g.add_node("outer");
+/// let inner = g.add_node("inner");
+/// let latch = g.add_node("latch");
+/// let exit = g.add_node("exit");
+/// g.extend_with_edges(&[
+///     (entry, outer), (outer, inner), (inner, inner),
+///     (inner, latch), (latch, outer), (latch, exit),
+/// ]);
+///
+/// let forest = loop_nesting_forest(&g, entry);
+/// assert_eq!(forest.loop_count(), 2);
+///
+/// let inner_loop = forest.loop_of(inner).unwrap();
+/// assert_eq!(forest.header(inner_loop), inner);
+/// let outer_loop = forest.parent(inner_loop).unwrap();
+/// assert_eq!(forest.header(outer_loop), outer);
+/// assert_eq!(forest.loop_of(latch), Some(outer_loop));
+///
+/// assert_eq!(forest.depth(entry), 0);
+/// assert_eq!(forest.depth(latch), 1);
+/// assert_eq!(forest.depth(inner), 2);
+/// ```
+pub fn loop_nesting_forest<G>(graph: G, entry: G::NodeId) -> LoopNestingForest<G::NodeId>
+where
+    G: IntoNeighbors,
+    <G as GraphBase>::NodeId: Eq + Hash,
+{
+    let search = Preorder::new(graph, entry);
+    let dominators = semi_nca(&search);
+    let Preorder {
+        nodes: preorder,
+        predecessors,
+        ..
+    } = search;
+    let length = preorder.len();
+    let mut successors = vec![Vec::new(); length];
+    for (v, preds) in predecessors.iter().enumerate() {
+        for &u in preds {
+            successors[u].push(v);
+        }
+    }
+
+    let mut loops: Vec<LoopData<G::NodeId>> = Vec::new();
+    let mut innermost = vec![None; length];
+    let mut is_header = vec![false; length];
+    // `region[v]` is the work item that `v` currently belongs to.
+    let mut region = vec![0; length];
+    let mut scc = SccState::new(length);
+    let mut work = vec![(None, (0..length).collect::<Vec<_>>())];
+    let mut next_region = 1;
+
+    while let Some((parent, nodes)) = work.pop() {
+        for component in scc.components(&nodes, &successors, &region, &is_header) {
+            let is_loop = component.len() > 1 || {
+                let v = component[0];
+                !is_header[v] && successors[v].contains(&v)
+            };
+            if !is_loop {
+                continue;
+            }
+
+            let id = Loop(loops.len());
+            for &v in &component {
+                region[v] = next_region;
+            }
+            let mut headers: Vec<usize> = component
+                .iter()
+                .cloned()
+                .filter(|&v| v == 0 || predecessors[v].iter().any(|&u| region[u] != next_region))
+                .collect();
+            headers.sort();
+            let mut back_edges = Vec::new();
+            for &h in &headers {
+                is_header[h] = true;
+                for &u in &predecessors[h] {
+                    if region[u] == next_region {
+                        back_edges.push((preorder[u], preorder[h]));
+                    }
+                }
+            }
+            for &v in &component {
+                innermost[v] = Some(id);
+            }
+            debug_assert!(
+                headers.len() > 1
+                    || back_edges
+                        .iter()
+                        .all(|&(u, h)| dominates(&dominators, h, u))
+            );
+
+            let depth = parent.map_or(1, |p: Loop| loops[p.0].depth + 1);
+            loops.push(LoopData {
+                headers: headers.iter().map(|&h| preorder[h]).collect(),
+                parent,
+                depth,
+                nodes: component.iter().map(|&v| preorder[v]).collect(),
+                back_edges,
+            });
+            next_region += 1;
+            work.push((Some(id), component));
+        }
+    }
+
+    LoopNestingForest {
+        dominators,
+        loops,
+        innermost: innermost
+            .into_iter()
+            .enumerate()
+            .filter_map(|(v, l)| l.map(|l| (preorder[v], l)))
+            .collect(),
+    }
+}
+
+fn dominates<N>(dominators: &Dominators<N>, a: N, b: N) -> bool
+where
+    N: Copy + Eq + Hash,
+{
+    match dominators.dominators(b) {
+        Some(mut doms) => doms.any(|d| d == a),
+        None => false,
+    }
+}
+
+/// The index sentinel, for nodes that the search has not visited yet.
+const UNVISITED: usize = ::std::usize::MAX;
+
+/// Workspace for Tarjan's strongly connected components algorithm on a subset
+/// of the nodes.
+struct SccState {
+    index: Vec<usize>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+}
+
+impl SccState {
+    fn new(length: usize) -> Self {
+        SccState {
+            index: vec![UNVISITED; length],
+            lowlink: vec![0; length],
+            on_stack: vec![false; length],
+        }
+    }
+
+    /// Return the strongly connected components of the subgraph induced by
+    /// `nodes`, all of which are in the same region, ignoring edges into
+    /// headers.
+    fn components(
+        &mut self,
+        nodes: &[usize],
+        successors: &[Vec<usize>],
+        region: &[usize],
+        is_header: &[bool],
+    ) -> Vec<Vec<usize>> {
+        let current = region[nodes[0]];
+        let follow = |v: usize| region[v] == current && !is_header[v];
+        for &v in nodes {
+            self.index[v] = UNVISITED;
+            self.on_stack[v] = false;
+        }
+
+        let mut components = Vec::new();
+        let mut counter = 0;
+        let mut stack = Vec::new();
+        let mut call_stack = Vec::new();
+        for &start in nodes {
+            if self.index[start] != UNVISITED {
+                continue;
+            }
+            call_stack.push((start, 0));
+            self.index[start] = counter;
+            self.lowlink[start] = counter;
+            counter += 1;
+            stack.push(start);
+            self.on_stack[start] = true;
+
+            while let Some(&mut (v, ref mut position)) = call_stack.last_mut() {
+                let mut descended = false;
+                while *position < successors[v].len() {
+                    let w = successors[v][*position];
+                    *position += 1;
+                    if !follow(w) {
+                        continue;
+                    }
+                    if self.index[w] == UNVISITED {
+                        self.index[w] = counter;
+                        self.lowlink[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        self.on_stack[w] = true;
+                        call_stack.push((w, 0));
+                        descended = true;
+                        break;
+                    } else if self.on_stack[w] && self.index[w] < self.lowlink[v] {
+                        self.lowlink[v] = self.index[w];
+                    }
+                }
+                if descended {
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    if self.lowlink[v] < self.lowlink[parent] {
+                        self.lowlink[parent] = self.lowlink[v];
+                    }
+                }
+                if self.lowlink[v] == self.index[v] {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = stack.pop().unwrap();
+                        self.on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
@@ -4,7 +4,29 @@
 //! so that they are generally applicable. For now, some of these still require
 //! the `Graph` type.
 
+mod adjacency;
+mod arborescence;
+mod arcs;
+mod betweenness;
+mod bipartite;
+mod canonical;
+pub mod centrality;
+mod cliques;
+pub mod coloring;
+pub mod community;
+pub mod covering;
+mod degree;
+pub mod distance_measures;
 pub mod dominators;
+mod edit_distance;
+mod k_core;
+pub mod lca;
+pub mod link_prediction;
+pub mod loops;
+mod page_rank;
+mod spanning_forest;
+pub mod tree;
+mod triangles;
 
 use std::cmp::min;
 use std::collections::{BinaryHeap, HashMap};
@@ -24,9 +46,35 @@ use crate::scored::MinScored;
 use crate::visit::Walker;
 use crate::visit::{Data, IntoNodeReferences, NodeRef};
 
+pub use self::arborescence::{max_branching, min_spanning_arborescence, Unreachable};
+pub use self::betweenness::{
+    betweenness_centrality, edge_betweenness_centrality, weighted_betweenness_centrality,
+    weighted_edge_betweenness_centrality,
+};
+pub use self::bipartite::{bipartite_partition, OddCycle};
+pub use self::canonical::{canonical_labeling, wl_hash};
+pub use self::cliques::{
+    clique_number, maximal_cliques, maximum_clique, node_clique_number, MaximalCliques,
+};
+pub use self::degree::{degree_assortativity, degree_histogram, degree_sequence};
+pub use self::edit_distance::{
+    approximate_graph_edit_distance, graph_edit_distance, EditOperation, EditPath,
+};
+pub use self::k_core::{core_number, degeneracy_ordering, k_core};
+pub use self::page_rank::{page_rank, personalized_page_rank};
+pub use self::spanning_forest::{
+    boruvka_spanning_forest, max_spanning_forest, prim_spanning_forest, SpanningForest,
+};
+pub use self::triangles::{
+    clustering, clustering_csr, transitivity, transitivity_csr, triangles, triangles_csr,
+};
 pub use super::astar::astar;
 pub use super::dijkstra::dijkstra;
-pub use super::isomorphism::{is_isomorphic, is_isomorphic_matching};
+pub use super::isomorphism::{
+    automorphisms, is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph,
+    is_isomorphic_subgraph_matching, isomorphisms, subgraph_isomorphisms, subgraph_monomorphisms,
+    Mappings,
+};
 pub use super::simple_paths::all_simple_paths;
 
 /// \[Generic\] Return the number of connected components of the graph.
@@ -796,6 +844,10 @@ where
 /// algorithm implements 2-coloring algorithm based on the BFS algorithm.
 ///
 /// Always treats the input graph as if undirected.
+///
+/// Only the component containing `start` is checked; see
+/// [`bipartite_partition`](fn.bipartite_partition.html) for the partition of
+/// the whole graph, or an odd cycle if there is none.
 pub fn is_bipartite_undirected<G, N, VM>(g: G, start: N) -> bool
     where G: GraphRef + Visitable<NodeId=N, Map=VM> + IntoNeighbors<NodeId=N>,
           N: Copy + PartialEq + std::fmt::Debug,
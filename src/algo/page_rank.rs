@@ -0,0 +1,188 @@
+//! PageRank and personalized PageRank.
+
+use crate::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Compute the *PageRank* of every node.
+///
+/// The PageRank of a node is the probability of finding a random surfer at
+/// the node, where the surfer follows a random outgoing edge with probability
+/// `damping`, and jumps to a uniformly random node otherwise. The surfer
+/// jumps to a random node from nodes without outgoing edges (*dangling*
+/// nodes). The edges of undirected graphs are followed in both directions,
+/// and parallel edges are counted with their multiplicity.
+///
+/// The scores are computed by power iteration, starting from the uniform
+/// distribution, until the sum of the absolute changes of one iteration is
+/// below `tolerance`, or for at most `max_iter` iterations. See
+/// [`personalized_page_rank`](fn.personalized_page_rank.html) for weighted
+/// edges and other jump distributions.
+///
+/// Returns the scores, which sum to one, indexed by
+/// [`NodeIndexable::to_index`](../visit/trait.NodeIndexable.html#tymethod.to_index);
+/// indices that are not in use have a score of zero.
+///
+/// **Panics** if `damping` is not in the range `0. ..= 1.`.
+///
+/// Runtime is **O(|V| + |E|)** per iteration. See [PageRank][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/PageRank
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::page_rank;
+///
+/// // Node 0 links to 1 and 2, which both link back to 0.
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 0), (2, 0)]);
+///
+/// let scores = page_rank(&g, 0.85, 1e-10, 100);
+/// assert!((scores.iter().sum::<f64>() - 1.).abs() < 1e-9);
+/// assert!(scores[0] > scores[1]);
+/// assert!((scores[1] - scores[2]).abs() < 1e-9);
+/// ```
+pub fn page_rank<G>(graph: G, damping: f64, tolerance: f64, max_iter: usize) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    personalized_page_rank(graph, damping, tolerance, max_iter, None, |_| 1.)
+}
+
+/// \[Generic\] Compute the *personalized PageRank* of every node, with
+/// weighted edges.
+///
+/// This is [`page_rank`](fn.page_rank.html), except that the surfer follows
+/// an outgoing edge with a probability proportional to its weight, given by
+/// `edge_weight`, and jumps to a node with a probability proportional to its
+/// entry in `personalization`. The surfer also jumps according to
+/// `personalization` from dangling nodes, which are the nodes whose outgoing
+/// edges have a total weight of zero.
+///
+/// `personalization` is indexed by
+/// [`NodeIndexable::to_index`](../visit/trait.NodeIndexable.html#tymethod.to_index)
+/// and does not need to be normalized; entries for indices that are not in
+/// use are ignored. The jump distribution is uniform if it is `None`.
+///
+/// **Panics** if `damping` is not in the range `0. ..= 1.`, if an edge weight
+/// is negative, or if `personalization` is shorter than `node_bound`, has a
+/// negative entry, or has no positive entry for a node of the graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::personalized_page_rank;
+/// use petgraph::visit::EdgeRef;
+///
+/// // A directed cycle with one heavy edge 0 -> 2.
+/// let g = Graph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 2, 1.), (2, 0, 1.), (0, 2, 3.)]);
+///
+/// let scores = personalized_page_rank(&g, 0.85, 1e-10, 100, None, |e| *e.weight());
+/// assert!(scores[2] > scores[0]);
+/// assert!(scores[0] > scores[1]);
+///
+/// // Always jump to node 1.
+/// let jump = [0., 1., 0.];
+/// let personalized = personalized_page_rank(&g, 0.85, 1e-10, 100, Some(&jump), |e| *e.weight());
+/// assert!(personalized[1] > scores[1]);
+/// ```
+pub fn personalized_page_rank<G, F>(
+    graph: G,
+    damping: f64,
+    tolerance: f64,
+    max_iter: usize,
+    personalization: Option<&[f64]>,
+    mut edge_weight: F,
+) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    assert!(
+        (0. ..=1.).contains(&damping),
+        "PageRank damping factor must be in 0. ..= 1."
+    );
+    let bound = graph.node_bound();
+    let nodes: Vec<usize> = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .collect();
+    if nodes.is_empty() {
+        return vec![0.; bound];
+    }
+
+    // The jump distribution.
+    let mut jump = vec![0.; bound];
+    match personalization {
+        None => {
+            for &i in &nodes {
+                jump[i] = 1. / nodes.len() as f64;
+            }
+        }
+        Some(weights) => {
+            assert!(
+                weights.len() >= bound,
+                "PageRank personalization must have an entry for every node index"
+            );
+            for &i in &nodes {
+                assert!(
+                    weights[i] >= 0.,
+                    "PageRank personalization must not be negative"
+                );
+                jump[i] = weights[i];
+            }
+            let total: f64 = jump.iter().sum();
+            assert!(
+                total > 0.,
+                "PageRank personalization must have a positive entry"
+            );
+            for p in &mut jump {
+                *p /= total;
+            }
+        }
+    }
+
+    // The outgoing edges as `(source, target, transition probability)`.
+    let mut edges = Vec::new();
+    let mut dangling = Vec::new();
+    for n in graph.node_identifiers() {
+        let source = graph.to_index(n);
+        let first = edges.len();
+        let mut total = 0.;
+        for edge in graph.edges(n) {
+            let weight = edge_weight(edge);
+            assert!(weight >= 0., "PageRank edge weights must not be negative");
+            if weight > 0. {
+                total += weight;
+                edges.push((source, graph.to_index(edge.target()), weight));
+            }
+        }
+        if total > 0. {
+            for edge in &mut edges[first..] {
+                edge.2 /= total;
+            }
+        } else {
+            dangling.push(source);
+        }
+    }
+
+    let mut scores = vec![0.; bound];
+    for &i in &nodes {
+        scores[i] = 1. / nodes.len() as f64;
+    }
+    let mut next = vec![0.; bound];
+    for _ in 0..max_iter {
+        let dangling_score: f64 = dangling.iter().map(|&i| scores[i]).sum();
+        let jump_score = damping * dangling_score + (1. - damping);
+        for &i in &nodes {
+            next[i] = jump_score * jump[i];
+        }
+        for &(source, target, p) in &edges {
+            next[target] += damping * p * scores[source];
+        }
+        let change: f64 = nodes.iter().map(|&i| (next[i] - scores[i]).abs()).sum();
+        std::mem::swap(&mut scores, &mut next);
+        if change < tolerance {
+            break;
+        }
+    }
+    scores
+}
@@ -0,0 +1,292 @@
+//! Spanning forests with edge references as output (Prim, Borůvka).
+
+use std::cmp::Ordering;
+use std::slice;
+
+use crate::unionfind::UnionFind;
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+use super::Measure;
+
+/// A spanning forest: the chosen edges and their total weight.
+///
+/// Returned by [`prim_spanning_forest`](fn.prim_spanning_forest.html),
+/// [`boruvka_spanning_forest`](fn.boruvka_spanning_forest.html) and
+/// [`max_spanning_forest`](fn.max_spanning_forest.html).
+#[derive(Clone, Debug)]
+pub struct SpanningForest<E, K> {
+    edges: Vec<E>,
+    total_weight: K,
+}
+
+impl<E, K> SpanningForest<E, K>
+where
+    E: EdgeRef,
+{
+    /// Return the edges of the forest.
+    pub fn edges(&self) -> &[E] {
+        &self.edges
+    }
+
+    /// Iterate over the edge ids of the forest.
+    pub fn edge_ids(&self) -> EdgeIds<'_, E> {
+        EdgeIds {
+            iter: self.edges.iter(),
+        }
+    }
+
+    /// Return the sum of the edge costs of the forest.
+    pub fn total_weight(&self) -> &K {
+        &self.total_weight
+    }
+
+    /// Return the number of edges in the forest.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return `true` if the forest has no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Consume the forest and return its edges.
+    pub fn into_edges(self) -> Vec<E> {
+        self.edges
+    }
+}
+
+/// Iterator over the edge ids of a `SpanningForest`.
+#[derive(Clone, Debug)]
+pub struct EdgeIds<'a, E: 'a> {
+    iter: slice::Iter<'a, E>,
+}
+
+impl<'a, E> Iterator for EdgeIds<'a, E>
+where
+    E: EdgeRef,
+{
+    type Item = E::EdgeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| e.id())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// \[Generic\] Compute a *minimum spanning forest* of a graph using
+/// [Prim's algorithm][1].
+///
+/// The input graph is treated as if undirected. The function `edge_cost`
+/// should return the cost for a particular edge.
+///
+/// This variant of the algorithm scans all nodes to find the next closest one
+/// instead of using a priority queue, which gives a running time of
+/// **O(|V|² + |E|)**. It is the better choice for dense graphs, such as graphs
+/// stored in a `MatrixGraph`; for sparse graphs prefer
+/// [`boruvka_spanning_forest`](fn.boruvka_spanning_forest.html) or
+/// [`min_spanning_tree`](fn.min_spanning_tree.html).
+///
+/// [1]: https://en.wikipedia.org/wiki/Prim%27s_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::matrix_graph::UnMatrix;
+/// use petgraph::algo::prim_spanning_forest;
+/// use petgraph::visit::EdgeRef;
+///
+/// let g = UnMatrix::<(), u32>::from_edges(&[
+///     (0, 1, 4), (0, 2, 1), (1, 2, 2), (1, 3, 5), (2, 3, 8),
+/// ]);
+///
+/// let forest = prim_spanning_forest(&g, |e| *e.weight());
+/// assert_eq!(forest.len(), 3);
+/// assert_eq!(*forest.total_weight(), 1 + 2 + 5);
+/// ```
+pub fn prim_spanning_forest<G, F, K>(graph: G, mut edge_cost: F) -> SpanningForest<G::EdgeRef, K>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let edges: Vec<_> = graph.edge_references().map(|e| (e, edge_cost(e))).collect();
+    let mut adjacency = vec![Vec::new(); graph.node_bound()];
+    for (i, &(e, _)) in edges.iter().enumerate() {
+        let (a, b) = (graph.to_index(e.source()), graph.to_index(e.target()));
+        adjacency[a].push((b, i));
+        adjacency[b].push((a, i));
+    }
+
+    // `closest[v]` is the cheapest edge connecting `v` to the current tree.
+    let mut closest: Vec<Option<usize>> = vec![None; graph.node_bound()];
+    let mut in_tree = vec![false; graph.node_bound()];
+    let mut frontier = Vec::new();
+    let mut chosen = Vec::new();
+    for start in graph.node_identifiers() {
+        if in_tree[graph.to_index(start)] {
+            continue;
+        }
+        let mut next = Some(graph.to_index(start));
+        while let Some(u) = next {
+            in_tree[u] = true;
+            if let Some(i) = closest[u] {
+                chosen.push(i);
+            }
+            for &(v, i) in &adjacency[u] {
+                if in_tree[v] {
+                    continue;
+                }
+                match closest[v] {
+                    None => {
+                        closest[v] = Some(i);
+                        frontier.push(v);
+                    }
+                    Some(j) if edges[i].1 < edges[j].1 => closest[v] = Some(i),
+                    Some(_) => {}
+                }
+            }
+
+            // Pick the closest node outside of the tree.
+            frontier.retain(|&v| !in_tree[v]);
+            next = None;
+            for &v in &frontier {
+                let better = match next {
+                    None => true,
+                    Some(w) => edges[closest[v].unwrap()].1 < edges[closest[w].unwrap()].1,
+                };
+                if better {
+                    next = Some(v);
+                }
+            }
+        }
+    }
+    forest(&edges, chosen)
+}
+
+/// \[Generic\] Compute a *minimum spanning forest* of a graph using
+/// [Borůvka's algorithm][1].
+///
+/// The input graph is treated as if undirected. The function `edge_cost`
+/// should return the cost for a particular edge.
+///
+/// Every round connects each tree of the forest with the closest other tree,
+/// which at least halves the number of trees, for a running time of
+/// **O(|E| log |V|)**.
+///
+/// [1]: https://en.wikipedia.org/wiki/Bor%C5%AFvka%27s_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::boruvka_spanning_forest;
+///
+/// let g = Graph::<(), f64>::from_edges(&[
+///     (0, 1, 1.5), (1, 2, 0.5), (0, 2, 1.0), (3, 4, 2.0),
+/// ]);
+///
+/// let forest = boruvka_spanning_forest(&g, |e| *e.weight());
+/// assert_eq!(forest.len(), 3);
+/// assert_eq!(*forest.total_weight(), 3.5);
+/// ```
+pub fn boruvka_spanning_forest<G, F, K>(graph: G, edge_cost: F) -> SpanningForest<G::EdgeRef, K>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    boruvka(graph, edge_cost, |a, b| a.partial_cmp(b))
+}
+
+/// \[Generic\] Compute a *maximum spanning forest* of a graph.
+///
+/// The input graph is treated as if undirected. The function `edge_cost`
+/// should return the cost for a particular edge; the forest with the largest
+/// total cost is returned. This uses Borůvka's algorithm, see
+/// [`boruvka_spanning_forest`](fn.boruvka_spanning_forest.html).
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::max_spanning_forest;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, 5), (0, 2, 3)]);
+///
+/// let forest = max_spanning_forest(&g, |e| *e.weight());
+/// assert_eq!(*forest.total_weight(), 8);
+/// ```
+pub fn max_spanning_forest<G, F, K>(graph: G, edge_cost: F) -> SpanningForest<G::EdgeRef, K>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    boruvka(graph, edge_cost, |a, b| b.partial_cmp(a))
+}
+
+fn boruvka<G, F, K, C>(graph: G, mut edge_cost: F, compare: C) -> SpanningForest<G::EdgeRef, K>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+    C: Fn(&K, &K) -> Option<Ordering>,
+{
+    let edges: Vec<_> = graph.edge_references().map(|e| (e, edge_cost(e))).collect();
+    let endpoints: Vec<_> = edges
+        .iter()
+        .map(|&(e, _)| (graph.to_index(e.source()), graph.to_index(e.target())))
+        .collect();
+    // Ties are broken by edge position, so that equal costs can't form a cycle.
+    let preferred = |i: usize, j: usize| match compare(&edges[i].1, &edges[j].1) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Greater) => false,
+        _ => i < j,
+    };
+
+    let mut trees = UnionFind::new(graph.node_bound());
+    let mut cheapest: Vec<Option<usize>> = vec![None; graph.node_bound()];
+    let mut chosen = Vec::new();
+    loop {
+        for (i, &(a, b)) in endpoints.iter().enumerate() {
+            let (ra, rb) = (trees.find_mut(a), trees.find_mut(b));
+            if ra == rb {
+                continue;
+            }
+            for &r in &[ra, rb] {
+                match cheapest[r] {
+                    Some(j) if !preferred(i, j) => {}
+                    _ => cheapest[r] = Some(i),
+                }
+            }
+        }
+
+        let mut merged = false;
+        for slot in &mut cheapest {
+            if let Some(i) = slot.take() {
+                let (a, b) = endpoints[i];
+                if trees.union(a, b) {
+                    chosen.push(i);
+                    merged = true;
+                }
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    forest(&edges, chosen)
+}
+
+fn forest<E, K>(edges: &[(E, K)], chosen: Vec<usize>) -> SpanningForest<E, K>
+where
+    E: Copy,
+    K: Measure + Copy,
+{
+    let total_weight = chosen.iter().fold(K::default(), |sum, &i| sum + edges[i].1);
+    SpanningForest {
+        edges: chosen.into_iter().map(|i| edges[i].0).collect(),
+        total_weight,
+    }
+}
@@ -0,0 +1,539 @@
+//! Trees: centers, canonical encodings and isomorphism.
+//!
+//! A *tree* is a connected graph with one edge less than it has nodes, so it
+//! has no cycles, self loops or parallel edges. The direction of edges is
+//! ignored, and a rooted tree is given by a tree and its root.
+//!
+//! The *canonical encoding* of a rooted tree is a string of balanced
+//! parentheses: each node is written as `(`, followed by the encodings of its
+//! children in a canonical order, followed by `)`. Two rooted trees are
+//! isomorphic if and only if their encodings are equal. The encoding of an
+//! unrooted tree is the least encoding of the tree rooted at a center.
+//!
+//! The children are ordered with the algorithm of Aho, Hopcroft and Ullman,
+//! from *The Design and Analysis of Computer Algorithms*, in time linear in
+//! the number of nodes.
+
+use crate::visit::{GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// An algorithm error: the graph is not a tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NotATree {
+    /// The graph has no nodes.
+    Empty,
+    /// Some nodes are not connected to the others.
+    Disconnected,
+    /// The graph has a cycle, a self loop or parallel edges.
+    Cycle,
+}
+
+/// \[Generic\] Return the center of the tree: the one node, or the two
+/// adjacent nodes, whose largest distance to any other node is the least.
+///
+/// The nodes are returned in the order of the graph's node identifiers.
+///
+/// Returns a `NotATree` error if the graph is not a tree.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::tree::{tree_center, NotATree};
+///
+/// // A path of four nodes has two centers.
+/// let mut g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert_eq!(tree_center(&g), Ok(vec![1.into(), 2.into()]));
+///
+/// let leaf = g.add_node(());
+/// g.add_edge(3.into(), leaf, ());
+/// assert_eq!(tree_center(&g), Ok(vec![2.into()]));
+///
+/// g.add_edge(leaf, 0.into(), ());
+/// assert_eq!(tree_center(&g), Err(NotATree::Cycle));
+/// ```
+pub fn tree_center<G>(graph: G) -> Result<Vec<G::NodeId>, NotATree>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let tree = Tree::new(graph)?;
+    Ok(tree.center().into_iter().map(|u| tree.nodes[u]).collect())
+}
+
+/// \[Generic\] Return the canonical encoding of the tree rooted at `root`.
+///
+/// See the [module documentation](index.html) for the encoding.
+///
+/// Returns a `NotATree` error if the graph is not a tree.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// **Panics** if `root` is not a node of the graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::DiGraph;
+/// use petgraph::algo::tree::rooted_tree_encoding;
+///
+/// // A root with a leaf and a path of two nodes as children; the order of
+/// // the children does not matter.
+/// let a = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (2, 3)]);
+/// let b = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 3)]);
+/// assert_eq!(rooted_tree_encoding(&a, 0.into()), Ok("(()(()))".to_string()));
+/// assert_eq!(rooted_tree_encoding(&b, 0.into()), Ok("(()(()))".to_string()));
+///
+/// // The same tree rooted at a leaf.
+/// assert_eq!(rooted_tree_encoding(&a, 3.into()), Ok("(((())))".to_string()));
+/// ```
+pub fn rooted_tree_encoding<G>(graph: G, root: G::NodeId) -> Result<String, NotATree>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let root = graph.to_index(root);
+    let tree = Tree::new(graph)?;
+    Ok(tree.encoding(tree.compact(root)))
+}
+
+/// \[Generic\] Return the canonical encoding of the unrooted tree.
+///
+/// See the [module documentation](index.html) for the encoding.
+///
+/// Returns a `NotATree` error if the graph is not a tree.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::tree::tree_encoding;
+///
+/// // Two numberings of a path of three nodes.
+/// let a = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let b = UnGraph::<(), ()>::from_edges(&[(0, 2), (2, 1)]);
+/// assert_eq!(tree_encoding(&a), Ok("(()())".to_string()));
+/// assert_eq!(tree_encoding(&a), tree_encoding(&b));
+/// ```
+pub fn tree_encoding<G>(graph: G) -> Result<String, NotATree>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    Ok(Tree::new(graph)?.unrooted_encoding())
+}
+
+/// \[Generic\] Return `true` if the trees rooted at `root0` and `root1` are
+/// isomorphic, with an isomorphism that maps `root0` to `root1`.
+///
+/// Returns a `NotATree` error if either graph is not a tree.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// **Panics** if a root is not a node of its graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::tree::is_isomorphic_rooted_tree;
+///
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// assert_eq!(is_isomorphic_rooted_tree(&path, 0.into(), &path, 2.into()), Ok(true));
+/// assert_eq!(is_isomorphic_rooted_tree(&path, 0.into(), &path, 1.into()), Ok(false));
+/// ```
+pub fn is_isomorphic_rooted_tree<G0, G1>(
+    g0: G0,
+    root0: G0::NodeId,
+    g1: G1,
+    root1: G1::NodeId,
+) -> Result<bool, NotATree>
+where
+    G0: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G1: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let (root0, root1) = (g0.to_index(root0), g1.to_index(root1));
+    let (t0, t1) = (Tree::new(g0)?, Tree::new(g1)?);
+    let (root0, root1) = (t0.compact(root0), t1.compact(root1));
+    Ok(t0.nodes.len() == t1.nodes.len() && t0.encoding(root0) == t1.encoding(root1))
+}
+
+/// \[Generic\] Return `true` if the trees are isomorphic.
+///
+/// This is faster than [`is_isomorphic`](../fn.is_isomorphic.html) for
+/// trees.
+///
+/// Returns a `NotATree` error if either graph is not a tree.
+///
+/// Runtime is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::tree::{is_isomorphic_tree, NotATree};
+///
+/// // Two numberings of a star with three leaves, and a path.
+/// let a = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+/// let b = UnGraph::<(), ()>::from_edges(&[(3, 0), (3, 1), (3, 2)]);
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert_eq!(is_isomorphic_tree(&a, &b), Ok(true));
+/// assert_eq!(is_isomorphic_tree(&a, &path), Ok(false));
+///
+/// let forest = UnGraph::<(), ()>::from_edges(&[(0, 1), (2, 3)]);
+/// assert_eq!(is_isomorphic_tree(&a, &forest), Err(NotATree::Disconnected));
+/// ```
+pub fn is_isomorphic_tree<G0, G1>(g0: G0, g1: G1) -> Result<bool, NotATree>
+where
+    G0: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G1: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let (t0, t1) = (Tree::new(g0)?, Tree::new(g1)?);
+    Ok(t0.nodes.len() == t1.nodes.len() && t0.unrooted_encoding() == t1.unrooted_encoding())
+}
+
+/// A tree with compact node indices.
+struct Tree<N> {
+    nodes: Vec<N>,
+    /// The compact index of each node index.
+    index: Vec<usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<N: Copy> Tree<N> {
+    fn new<G>(graph: G) -> Result<Self, NotATree>
+    where
+        G: IntoNeighbors<NodeId = N> + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    {
+        let nodes: Vec<N> = graph.node_identifiers().collect();
+        if nodes.is_empty() {
+            return Err(NotATree::Empty);
+        }
+        let mut index = vec![usize::MAX; graph.node_bound()];
+        for (i, &u) in nodes.iter().enumerate() {
+            index[graph.to_index(u)] = i;
+        }
+        // An undirected edge is found from both of its endpoints.
+        let mut adjacency = vec![Vec::new(); nodes.len()];
+        let mut arcs = 0;
+        for (i, &u) in nodes.iter().enumerate() {
+            for v in graph.neighbors(u) {
+                let j = index[graph.to_index(v)];
+                adjacency[i].push(j);
+                if graph.is_directed() {
+                    adjacency[j].push(i);
+                }
+                arcs += 1;
+            }
+        }
+        let tree_arcs = if graph.is_directed() { 1 } else { 2 } * (nodes.len() - 1);
+
+        let mut discovered = vec![false; nodes.len()];
+        discovered[0] = true;
+        let mut stack = vec![0];
+        let mut reached = 1;
+        while let Some(u) = stack.pop() {
+            for &v in &adjacency[u] {
+                if !discovered[v] {
+                    discovered[v] = true;
+                    reached += 1;
+                    stack.push(v);
+                }
+            }
+        }
+        if reached < nodes.len() {
+            Err(NotATree::Disconnected)
+        } else if arcs != tree_arcs {
+            Err(NotATree::Cycle)
+        } else {
+            Ok(Tree {
+                nodes,
+                index,
+                adjacency,
+            })
+        }
+    }
+
+    /// Return the compact index of the node with index `u`.
+    fn compact(&self, u: usize) -> usize {
+        match self.index.get(u) {
+            Some(&u) if u != usize::MAX => u,
+            _ => panic!("root is not a node of the graph"),
+        }
+    }
+
+    /// Return the centers, by removing the leaves until at most two nodes
+    /// are left.
+    fn center(&self) -> Vec<usize> {
+        let n = self.nodes.len();
+        let mut degree: Vec<usize> = self.adjacency.iter().map(Vec::len).collect();
+        let mut leaves: Vec<usize> = (0..n).filter(|&u| degree[u] <= 1).collect();
+        let mut remaining = n;
+        while remaining > 2 {
+            remaining -= leaves.len();
+            for &u in &leaves {
+                degree[u] = 0;
+            }
+            let mut next = Vec::new();
+            for &u in &leaves {
+                for &v in &self.adjacency[u] {
+                    if degree[v] > 0 {
+                        degree[v] -= 1;
+                        if degree[v] == 1 {
+                            next.push(v);
+                        }
+                    }
+                }
+            }
+            leaves = next;
+        }
+        leaves.sort();
+        leaves
+    }
+
+    fn unrooted_encoding(&self) -> String {
+        self.center()
+            .into_iter()
+            .map(|c| self.encoding(c))
+            .min()
+            .unwrap()
+    }
+
+    /// Return the encoding of the tree rooted at `root`.
+    fn encoding(&self, root: usize) -> String {
+        let n = self.nodes.len();
+        // Breadth first order, which lists the nodes level by level; each
+        // level is `order[levels[d]..levels[d + 1]]`.
+        let mut order = Vec::with_capacity(n);
+        let mut position = vec![usize::MAX; n];
+        let mut parent = vec![0; n];
+        let mut levels = vec![0];
+        position[root] = 0;
+        order.push(root);
+        while *levels.last().unwrap() < n {
+            let (start, end) = (*levels.last().unwrap(), order.len());
+            for i in start..end {
+                let u = order[i];
+                for &v in &self.adjacency[u] {
+                    if position[v] == usize::MAX {
+                        position[v] = order.len();
+                        parent[v] = i;
+                        order.push(v);
+                    }
+                }
+            }
+            levels.push(end);
+        }
+        levels.push(n);
+
+        // Number the isomorphism classes of the subtrees level by level, from
+        // the deepest, in the order of their tuples of child classes. The
+        // children of the node at `order[i]` are sorted by class, and stored
+        // in `children[first_child[i]..first_child[i + 1]]`.
+        let mut class = vec![0; n];
+        let mut classes = 0;
+        let mut children = vec![0; n];
+        let mut first_child = vec![n; n + 1];
+        let mut sorter = TupleSorter::default();
+        let mut by_class = Vec::new();
+        let mut grouped = Vec::new();
+        let mut offsets = Vec::new();
+        let mut tuples = Vec::new();
+        for window in levels.windows(3).rev() {
+            let (start, end, lower_end) = (window[0], window[1], window[2]);
+            let lower = &order[end..lower_end];
+            counting_sort(lower, classes, |v| class[v], &mut by_class, &mut offsets);
+            counting_sort(
+                &by_class,
+                end - start,
+                |v| parent[v] - start,
+                &mut grouped,
+                &mut offsets,
+            );
+            children[end..lower_end].copy_from_slice(&grouped);
+            for (first, &offset) in first_child[start..end].iter_mut().zip(&offsets) {
+                *first = end + offset;
+            }
+            tuples.clear();
+            tuples.extend(grouped.iter().map(|&v| class[v]));
+
+            let sorted = sorter.sort(&offsets, &tuples, classes);
+            let tuple = |i: usize| &tuples[offsets[i]..offsets[i + 1]];
+            classes = 0;
+            for (k, &i) in sorted.iter().enumerate() {
+                if k > 0 && tuple(i) != tuple(sorted[k - 1]) {
+                    classes += 1;
+                }
+                class[order[start + i]] = classes;
+            }
+            classes += 1;
+        }
+
+        let mut encoding = String::with_capacity(2 * n);
+        encoding.push('(');
+        let mut stack = vec![(first_child[0], first_child[1])];
+        while let Some(top) = stack.last_mut() {
+            if top.0 < top.1 {
+                let i = position[children[top.0]];
+                top.0 += 1;
+                encoding.push('(');
+                stack.push((first_child[i], first_child[i + 1]));
+            } else {
+                encoding.push(')');
+                stack.pop();
+            }
+        }
+        encoding
+    }
+}
+
+/// Sort `items` stably by `key`, which is less than `keys`, into `sorted`.
+/// Afterwards, the items with key **k** are `sorted[start[k]..start[k + 1]]`.
+fn counting_sort<K>(
+    items: &[usize],
+    keys: usize,
+    key: K,
+    sorted: &mut Vec<usize>,
+    start: &mut Vec<usize>,
+) where
+    K: Fn(usize) -> usize,
+{
+    start.clear();
+    start.resize(keys + 1, 0);
+    for &i in items {
+        start[key(i)] += 1;
+    }
+    let mut total = 0;
+    for s in start.iter_mut() {
+        total += *s;
+        *s = total;
+    }
+    sorted.clear();
+    sorted.resize(items.len(), 0);
+    for &i in items.iter().rev() {
+        let k = key(i);
+        start[k] -= 1;
+        sorted[start[k]] = i;
+    }
+}
+
+/// Lexicographic sorting of tuples, with the buffers reused between sorts.
+#[derive(Default)]
+struct TupleSorter {
+    indices: Vec<usize>,
+    by_length: Vec<usize>,
+    length_start: Vec<usize>,
+    element_position: Vec<usize>,
+    by_number: Vec<usize>,
+    by_position: Vec<usize>,
+    position_start: Vec<usize>,
+    occurring: Vec<usize>,
+    occurring_start: Vec<usize>,
+    head: Vec<usize>,
+    tail: Vec<usize>,
+    next: Vec<usize>,
+    queue: Vec<usize>,
+    sorted: Vec<usize>,
+}
+
+impl TupleSorter {
+    /// Sort the tuples `data[offsets[i]..offsets[i + 1]]` of numbers less
+    /// than `alphabet` lexicographically, where a tuple comes before its
+    /// extensions. Return the indices of the tuples in sorted order.
+    ///
+    /// Runtime is **O(alphabet + m + l)** for **m** tuples of total length
+    /// **l**.
+    fn sort(&mut self, offsets: &[usize], data: &[usize], alphabet: usize) -> &[usize] {
+        let TupleSorter {
+            ref mut indices,
+            ref mut by_length,
+            ref mut length_start,
+            ref mut element_position,
+            ref mut by_number,
+            ref mut by_position,
+            ref mut position_start,
+            ref mut occurring,
+            ref mut occurring_start,
+            ref mut head,
+            ref mut tail,
+            ref mut next,
+            ref mut queue,
+            ref mut sorted,
+        } = *self;
+        let m = offsets.len() - 1;
+        let length = |i: usize| offsets[i + 1] - offsets[i];
+        let max_length = (0..m).map(length).max().unwrap_or(0);
+        let count = m.max(data.len());
+        let have = indices.len();
+        indices.extend(have..count);
+        counting_sort(
+            &indices[..m],
+            max_length + 1,
+            length,
+            by_length,
+            length_start,
+        );
+
+        // The numbers that occur at each position, in increasing order.
+        element_position.clear();
+        for i in 0..m {
+            element_position.extend(0..length(i));
+        }
+        let element_position = &*element_position;
+        counting_sort(
+            &indices[..data.len()],
+            alphabet,
+            |e| data[e],
+            by_number,
+            position_start,
+        );
+        counting_sort(
+            by_number,
+            max_length,
+            |e| element_position[e],
+            by_position,
+            position_start,
+        );
+        occurring.clear();
+        occurring_start.clear();
+        for p in 0..max_length {
+            occurring_start.push(occurring.len());
+            for &e in &by_position[position_start[p]..position_start[p + 1]] {
+                if occurring.len() == occurring_start[p] || occurring.last() != Some(&data[e]) {
+                    occurring.push(data[e]);
+                }
+            }
+        }
+        occurring_start.push(occurring.len());
+
+        // Distribute the tuples into buckets from the last position, where
+        // the tuples that end at a position join in front of the longer ones.
+        head.clear();
+        head.resize(alphabet, usize::MAX);
+        tail.resize(alphabet, 0);
+        next.resize(m, 0);
+        queue.clear();
+        for p in (0..max_length).rev() {
+            sorted.clear();
+            sorted.extend_from_slice(&by_length[length_start[p + 1]..length_start[p + 2]]);
+            sorted.append(queue);
+            for &i in sorted.iter() {
+                let x = data[offsets[i] + p];
+                next[i] = usize::MAX;
+                if head[x] == usize::MAX {
+                    head[x] = i;
+                } else {
+                    next[tail[x]] = i;
+                }
+                tail[x] = i;
+            }
+            for &x in &occurring[occurring_start[p]..occurring_start[p + 1]] {
+                let mut i = head[x];
+                while i != usize::MAX {
+                    queue.push(i);
+                    i = next[i];
+                }
+                head[x] = usize::MAX;
+            }
+        }
+        sorted.clear();
+        sorted.extend_from_slice(&by_length[..length_start[1]]);
+        sorted.append(queue);
+        sorted
+    }
+}
@@ -0,0 +1,279 @@
+//! Triangle counting and clustering coefficients.
+
+use std::cmp::Ordering;
+
+use super::adjacency::Adjacency;
+use crate::csr::{Csr, IndexType};
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+use crate::Undirected;
+
+/// \[Generic\] Return the number of triangles that each node is part of.
+///
+/// The edges of directed graphs are treated as if undirected; self loops and
+/// parallel edges are ignored. The counts are indexed by
+/// [`NodeIndexable::to_index`](../visit/trait.NodeIndexable.html#tymethod.to_index).
+///
+/// Nodes are ranked by degree, and every triangle is found once, from its
+/// lowest ranked node, by intersecting the lists of higher ranked neighbors.
+/// Runtime is **O(|E|<sup>3/2</sup>)**, plus **O(|E| log |E|)** to build the
+/// adjacency lists. For an undirected `Csr`,
+/// [`triangles_csr`](fn.triangles_csr.html) works on the sorted neighbor
+/// slices directly.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::triangles;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+///
+/// assert_eq!(triangles(&g), vec![1, 2, 2, 1]);
+/// ```
+pub fn triangles<G>(graph: G) -> Vec<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    TriangleCounts::new(graph).triangles
+}
+
+/// \[Generic\] Return the local *clustering coefficient* of each node.
+///
+/// The clustering coefficient of a node is the fraction of pairs of its
+/// neighbors that are adjacent, or `0.` for nodes with fewer than two
+/// neighbors. It is computed from the triangle counts, see
+/// [`triangles`](fn.triangles.html), and indexed the same way.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::clustering;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+///
+/// let c = clustering(&g);
+/// assert_eq!(c[0], 1.);
+/// assert_eq!(c[1], 2. / 3.);
+/// ```
+pub fn clustering<G>(graph: G) -> Vec<f64>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    TriangleCounts::new(graph).clustering()
+}
+
+/// \[Generic\] Return the *transitivity* (global clustering coefficient) of the
+/// graph.
+///
+/// The transitivity is the fraction of paths of length two whose endpoints
+/// are adjacent: three times the number of triangles, divided by the number
+/// of paths of length two, or `0.` if there are none. See
+/// [`triangles`](fn.triangles.html).
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::transitivity;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+///
+/// assert_eq!(transitivity(&g), 6. / 8.);
+/// ```
+pub fn transitivity<G>(graph: G) -> f64
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    TriangleCounts::new(graph).transitivity()
+}
+
+/// Return the number of triangles that each node of an undirected `Csr` is
+/// part of.
+///
+/// This is [`triangles`](fn.triangles.html) for a `Csr`: the sorted neighbor
+/// slices are intersected directly, without building adjacency lists, and
+/// every triangle is found from its node with the smallest index. Self loops
+/// are ignored. Runtime is **O(|E| Δ)**, where **Δ** is the maximum degree.
+///
+/// # Example
+/// ```rust
+/// use petgraph::csr::Csr;
+/// use petgraph::Undirected;
+/// use petgraph::algo::triangles_csr;
+///
+/// let mut g = Csr::<(), (), Undirected>::with_nodes(4);
+/// for &(a, b) in &[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)] {
+///     g.add_edge(a, b, ());
+/// }
+///
+/// assert_eq!(triangles_csr(&g), vec![1, 2, 2, 1]);
+/// ```
+pub fn triangles_csr<N, E, Ix>(graph: &Csr<N, E, Undirected, Ix>) -> Vec<usize>
+where
+    Ix: IndexType,
+{
+    TriangleCounts::from_csr(graph).triangles
+}
+
+/// Return the local clustering coefficient of each node of an undirected
+/// `Csr`.
+///
+/// See [`clustering`](fn.clustering.html) and
+/// [`triangles_csr`](fn.triangles_csr.html).
+pub fn clustering_csr<N, E, Ix>(graph: &Csr<N, E, Undirected, Ix>) -> Vec<f64>
+where
+    Ix: IndexType,
+{
+    TriangleCounts::from_csr(graph).clustering()
+}
+
+/// Return the transitivity of an undirected `Csr`.
+///
+/// See [`transitivity`](fn.transitivity.html) and
+/// [`triangles_csr`](fn.triangles_csr.html).
+pub fn transitivity_csr<N, E, Ix>(graph: &Csr<N, E, Undirected, Ix>) -> f64
+where
+    Ix: IndexType,
+{
+    TriangleCounts::from_csr(graph).transitivity()
+}
+
+/// Triangle counts and degrees (without self loops), by node index.
+struct TriangleCounts {
+    triangles: Vec<usize>,
+    degree: Vec<usize>,
+}
+
+impl TriangleCounts {
+    fn new<G>(graph: G) -> Self
+    where
+        G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    {
+        let adjacency = Adjacency::new(graph);
+        let length = adjacency.len();
+
+        // Rank the nodes by degree; `forward[r]` holds the ranks of the
+        // neighbors of the node with rank `r` that have a higher rank.
+        let mut by_degree: Vec<_> = (0..length).collect();
+        by_degree.sort_by_key(|&v| adjacency.degree(v));
+        let mut rank = vec![0; length];
+        for (r, &v) in by_degree.iter().enumerate() {
+            rank[v] = r;
+        }
+        let forward: Vec<Vec<usize>> = by_degree
+            .iter()
+            .map(|&v| {
+                let mut higher: Vec<_> = adjacency.neighbors[v]
+                    .iter()
+                    .map(|&w| rank[w])
+                    .filter(|&s| s > rank[v])
+                    .collect();
+                higher.sort();
+                higher
+            })
+            .collect();
+
+        let mut by_rank = vec![0; length];
+        for (r, list) in forward.iter().enumerate() {
+            for &s in list {
+                for_each_common(list, &forward[s], |t| {
+                    by_rank[r] += 1;
+                    by_rank[s] += 1;
+                    by_rank[t] += 1;
+                });
+            }
+        }
+
+        let mut triangles = vec![0; graph.node_bound()];
+        let mut degree = vec![0; graph.node_bound()];
+        for (v, &n) in adjacency.nodes.iter().enumerate() {
+            triangles[graph.to_index(n)] = by_rank[rank[v]];
+            degree[graph.to_index(n)] = adjacency.degree(v);
+        }
+        TriangleCounts { triangles, degree }
+    }
+
+    fn from_csr<N, E, Ix>(graph: &Csr<N, E, Undirected, Ix>) -> Self
+    where
+        Ix: IndexType,
+    {
+        let length = graph.node_count();
+        // The neighbors of `a` with a larger index.
+        let forward = |a: usize| {
+            let neighbors = graph.neighbors_slice(Ix::new(a));
+            let start = match neighbors.binary_search(&Ix::new(a)) {
+                Ok(i) => i + 1,
+                Err(i) => i,
+            };
+            &neighbors[start..]
+        };
+
+        let mut triangles = vec![0; length];
+        let mut degree = vec![0; length];
+        for a in 0..length {
+            let neighbors = graph.neighbors_slice(Ix::new(a));
+            degree[a] = neighbors.len();
+            if neighbors.binary_search(&Ix::new(a)).is_ok() {
+                degree[a] -= 1;
+            }
+            let higher = forward(a);
+            for &b in higher {
+                for_each_common(higher, forward(b.index()), |c| {
+                    triangles[a] += 1;
+                    triangles[b.index()] += 1;
+                    triangles[c.index()] += 1;
+                });
+            }
+        }
+        TriangleCounts { triangles, degree }
+    }
+
+    fn clustering(&self) -> Vec<f64> {
+        self.triangles
+            .iter()
+            .zip(&self.degree)
+            .map(|(&t, &d)| {
+                if d < 2 {
+                    0.
+                } else {
+                    2. * t as f64 / (d * (d - 1)) as f64
+                }
+            })
+            .collect()
+    }
+
+    fn transitivity(&self) -> f64 {
+        let triangles: usize = self.triangles.iter().sum();
+        let paths: usize = self
+            .degree
+            .iter()
+            .map(|&d| d * d.saturating_sub(1) / 2)
+            .sum();
+        if paths == 0 {
+            0.
+        } else {
+            triangles as f64 / paths as f64
+        }
+    }
+}
+
+/// Call `f` for every element of both sorted slices.
+fn for_each_common<T, F>(a: &[T], b: &[T], mut f: F)
+where
+    T: Ord + Copy,
+    F: FnMut(T),
+{
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                f(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+}
@@ -3,12 +3,12 @@
 use std::cmp::max;
 use std::iter::{Enumerate, Zip};
 use std::marker::PhantomData;
-use std::ops::{Index, IndexMut, Range};
+use std::ops::{Add, Index, IndexMut, Range};
 use std::slice::Windows;
 
-use crate::visit::{Data, GraphProp, IntoEdgeReferences, NodeCount};
+use crate::visit::{incident_weight, Data, Degree, GraphProp, IntoEdgeReferences, NodeCount};
 use crate::visit::{EdgeRef, GraphBase, IntoEdges, IntoNeighbors, NodeIndexable};
-use crate::visit::{IntoNodeIdentifiers, NodeCompactIndexable, Visitable};
+use crate::visit::{IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable, Visitable};
 
 use crate::util::zip;
 
@@ -556,6 +556,42 @@ where
     }
 }
 
+/// The in-degree of a directed graph is computed in **O(|E|)** time, since
+/// only the outgoing edges of each node are stored.
+impl<N, E, Ty, Ix> Degree for &Csr<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        incident_weight(Ty::is_directed(), self.edges(a), edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        if !Ty::is_directed() {
+            return self.weighted_out_degree(a, edge_weight);
+        }
+        let edges = self.edge_references().filter(|edge| edge.target() == a);
+        incident_weight(true, edges, edge_weight)
+    }
+
+    fn out_degree(self, a: Self::NodeId) -> usize {
+        if Ty::is_directed() {
+            Csr::out_degree(self, a)
+        } else {
+            self.weighted_out_degree(a, |_| 1)
+        }
+    }
+}
+
 impl<N, E, Ty, Ix> GraphBase for Csr<N, E, Ty, Ix>
 where
     Ty: EdgeType,
@@ -704,6 +740,41 @@ where
     }
 }
 
+impl<'a, N, E, Ty, Ix> IntoNodeReferences for &'a Csr<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type NodeRef = (NodeIndex<Ix>, &'a N);
+    type NodeReferences = NodeReferences<'a, N, Ix>;
+    fn node_references(self) -> Self::NodeReferences {
+        NodeReferences {
+            iter: self.node_weights.iter().enumerate(),
+            ty: PhantomData,
+        }
+    }
+}
+
+pub struct NodeReferences<'a, N: 'a, Ix = DefaultIx> {
+    iter: Enumerate<SliceIter<'a, N>>,
+    ty: PhantomData<Ix>,
+}
+
+impl<'a, N, Ix> Iterator for NodeReferences<'a, N, Ix>
+where
+    Ix: IndexType,
+{
+    type Item = (NodeIndex<Ix>, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(i, weight)| (Ix::new(i), weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 impl<N, E, Ty, Ix> NodeCount for Csr<N, E, Ty, Ix>
 where
     Ty: EdgeType,
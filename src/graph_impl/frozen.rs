@@ -1,10 +1,12 @@
-use std::ops::{Deref, Index, IndexMut};
+use std::ops::{Add, Deref, Index, IndexMut};
 
 use super::Frozen;
 use crate::data::{DataMap, DataMapMut};
 use crate::graph::Graph;
 use crate::graph::{GraphIndex, IndexType};
-use crate::visit::{Data, GraphProp, IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable};
+use crate::visit::{
+    Data, Degree, GraphProp, IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable,
+};
 use crate::visit::{
     GetAdjacencyMatrix, IntoEdges, IntoEdgesDirected, NodeCompactIndexable, NodeCount,
 };
@@ -72,6 +74,27 @@ where
     }
 }
 
+impl<'a, 'b, G> Degree for &'b Frozen<'a, G>
+where
+    G: Degree,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        (**self).weighted_out_degree(a, edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        (**self).weighted_in_degree(a, edge_weight)
+    }
+}
+
 macro_rules! access0 {
     ($e:expr) => {
         $e.0
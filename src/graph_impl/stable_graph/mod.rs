@@ -9,7 +9,7 @@ use std::iter;
 use std::marker::PhantomData;
 use std::mem::replace;
 use std::mem::size_of;
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut};
 use std::slice;
 
 use crate::{Directed, Direction, EdgeType, Graph, Incoming, Outgoing, Undirected};
@@ -19,7 +19,8 @@ use crate::iter_utils::IterUtilsExt;
 
 use super::{index_twice, Edge, Frozen, Node, Pair, DIRECTIONS};
 use crate::visit::{
-    EdgeRef, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNodeReferences, NodeIndexable,
+    incident_weight, Degree, EdgeRef, IntoEdgeReferences, IntoEdges, IntoEdgesDirected,
+    IntoNodeReferences, NodeIndexable,
 };
 use crate::IntoWeightedEdge;
 
@@ -1298,6 +1299,29 @@ where
     }
 }
 
+impl<N, E, Ty, Ix> Degree for &StableGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        incident_weight(self.is_directed(), self.edges(a), edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        let edges = self.edges_directed(a, Incoming);
+        incident_weight(self.is_directed(), edges, edge_weight)
+    }
+}
+
 impl<'a, N, E, Ty, Ix> IntoEdgesDirected for &'a StableGraph<N, E, Ty, Ix>
 where
     Ty: EdgeType,
@@ -10,14 +10,14 @@ use std::hash::{self, Hash};
 use std::iter::FromIterator;
 use std::iter::{Cloned, DoubleEndedIterator};
 use std::marker::PhantomData;
-use std::ops::{Deref, Index, IndexMut};
+use std::ops::{Add, Deref, Index, IndexMut};
 use std::slice::Iter;
 
 use crate::{Directed, Direction, EdgeType, Incoming, Outgoing, Undirected};
 
 use crate::graph::node_index;
 use crate::graph::Graph;
-use crate::visit::{IntoEdgeReferences, IntoEdges, NodeCompactIndexable};
+use crate::visit::{incident_weight, Degree, IntoEdgeReferences, IntoEdges, NodeCompactIndexable};
 use crate::visit::{IntoNodeIdentifiers, IntoNodeReferences, NodeCount, NodeIndexable};
 use crate::IntoWeightedEdge;
 
@@ -734,6 +734,34 @@ where
     }
 }
 
+impl<N, E, Ty> Degree for &GraphMap<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        incident_weight(Ty::is_directed(), self.edges(a), edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        if !Ty::is_directed() {
+            return self.weighted_out_degree(a, edge_weight);
+        }
+        let edges = self
+            .neighbors_directed(a, Incoming)
+            .map(|b| (b, a, &self.edges[&GraphMap::<N, E, Ty>::edge_key(b, a)]));
+        incident_weight(true, edges, edge_weight)
+    }
+}
+
 /// Index `GraphMap` by node pairs to access edge weights.
 impl<N, E, Ty> Index<(N, N)> for GraphMap<N, E, Ty>
 where
@@ -1,16 +1,32 @@
 use fixedbitset::FixedBitSet;
-use std::marker;
+use std::collections::HashMap;
 
-use super::graph::{Graph, IndexType, NodeIndex};
-use super::{EdgeType, Incoming};
+use super::unionfind::UnionFind;
+use super::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNeighbors, IntoNodeIdentifiers, IntoNodeReferences,
+    NodeIndexable, NodeRef,
+};
 
-use super::visit::GetAdjacencyMatrix;
+/// The mapping of a node that is not mapped.
+const UNMAPPED: usize = usize::MAX;
 
+/// The edges of a graph, in lock step with the adjacency lists of its
+/// `Vf2State`.
+struct Vf2Edges<R> {
+    succ: Vec<Vec<R>>,
+    pred: Vec<Vec<R>>,
+}
+
+/// The structure of a graph and its side of the mapping.
+///
+/// The nodes of the graph are numbered from 0, in the order of their
+/// indices, so that graphs with holes in their indices (like `StableGraph`)
+/// and graphs of different types can be matched.
 #[derive(Debug)]
-struct Vf2State<Ty, Ix> {
+struct Vf2State {
     /// The current mapping M(s) of nodes from G0 → G1 and G1 → G0,
-    /// NodeIndex::end() for no mapping.
-    mapping: Vec<NodeIndex<Ix>>,
+    /// UNMAPPED for no mapping.
+    mapping: Vec<usize>,
     /// out[i] is non-zero if i is in either M_0(s) or Tout_0(s)
     /// These are all the next vertices that are not mapped yet, but
     /// have an outgoing edge from the mapping.
@@ -22,36 +38,80 @@ struct Vf2State<Ty, Ix> {
     ins: Vec<usize>,
     out_size: usize,
     ins_size: usize,
+    /// The successors of each node, without repetitions.
+    succ: Vec<Vec<usize>>,
+    /// The predecessors of each node, without repetitions.
+    /// Unused if graph is undirected.
+    pred: Vec<Vec<usize>>,
     adjacency_matrix: FixedBitSet,
+    /// The number of ones in the adjacency matrix.
+    arc_count: usize,
     generation: usize,
-    _etype: marker::PhantomData<Ty>,
+    directed: bool,
 }
 
-impl<Ty, Ix> Vf2State<Ty, Ix>
-where
-    Ty: EdgeType,
-    Ix: IndexType,
-{
-    pub fn new<N, E>(g: &Graph<N, E, Ty, Ix>) -> Self {
-        let c0 = g.node_count();
+impl Vf2State {
+    /// Create the state of the graph `g`, and return it along with the
+    /// nodes and edges of the graph.
+    pub fn new<G>(g: G) -> (Self, Vec<G::NodeId>, Vf2Edges<G::EdgeRef>)
+    where
+        G: IntoNodeIdentifiers + IntoEdgeReferences + NodeIndexable + GraphProp,
+    {
+        let mut nodes: Vec<_> = g.node_identifiers().collect();
+        nodes.sort_by_key(|&n| g.to_index(n));
+        let mut compact = vec![UNMAPPED; g.node_bound()];
+        for (i, &n) in nodes.iter().enumerate() {
+            compact[g.to_index(n)] = i;
+        }
+
+        let c0 = nodes.len();
+        let directed = g.is_directed();
+        let c1 = c0 * (directed as usize);
         let mut state = Vf2State {
-            mapping: Vec::with_capacity(c0),
-            out: Vec::with_capacity(c0),
-            ins: Vec::with_capacity(c0 * (g.is_directed() as usize)),
+            mapping: vec![UNMAPPED; c0],
+            out: vec![0; c0],
+            ins: vec![0; c1],
             out_size: 0,
             ins_size: 0,
-            adjacency_matrix: g.adjacency_matrix(),
+            succ: vec![Vec::new(); c0],
+            pred: vec![Vec::new(); c1],
+            adjacency_matrix: FixedBitSet::with_capacity(c0 * c0),
+            arc_count: 0,
             generation: 0,
-            _etype: marker::PhantomData,
+            directed,
         };
-        for _ in 0..c0 {
-            state.mapping.push(NodeIndex::end());
-            state.out.push(0);
-            if Ty::is_directed() {
-                state.ins.push(0);
+        let mut edges = Vf2Edges {
+            succ: vec![Vec::new(); c0],
+            pred: vec![Vec::new(); c1],
+        };
+        for edge in g.edge_references() {
+            let a = compact[g.to_index(edge.source())];
+            let b = compact[g.to_index(edge.target())];
+            state.add_arc(a, b, edge, &mut edges);
+            if !directed {
+                state.add_arc(b, a, edge, &mut edges);
             }
         }
-        state
+        (state, nodes, edges)
+    }
+
+    /// Add the arc **a** → **b**, unless it is already present.
+    fn add_arc<R: Copy>(&mut self, a: usize, b: usize, edge: R, edges: &mut Vf2Edges<R>) {
+        if self.adjacency_matrix.put(a * self.mapping.len() + b) {
+            return;
+        }
+        self.arc_count += 1;
+        self.succ[a].push(b);
+        edges.succ[a].push(edge);
+        if self.directed {
+            self.pred[b].push(a);
+            edges.pred[b].push(edge);
+        }
+    }
+
+    /// Return **true** if there is an arc **a** → **b**.
+    pub fn is_adjacent(&self, a: usize, b: usize) -> bool {
+        self.adjacency_matrix[a * self.mapping.len() + b]
     }
 
     /// Return **true** if we have a complete mapping
@@ -60,28 +120,23 @@ where
     }
 
     /// Add mapping **from** <-> **to** to the state.
-    pub fn push_mapping<N, E>(
-        &mut self,
-        from: NodeIndex<Ix>,
-        to: NodeIndex<Ix>,
-        g: &Graph<N, E, Ty, Ix>,
-    ) {
+    pub fn push_mapping(&mut self, from: usize, to: usize) {
         self.generation += 1;
         let s = self.generation;
-        self.mapping[from.index()] = to;
+        self.mapping[from] = to;
         // update T0 & T1 ins/outs
         // T0out: Node in G0 not in M0 but successor of a node in M0.
         // st.out[0]: Node either in M0 or successor of M0
-        for ix in g.neighbors(from) {
-            if self.out[ix.index()] == 0 {
-                self.out[ix.index()] = s;
+        for &ix in &self.succ[from] {
+            if self.out[ix] == 0 {
+                self.out[ix] = s;
                 self.out_size += 1;
             }
         }
-        if g.is_directed() {
-            for ix in g.neighbors_directed(from, Incoming) {
-                if self.ins[ix.index()] == 0 {
-                    self.ins[ix.index()] = s;
+        if self.directed {
+            for &ix in &self.pred[from] {
+                if self.ins[ix] == 0 {
+                    self.ins[ix] = s;
                     self.ins_size += 1;
                 }
             }
@@ -89,24 +144,24 @@ where
     }
 
     /// Restore the state to before the last added mapping
-    pub fn pop_mapping<N, E>(&mut self, from: NodeIndex<Ix>, g: &Graph<N, E, Ty, Ix>) {
+    pub fn pop_mapping(&mut self, from: usize) {
         let s = self.generation;
         self.generation -= 1;
 
         // undo (n, m) mapping
-        self.mapping[from.index()] = NodeIndex::end();
+        self.mapping[from] = UNMAPPED;
 
         // unmark in ins and outs
-        for ix in g.neighbors(from) {
-            if self.out[ix.index()] == s {
-                self.out[ix.index()] = 0;
+        for &ix in &self.succ[from] {
+            if self.out[ix] == s {
+                self.out[ix] = 0;
                 self.out_size -= 1;
             }
         }
-        if g.is_directed() {
-            for ix in g.neighbors_directed(from, Incoming) {
-                if self.ins[ix.index()] == s {
-                    self.ins[ix.index()] = 0;
+        if self.directed {
+            for &ix in &self.pred[from] {
+                if self.ins[ix] == s {
+                    self.ins[ix] = 0;
                     self.ins_size -= 1;
                 }
             }
@@ -118,23 +173,19 @@ where
         self.out[from_index..]
             .iter()
             .enumerate()
-            .find(move |&(index, elt)| {
-                *elt > 0 && self.mapping[from_index + index] == NodeIndex::end()
-            })
+            .find(move |&(index, elt)| *elt > 0 && self.mapping[from_index + index] == UNMAPPED)
             .map(|(index, _)| index)
     }
 
     /// Find the next (least) node in the Tin set.
     pub fn next_in_index(&self, from_index: usize) -> Option<usize> {
-        if !Ty::is_directed() {
+        if !self.directed {
             return None;
         }
         self.ins[from_index..]
             .iter()
             .enumerate()
-            .find(move |&(index, elt)| {
-                *elt > 0 && self.mapping[from_index + index] == NodeIndex::end()
-            })
+            .find(move |&(index, elt)| *elt > 0 && self.mapping[from_index + index] == UNMAPPED)
             .map(|(index, _)| index)
     }
 
@@ -143,204 +194,905 @@ where
         self.mapping[from_index..]
             .iter()
             .enumerate()
-            .find(|&(_, elt)| *elt == NodeIndex::end())
+            .find(|&(_, elt)| *elt == UNMAPPED)
             .map(|(index, _)| index)
     }
 }
 
-/// [Graph] Return `true` if the graphs `g0` and `g1` are isomorphic.
+/// \[Generic\] Return `true` if the graphs `g0` and `g1` are isomorphic.
 ///
 /// Using the VF2 algorithm, only matching graph syntactically (graph
 /// structure).
 ///
-/// The graphs should not be multigraphs.
+/// The graphs may be of different types, for example a `StableGraph` and a
+/// `Csr`, as long as both are directed or both are undirected. They should
+/// not be multigraphs: graphs with a different number of edges are not
+/// isomorphic, but otherwise parallel edges are treated as a single edge.
 ///
 /// **Reference**
 ///
 /// * Luigi P. Cordella, Pasquale Foggia, Carlo Sansone, Mario Vento;
 ///   *A (Sub)Graph Isomorphism Algorithm for Matching Large Graphs*
-pub fn is_isomorphic<N, E, Ty, Ix>(g0: &Graph<N, E, Ty, Ix>, g1: &Graph<N, E, Ty, Ix>) -> bool
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::is_isomorphic;
+/// use petgraph::csr::Csr;
+/// use petgraph::graphmap::UnGraphMap;
+/// use petgraph::Undirected;
+///
+/// let square = UnGraphMap::<char, ()>::from_edges(&[('a', 'b'), ('b', 'c'), ('c', 'd'), ('d', 'a')]);
+/// let mut csr = Csr::<(), (), Undirected>::with_nodes(4);
+/// for &(a, b) in &[(0, 2), (2, 1), (1, 3), (3, 0)] {
+///     csr.add_edge(a, b, ());
+/// }
+///
+/// assert!(is_isomorphic(&square, &csr));
+/// ```
+pub fn is_isomorphic<G0, G1>(g0: G0, g1: G1) -> bool
 where
-    Ty: EdgeType,
-    Ix: IndexType,
+    G0: IntoNodeIdentifiers + IntoNeighbors + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeIdentifiers
+        + IntoNeighbors
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
 {
-    if g0.node_count() != g1.node_count() || g0.edge_count() != g1.edge_count() {
+    if edge_ends(g0) != edge_ends(g1) {
         return false;
     }
-
-    let mut st = [Vf2State::new(g0), Vf2State::new(g1)];
-    try_match(&mut st, g0, g1, &mut NoSemanticMatch, &mut NoSemanticMatch).unwrap_or(false)
+    let mut vf2 = Vf2::new(g0, g1, Problem::Isomorphism);
+    vf2.next_match(&mut NoSemanticMatch, &mut NoSemanticMatch)
 }
 
-/// [Graph] Return `true` if the graphs `g0` and `g1` are isomorphic.
+/// \[Generic\] Return `true` if the graphs `g0` and `g1` are isomorphic.
 ///
 /// Using the VF2 algorithm, examining both syntactic and semantic
 /// graph isomorphism (graph structure and matching node and edge weights).
 ///
 /// The graphs should not be multigraphs.
-pub fn is_isomorphic_matching<N, E, Ty, Ix, F, G>(
-    g0: &Graph<N, E, Ty, Ix>,
-    g1: &Graph<N, E, Ty, Ix>,
-    mut node_match: F,
+pub fn is_isomorphic_matching<G0, G1, F, G>(
+    g0: G0,
+    g1: G1,
+    node_match: F,
     mut edge_match: G,
 ) -> bool
 where
-    Ty: EdgeType,
-    Ix: IndexType,
-    F: FnMut(&N, &N) -> bool,
-    G: FnMut(&E, &E) -> bool,
+    G0: IntoNodeReferences + IntoNeighbors + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoNeighbors
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    F: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    G: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
 {
-    if g0.node_count() != g1.node_count() || g0.edge_count() != g1.edge_count() {
+    if edge_ends(g0) != edge_ends(g1) {
         return false;
     }
+    let mut node_match = NodeWeightMatcher::new(g0, g1, node_match);
+    let mut vf2 = Vf2::new(g0, g1, Problem::Isomorphism);
+    vf2.next_match(&mut node_match, &mut edge_match)
+}
+
+/// \[Generic\] Return `true` if the graph `g0` is isomorphic to an induced
+/// subgraph of `g1`.
+///
+/// See [`subgraph_isomorphisms`](fn.subgraph_isomorphisms.html).
+pub fn is_isomorphic_subgraph<G0, G1>(g0: G0, g1: G1) -> bool
+where
+    G0: IntoNodeIdentifiers + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeIdentifiers
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+{
+    let mut vf2 = Vf2::new(g0, g1, Problem::InducedSubgraph);
+    vf2.next_match(&mut NoSemanticMatch, &mut NoSemanticMatch)
+}
+
+/// \[Generic\] Return `true` if the graph `g0` is isomorphic to an induced
+/// subgraph of `g1`, with matching node and edge weights.
+///
+/// See [`subgraph_isomorphisms`](fn.subgraph_isomorphisms.html).
+pub fn is_isomorphic_subgraph_matching<G0, G1, F, G>(
+    g0: G0,
+    g1: G1,
+    node_match: F,
+    mut edge_match: G,
+) -> bool
+where
+    G0: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    F: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    G: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    let mut node_match = NodeWeightMatcher::new(g0, g1, node_match);
+    let mut vf2 = Vf2::new(g0, g1, Problem::InducedSubgraph);
+    vf2.next_match(&mut node_match, &mut edge_match)
+}
+
+/// \[Generic\] Return an iterator of the ways that the graph `g0` is
+/// isomorphic to an induced subgraph of `g1`.
+///
+/// Each mapping maps the nodes of `g0` to distinct nodes of `g1`, such that
+/// two nodes of `g0` are adjacent if and only if the nodes they are mapped to
+/// are adjacent, and `node_match` and `edge_match` accept the weights of the
+/// matched nodes and edges. The weights of `g0` are passed as the first
+/// argument.
+///
+/// The mappings are vectors of the nodes of `g1` that the nodes of `g0` are
+/// mapped to, listed in the order of the node indices of `g0`. Every mapping
+/// is found once, using the VF2 algorithm; pass `|_, _| true` to ignore the
+/// weights.
+///
+/// The graphs should not be multigraphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::subgraph_isomorphisms;
+/// use petgraph::graph::node_index as n;
+///
+/// // Find the paths a -> b -> c in the target.
+/// let pattern = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let target = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (0, 2)]);
+///
+/// let matches: Vec<_> = subgraph_isomorphisms(&pattern, &target, |_, _| true, |_, _| true)
+///     .collect();
+/// // 0 -> 1 -> 2 is not induced, since it has the edge 0 -> 2.
+/// assert_eq!(matches, vec![vec![n(0), n(2), n(3)], vec![n(1), n(2), n(3)]]);
+/// ```
+pub fn subgraph_isomorphisms<G0, G1, F, G>(
+    g0: G0,
+    g1: G1,
+    node_match: F,
+    edge_match: G,
+) -> Mappings<G0, G1, F, G>
+where
+    G0: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    F: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    G: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    Mappings {
+        node_match: NodeWeightMatcher::new(g0, g1, node_match),
+        vf2: Vf2::new(g0, g1, Problem::InducedSubgraph),
+        edge_match,
+    }
+}
+
+/// \[Generic\] Return an iterator of the ways that the graph `g0` is
+/// monomorphic to a subgraph of `g1`.
+///
+/// This is [`subgraph_isomorphisms`](fn.subgraph_isomorphisms.html), except
+/// that the subgraph of `g1` does not need to be induced: every edge of `g0`
+/// must be matched by an edge of `g1`, but the nodes of `g1` that are
+/// matched may have additional edges between them.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::subgraph_monomorphisms;
+/// use petgraph::graph::node_index as n;
+///
+/// // Find the paths a -> b -> c in the target.
+/// let pattern = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let target = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (0, 2)]);
+///
+/// let matches = subgraph_monomorphisms(&pattern, &target, |_, _| true, |_, _| true);
+/// assert_eq!(matches.count(), 3);
+/// ```
+pub fn subgraph_monomorphisms<G0, G1, F, G>(
+    g0: G0,
+    g1: G1,
+    node_match: F,
+    edge_match: G,
+) -> Mappings<G0, G1, F, G>
+where
+    G0: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    F: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    G: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    Mappings {
+        node_match: NodeWeightMatcher::new(g0, g1, node_match),
+        vf2: Vf2::new(g0, g1, Problem::Monomorphism),
+        edge_match,
+    }
+}
+
+/// \[Generic\] Return an iterator of the isomorphisms from the graph `g0` to
+/// the graph `g1`.
+///
+/// Each isomorphism is a mapping of the nodes of `g0` to the nodes of `g1`
+/// that preserves adjacency and non-adjacency, and where `node_match` and
+/// `edge_match` accept the weights of the matched nodes and edges, as for
+/// [`subgraph_isomorphisms`](fn.subgraph_isomorphisms.html). The
+/// isomorphisms from a graph to itself are its automorphisms.
+///
+/// The VF2 search is pruned by node invariants: nodes are only matched if
+/// they have the same color by color refinement, which takes into account
+/// the degrees of the nodes and the colors of their neighbors.
+///
+/// The graphs should not be multigraphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::isomorphisms;
+/// use petgraph::graph::node_index as n;
+///
+/// // The automorphisms of a path are the identity and the reversal.
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let automorphisms: Vec<_> = isomorphisms(&path, &path, |_, _| true, |_, _| true).collect();
+/// assert_eq!(automorphisms, vec![vec![n(0), n(1), n(2)], vec![n(2), n(1), n(0)]]);
+/// ```
+pub fn isomorphisms<G0, G1, F, G>(
+    g0: G0,
+    g1: G1,
+    node_match: F,
+    edge_match: G,
+) -> Mappings<G0, G1, F, G>
+where
+    G0: IntoNodeReferences + IntoNeighbors + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G1: IntoNodeReferences
+        + IntoNeighbors
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = G0::EdgeType>,
+    F: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    G: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    let mut vf2 = Vf2::new(g0, g1, Problem::Isomorphism);
+    let mut node_match = NodeWeightMatcher::new(g0, g1, node_match);
+    node_match.invariants = Some(vf2.invariants());
+    if edge_ends(g0) != edge_ends(g1) {
+        vf2.stack.clear();
+    }
+    Mappings {
+        vf2,
+        node_match,
+        edge_match,
+    }
+}
+
+/// \[Generic\] Compute the orbits of the automorphism group of the graph.
+///
+/// Two nodes are in the same orbit if there is an automorphism of the graph
+/// (an isomorphism from the graph to itself) that maps one to the other.
+/// Return the orbits as a partition of the nodes, with each orbit and the
+/// list of orbits sorted by node index. Node and edge weights are ignored.
+///
+/// For each pair of nodes with the same node invariants, a VF2 search looks
+/// for an automorphism that maps one to the other; the automorphisms that
+/// are found merge whole orbits at once. To enumerate all automorphisms,
+/// use [`isomorphisms`](fn.isomorphisms.html) from the graph to itself.
+///
+/// The graph should not be a multigraph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::automorphisms;
+/// use petgraph::graph::node_index as n;
+///
+/// // A triangle with a pendant node at 0.
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (0, 3)]);
+///
+/// assert_eq!(automorphisms(&g), vec![vec![n(0)], vec![n(1), n(2)], vec![n(3)]]);
+/// ```
+pub fn automorphisms<G>(graph: G) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    let mut vf2 = Vf2::new(graph, graph, Problem::Isomorphism);
+    let mut invariants = vf2.invariants();
+    let n = vf2.nodes.len();
+    let mut orbits = UnionFind::new(n);
+    let mut done = vec![false; n];
+    for u in 0..n {
+        if done[u] {
+            continue;
+        }
+        for v in u + 1..n {
+            if invariants.colors[0][u] != invariants.colors[0][v] || orbits.equiv(u, v) {
+                continue;
+            }
+            if let Some(automorphism) = vf2.match_pair([u, v], &mut invariants) {
+                for (i, &j) in automorphism.iter().enumerate() {
+                    orbits.union(i, j);
+                }
+            }
+        }
+        // The orbit of u is complete.
+        for (v, done) in done.iter_mut().enumerate().skip(u) {
+            if orbits.equiv(u, v) {
+                *done = true;
+            }
+        }
+    }
 
-    let mut st = [Vf2State::new(g0), Vf2State::new(g1)];
-    try_match(&mut st, g0, g1, &mut node_match, &mut edge_match).unwrap_or(false)
+    let labels = orbits.into_labeling();
+    let mut index = vec![UNMAPPED; n];
+    let mut partition: Vec<Vec<_>> = Vec::new();
+    for (i, &label) in labels.iter().enumerate() {
+        if index[label] == UNMAPPED {
+            index[label] = partition.len();
+            partition.push(Vec::new());
+        }
+        partition[index[label]].push(vf2.nodes[i]);
+    }
+    partition
+}
+
+/// A search for the automorphisms of a graph that preserve a coloring of its
+/// nodes, by the VF2 algorithm.
+///
+/// The nodes are numbered from 0 in the order of their indices.
+pub(crate) struct ColoredAutomorphisms<G: IntoEdgeReferences> {
+    vf2: Vf2<G, G>,
 }
 
-trait SemanticMatcher<T> {
+impl<G> ColoredAutomorphisms<G>
+where
+    G: IntoNodeIdentifiers + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    pub(crate) fn new(graph: G) -> Self {
+        ColoredAutomorphisms {
+            vf2: Vf2::new(graph, graph, Problem::Isomorphism),
+        }
+    }
+
+    /// Return the nodes of the graph, in the order of their numbers.
+    pub(crate) fn nodes(&self) -> &[G::NodeId] {
+        &self.vf2.nodes
+    }
+
+    /// Find an automorphism that maps node `u` to node `v` and preserves the
+    /// colors, ignoring the weights and multiplicity of edges.
+    pub(crate) fn find(&mut self, colors: &[usize], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut invariants = Invariants {
+            colors: [colors.to_vec(), colors.to_vec()],
+        };
+        self.vf2.match_pair([u, v], &mut invariants)
+    }
+}
+
+/// An iterator of the node mappings of (sub)graph matches.
+///
+/// Created with [`subgraph_isomorphisms`](fn.subgraph_isomorphisms.html) or
+/// [`subgraph_monomorphisms`](fn.subgraph_monomorphisms.html).
+pub struct Mappings<G0, G1, F, G>
+where
+    G0: IntoNodeReferences + IntoEdgeReferences,
+    G1: IntoNodeReferences + IntoEdgeReferences,
+{
+    vf2: Vf2<G0, G1>,
+    node_match: NodeWeightMatcher<G0::NodeRef, G1::NodeRef, F>,
+    edge_match: G,
+}
+
+impl<G0, G1, F, G> Iterator for Mappings<G0, G1, F, G>
+where
+    G0: IntoNodeReferences + IntoEdgeReferences,
+    G1: IntoNodeReferences + IntoEdgeReferences,
+    F: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    G: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    type Item = Vec<G1::NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self
+            .vf2
+            .next_match(&mut self.node_match, &mut self.edge_match)
+        {
+            Some(self.vf2.mapping())
+        } else {
+            None
+        }
+    }
+}
+
+trait SemanticMatcher<T0, T1> {
     fn enabled() -> bool;
-    fn eq(&mut self, _: &T, _: &T) -> bool;
+    fn eq(&mut self, _: &T0, _: &T1) -> bool;
 }
 
 struct NoSemanticMatch;
 
-impl<T> SemanticMatcher<T> for NoSemanticMatch {
+impl<T0, T1> SemanticMatcher<T0, T1> for NoSemanticMatch {
     #[inline]
     fn enabled() -> bool {
         false
     }
     #[inline]
-    fn eq(&mut self, _: &T, _: &T) -> bool {
+    fn eq(&mut self, _: &T0, _: &T1) -> bool {
         true
     }
 }
 
-impl<T, F> SemanticMatcher<T> for F
+impl<T0, T1, F> SemanticMatcher<T0, T1> for F
 where
-    F: FnMut(&T, &T) -> bool,
+    F: FnMut(&T0, &T1) -> bool,
 {
     #[inline]
     fn enabled() -> bool {
         true
     }
     #[inline]
-    fn eq(&mut self, a: &T, b: &T) -> bool {
+    fn eq(&mut self, a: &T0, b: &T1) -> bool {
         self(a, b)
     }
 }
 
-/// Return Some(bool) if isomorphism is decided, else None.
-fn try_match<N, E, Ty, Ix, F, G>(
-    mut st: &mut [Vf2State<Ty, Ix>; 2],
-    g0: &Graph<N, E, Ty, Ix>,
-    g1: &Graph<N, E, Ty, Ix>,
-    node_match: &mut F,
-    edge_match: &mut G,
-) -> Option<bool>
+/// Matching of the nodes of `g0` and `g1`, by their numbers in the
+/// `Vf2State`.
+trait NodeMatcher {
+    fn enabled() -> bool;
+    fn eq(&mut self, _: usize, _: usize) -> bool;
+}
+
+impl NodeMatcher for NoSemanticMatch {
+    #[inline]
+    fn enabled() -> bool {
+        false
+    }
+    #[inline]
+    fn eq(&mut self, _: usize, _: usize) -> bool {
+        true
+    }
+}
+
+/// Matching of nodes by their weights, and by their invariants if the graphs
+/// must be isomorphic.
+struct NodeWeightMatcher<R0, R1, F> {
+    nodes: (Vec<R0>, Vec<R1>),
+    invariants: Option<Invariants>,
+    node_match: F,
+}
+
+impl<R0, R1, F> NodeWeightMatcher<R0, R1, F> {
+    fn new<G0, G1>(g0: G0, g1: G1, node_match: F) -> Self
+    where
+        G0: IntoNodeReferences<NodeRef = R0> + NodeIndexable,
+        G1: IntoNodeReferences<NodeRef = R1> + NodeIndexable,
+    {
+        NodeWeightMatcher {
+            nodes: (node_references(g0), node_references(g1)),
+            invariants: None,
+            node_match,
+        }
+    }
+}
+
+/// Return the nodes of `g`, numbered like `Vf2State` does.
+fn node_references<G>(g: G) -> Vec<G::NodeRef>
 where
-    Ty: EdgeType,
-    Ix: IndexType,
-    F: SemanticMatcher<N>,
-    G: SemanticMatcher<E>,
+    G: IntoNodeReferences + NodeIndexable,
 {
-    if st[0].is_complete() {
-        return Some(true);
+    let mut nodes: Vec<_> = g.node_references().collect();
+    nodes.sort_by_key(|n| g.to_index(n.id()));
+    nodes
+}
+
+impl<R0, R1, F> NodeMatcher for NodeWeightMatcher<R0, R1, F>
+where
+    R0: NodeRef,
+    R1: NodeRef,
+    F: SemanticMatcher<R0::Weight, R1::Weight>,
+{
+    #[inline]
+    fn enabled() -> bool {
+        F::enabled()
     }
-    let g = [g0, g1];
-    let graph_indices = 0..2;
-    let end = NodeIndex::end();
+    #[inline]
+    fn eq(&mut self, a: usize, b: usize) -> bool {
+        if let Some(ref mut invariants) = self.invariants {
+            if !invariants.eq(a, b) {
+                return false;
+            }
+        }
+        self.node_match
+            .eq(self.nodes.0[a].weight(), self.nodes.1[b].weight())
+    }
+}
 
-    // A "depth first" search of a valid mapping from graph 1 to graph 2
+/// Matching of nodes by node invariants: the colors of the nodes by color
+/// refinement (the 1-dimensional Weisfeiler–Lehman algorithm), refined on
+/// both graphs at once so that their colors are comparable.
+///
+/// Nodes that are matched by an isomorphism have the same color.
+struct Invariants {
+    colors: [Vec<usize>; 2],
+}
 
-    // F(s, n, m) -- evaluate state s and add mapping n <-> m
+impl Invariants {
+    fn new(st: &[Vf2State; 2]) -> Self {
+        // Start from the degrees and self loops of the nodes.
+        let mut colors = [Vec::new(), Vec::new()];
+        let mut table = HashMap::new();
+        for j in 0..2 {
+            colors[j] = (0..st[j].mapping.len())
+                .map(|i| {
+                    let pred = if st[j].directed {
+                        st[j].pred[i].len()
+                    } else {
+                        0
+                    };
+                    let key = (st[j].succ[i].len(), pred, st[j].is_adjacent(i, i));
+                    let len = table.len();
+                    *table.entry(key).or_insert(len)
+                })
+                .collect();
+        }
+        let mut classes = table.len();
+        // Refine each color by the colors of the neighbors, until no color
+        // class is split.
+        loop {
+            let mut table = HashMap::new();
+            let mut refined = [Vec::new(), Vec::new()];
+            for j in 0..2 {
+                refined[j] = (0..st[j].mapping.len())
+                    .map(|i| {
+                        let neighbor_colors = |neighbors: &[usize]| {
+                            let mut c: Vec<_> = neighbors.iter().map(|&k| colors[j][k]).collect();
+                            c.sort_unstable();
+                            c
+                        };
+                        let pred = if st[j].directed {
+                            neighbor_colors(&st[j].pred[i])
+                        } else {
+                            Vec::new()
+                        };
+                        let key = (colors[j][i], neighbor_colors(&st[j].succ[i]), pred);
+                        let len = table.len();
+                        *table.entry(key).or_insert(len)
+                    })
+                    .collect();
+            }
+            colors = refined;
+            if table.len() == classes {
+                break;
+            }
+            classes = table.len();
+        }
+        Invariants { colors }
+    }
 
-    // Find least T1out node (in st.out[1] but not in M[1])
-    #[derive(Copy, Clone, PartialEq, Debug)]
-    enum OpenList {
-        Out,
-        In,
-        Other,
+    /// Return `true` if both graphs have the same number of nodes of each
+    /// color.
+    fn is_balanced(&self) -> bool {
+        let mut counts = [self.colors[0].clone(), self.colors[1].clone()];
+        for c in &mut counts {
+            c.sort_unstable();
+        }
+        counts[0] == counts[1]
     }
+}
 
-    #[derive(Clone, PartialEq, Debug)]
-    enum Frame<N: marker::Copy> {
-        Outer,
-        Inner { nodes: [N; 2], open_list: OpenList },
-        Unwind { nodes: [N; 2], open_list: OpenList },
+impl NodeMatcher for Invariants {
+    #[inline]
+    fn enabled() -> bool {
+        true
     }
+    #[inline]
+    fn eq(&mut self, a: usize, b: usize) -> bool {
+        self.colors[0][a] == self.colors[1][b]
+    }
+}
 
-    let next_candidate =
-        |st: &mut [Vf2State<Ty, Ix>; 2]| -> Option<(NodeIndex<Ix>, NodeIndex<Ix>, OpenList)> {
-            let mut to_index;
-            let mut from_index = None;
-            let mut open_list = OpenList::Out;
-            // Try the out list
-            to_index = st[1].next_out_index(0);
+/// The kind of match between the graphs `g0` and `g1`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Problem {
+    /// A bijection that preserves adjacency.
+    Isomorphism,
+    /// An injection that preserves adjacency and non-adjacency.
+    InducedSubgraph,
+    /// An injection that preserves adjacency.
+    Monomorphism,
+}
+
+impl Problem {
+    /// Return `true` if a node or set of `g0` of size `n0` may be matched to
+    /// one of `g1` of size `n1`.
+    fn admits(self, n0: usize, n1: usize) -> bool {
+        match self {
+            Problem::Isomorphism => n0 == n1,
+            Problem::InducedSubgraph | Problem::Monomorphism => n0 <= n1,
+        }
+    }
+
+    /// Return `true` if edges of `g1` between matched nodes must be matched
+    /// by edges of `g0`.
+    fn is_induced(self) -> bool {
+        self != Problem::Monomorphism
+    }
+}
+
+// Find least T1out node (in st.out[1] but not in M[1])
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum OpenList {
+    Out,
+    In,
+    Other,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Frame {
+    Outer,
+    Inner {
+        nodes: [usize; 2],
+        open_list: OpenList,
+    },
+    Unwind {
+        nodes: [usize; 2],
+        open_list: OpenList,
+    },
+}
+
+/// A "depth first" search of the valid mappings from graph 0 to graph 1,
+/// which can be resumed after each complete mapping.
+struct Vf2<G0: IntoEdgeReferences, G1: IntoEdgeReferences> {
+    st: [Vf2State; 2],
+    /// The nodes of graph 1, by their numbers in the state.
+    nodes: Vec<G1::NodeId>,
+    edges: (Vf2Edges<G0::EdgeRef>, Vf2Edges<G1::EdgeRef>),
+    problem: Problem,
+    stack: Vec<Frame>,
+}
 
-            if to_index.is_some() {
-                from_index = st[0].next_out_index(0);
-                open_list = OpenList::Out;
+/// Return the number of edges of `g`, counting parallel edges, and counting
+/// undirected edges other than self loops from both ends.
+///
+/// Unlike `edge_references`, this counts each edge of an undirected `Csr`
+/// like for the other graph types.
+fn edge_ends<G>(g: G) -> usize
+where
+    G: IntoNodeIdentifiers + IntoNeighbors,
+{
+    g.node_identifiers().map(|n| g.neighbors(n).count()).sum()
+}
+
+impl<G0, G1> Vf2<G0, G1>
+where
+    G0: IntoEdgeReferences,
+    G1: IntoEdgeReferences,
+{
+    fn new(g0: G0, g1: G1, problem: Problem) -> Self
+    where
+        G0: IntoNodeIdentifiers + NodeIndexable + GraphProp,
+        G1: IntoNodeIdentifiers + NodeIndexable + GraphProp<EdgeType = G0::EdgeType>,
+    {
+        let (st0, _, edges0) = Vf2State::new(g0);
+        let (st1, nodes, edges1) = Vf2State::new(g1);
+        let stack = if problem.admits(st0.mapping.len(), st1.mapping.len())
+            && problem.admits(st0.arc_count, st1.arc_count)
+        {
+            vec![Frame::Outer]
+        } else {
+            Vec::new()
+        };
+        Vf2 {
+            st: [st0, st1],
+            nodes,
+            edges: (edges0, edges1),
+            problem,
+            stack,
+        }
+    }
+
+    /// Compute the node invariants of both graphs, and stop the search if
+    /// their colors do not have the same counts.
+    fn invariants(&mut self) -> Invariants {
+        let invariants = Invariants::new(&self.st);
+        if !invariants.is_balanced() {
+            self.stack.clear();
+        }
+        invariants
+    }
+
+    /// Find a complete mapping that maps `nodes[0]` to `nodes[1]`, and return
+    /// it. The search is reset before and after.
+    fn match_pair<F>(&mut self, nodes: [usize; 2], node_match: &mut F) -> Option<Vec<usize>>
+    where
+        F: NodeMatcher,
+    {
+        self.reset();
+        if !self.is_feasible(nodes, node_match, &mut NoSemanticMatch) {
+            return None;
+        }
+        self.push_state(nodes);
+        self.stack.push(Frame::Outer);
+        let found = self.next_match(node_match, &mut NoSemanticMatch);
+        let mapping = if found {
+            Some(self.st[0].mapping.clone())
+        } else {
+            None
+        };
+        self.reset();
+        self.pop_state(nodes);
+        mapping
+    }
+
+    /// Abandon the search, and restore the state of the mapping to before it.
+    fn reset(&mut self) {
+        while let Some(frame) = self.stack.pop() {
+            if let Frame::Unwind { nodes, .. } = frame {
+                self.pop_state(nodes);
             }
-            // Try the in list
-            if to_index.is_none() || from_index.is_none() {
-                to_index = st[1].next_in_index(0);
+        }
+    }
 
-                if to_index.is_some() {
-                    from_index = st[0].next_in_index(0);
-                    open_list = OpenList::In;
+    /// Return the current mapping, as the nodes of graph 1 matched to each
+    /// node of graph 0.
+    fn mapping(&self) -> Vec<G1::NodeId> {
+        self.st[0].mapping.iter().map(|&m| self.nodes[m]).collect()
+    }
+
+    /// Find the next complete mapping, and return `true` if there is one.
+    ///
+    /// The mapping is left in the state until the next call.
+    fn next_match<F, G>(&mut self, node_match: &mut F, edge_match: &mut G) -> bool
+    where
+        F: NodeMatcher,
+        G: SemanticMatcher<G0::EdgeWeight, G1::EdgeWeight>,
+    {
+        // F(s, n, m) -- evaluate state s and add mapping n <-> m
+        while let Some(frame) = self.stack.pop() {
+            match frame {
+                Frame::Unwind {
+                    nodes,
+                    open_list: ol,
+                } => {
+                    self.pop_state(nodes);
+
+                    match self.next_to_ix(nodes[1], ol) {
+                        None => continue,
+                        Some(mx) => {
+                            let f = Frame::Inner {
+                                nodes: [nodes[0], mx],
+                                open_list: ol,
+                            };
+                            self.stack.push(f);
+                        }
+                    }
                 }
-            }
-            // Try the other list -- disconnected graph
-            if to_index.is_none() || from_index.is_none() {
-                to_index = st[1].next_rest_index(0);
-                if to_index.is_some() {
-                    from_index = st[0].next_rest_index(0);
-                    open_list = OpenList::Other;
+                Frame::Outer => {
+                    if self.st[0].is_complete() {
+                        return true;
+                    }
+                    match self.next_candidate() {
+                        None => continue,
+                        Some((nx, mx, ol)) => {
+                            let f = Frame::Inner {
+                                nodes: [nx, mx],
+                                open_list: ol,
+                            };
+                            self.stack.push(f);
+                        }
+                    }
+                }
+                Frame::Inner {
+                    nodes,
+                    open_list: ol,
+                } => {
+                    if self.is_feasible(nodes, node_match, edge_match) {
+                        self.push_state(nodes);
+                        // Check cardinalities of Tin, Tout sets
+                        if self
+                            .problem
+                            .admits(self.st[0].out_size, self.st[1].out_size)
+                            && self
+                                .problem
+                                .admits(self.st[0].ins_size, self.st[1].ins_size)
+                        {
+                            let f0 = Frame::Unwind {
+                                nodes,
+                                open_list: ol,
+                            };
+                            self.stack.push(f0);
+                            self.stack.push(Frame::Outer);
+                            continue;
+                        }
+                        self.pop_state(nodes);
+                    }
+                    match self.next_to_ix(nodes[1], ol) {
+                        None => continue,
+                        Some(mx) => {
+                            let f = Frame::Inner {
+                                nodes: [nodes[0], mx],
+                                open_list: ol,
+                            };
+                            self.stack.push(f);
+                        }
+                    }
                 }
             }
-            match (from_index, to_index) {
-                (Some(n), Some(m)) => Some((NodeIndex::new(n), NodeIndex::new(m), open_list)),
-                // No more candidates
-                _ => None,
+        }
+        false
+    }
+
+    /// Find the next node of `g0` to map, and the first candidate of `g1`.
+    ///
+    /// The node of `g0` is kept, while the candidates of `g1` are tried in
+    /// turn, so that `g1` may have nodes that are left unmapped.
+    fn next_candidate(&self) -> Option<(usize, usize, OpenList)> {
+        let st = &self.st;
+        let mut from_index;
+        let mut to_index = None;
+        let mut open_list = OpenList::Out;
+        // Try the out list
+        from_index = st[0].next_out_index(0);
+
+        if from_index.is_some() {
+            to_index = st[1].next_out_index(0);
+            open_list = OpenList::Out;
+        }
+        // Try the in list
+        if to_index.is_none() || from_index.is_none() {
+            from_index = st[0].next_in_index(0);
+
+            if from_index.is_some() {
+                to_index = st[1].next_in_index(0);
+                open_list = OpenList::In;
             }
-        };
-    let next_from_ix = |st: &mut [Vf2State<Ty, Ix>; 2],
-                        nx: NodeIndex<Ix>,
-                        open_list: OpenList|
-     -> Option<NodeIndex<Ix>> {
-        // Find the next node index to try on the `from` side of the mapping
-        let start = nx.index() + 1;
-        let cand0 = match open_list {
-            OpenList::Out => st[0].next_out_index(start),
-            OpenList::In => st[0].next_in_index(start),
-            OpenList::Other => st[0].next_rest_index(start),
-        }
-        .map(|c| c + start); // compensate for start offset.
-        match cand0 {
-            None => None, // no more candidates
-            Some(ix) => {
-                debug_assert!(ix >= start);
-                Some(NodeIndex::new(ix))
+        }
+        // Try the other list -- disconnected graph
+        if to_index.is_none() || from_index.is_none() {
+            from_index = st[0].next_rest_index(0);
+            if from_index.is_some() {
+                to_index = st[1].next_rest_index(0);
+                open_list = OpenList::Other;
             }
         }
-    };
-    //fn pop_state(nodes: [NodeIndex<Ix>; 2]) {
-    let pop_state = |st: &mut [Vf2State<Ty, Ix>; 2], nodes: [NodeIndex<Ix>; 2]| {
-        // Restore state.
-        for j in graph_indices.clone() {
-            st[j].pop_mapping(nodes[j], g[j]);
-        }
-    };
-    //fn push_state(nodes: [NodeIndex<Ix>; 2]) {
-    let push_state = |st: &mut [Vf2State<Ty, Ix>; 2], nodes: [NodeIndex<Ix>; 2]| {
-        // Add mapping nx <-> mx to the state
-        for j in graph_indices.clone() {
-            st[j].push_mapping(nodes[j], nodes[1 - j], g[j]);
-        }
-    };
-    //fn is_feasible(nodes: [NodeIndex<Ix>; 2]) -> bool {
-    let mut is_feasible = |st: &mut [Vf2State<Ty, Ix>; 2], nodes: [NodeIndex<Ix>; 2]| -> bool {
+        match (from_index, to_index) {
+            (Some(n), Some(m)) => Some((n, m, open_list)),
+            // No more candidates
+            _ => None,
+        }
+    }
+
+    /// Find the next node index to try on the `to` side of the mapping
+    fn next_to_ix(&self, mx: usize, open_list: OpenList) -> Option<usize> {
+        let st = &self.st;
+        let start = mx + 1;
+        let cand1 = match open_list {
+            OpenList::Out => st[1].next_out_index(start),
+            OpenList::In => st[1].next_in_index(start),
+            OpenList::Other => st[1].next_rest_index(start),
+        };
+        cand1.map(|c| c + start) // compensate for start offset.
+    }
+
+    /// Restore state.
+    fn pop_state(&mut self, nodes: [usize; 2]) {
+        for (st, &node) in self.st.iter_mut().zip(&nodes) {
+            st.pop_mapping(node);
+        }
+    }
+
+    /// Add mapping nx <-> mx to the state
+    fn push_state(&mut self, nodes: [usize; 2]) {
+        self.st[0].push_mapping(nodes[0], nodes[1]);
+        self.st[1].push_mapping(nodes[1], nodes[0]);
+    }
+
+    fn is_feasible<F, G>(&self, nodes: [usize; 2], node_match: &mut F, edge_match: &mut G) -> bool
+    where
+        F: NodeMatcher,
+        G: SemanticMatcher<G0::EdgeWeight, G1::EdgeWeight>,
+    {
+        let st = &self.st;
         // Check syntactic feasibility of mapping by ensuring adjacencies
         // of nx map to adjacencies of mx.
         //
@@ -349,8 +1101,8 @@ where
         // R_succ
         //
         // Check that every neighbor of nx is mapped to a neighbor of mx,
-        // then check the reverse, from mx to nx. Check that they have the same
-        // count of edges.
+        // then check the reverse, from mx to nx, unless g1 may have extra
+        // edges. Check that they have compatible counts of edges.
         //
         // Note: We want to check the lookahead measures here if we can,
         // R_out: Equal for G0, G1: Card(Succ(G, n) ^ Tout); for both Succ and Pred
@@ -358,168 +1110,98 @@ where
         // R_new: Equal for G0, G1: Ñ n Pred(G, n); both Succ and Pred,
         //      Ñ is G0 - M - Tin - Tout
         // last attempt to add these did not speed up any of the testcases
-        let mut succ_count = [0, 0];
-        for j in graph_indices.clone() {
-            for n_neigh in g[j].neighbors(nodes[j]) {
-                succ_count[j] += 1;
+        let sides = if self.problem.is_induced() { 2 } else { 1 };
+        if !self
+            .problem
+            .admits(st[0].succ[nodes[0]].len(), st[1].succ[nodes[1]].len())
+        {
+            return false;
+        }
+        for j in 0..sides {
+            for &n_neigh in &st[j].succ[nodes[j]] {
                 // handle the self loop case; it's not in the mapping (yet)
                 let m_neigh = if nodes[j] != n_neigh {
-                    st[j].mapping[n_neigh.index()]
+                    st[j].mapping[n_neigh]
                 } else {
                     nodes[1 - j]
                 };
-                if m_neigh == end {
+                if m_neigh == UNMAPPED {
                     continue;
                 }
-                let has_edge =
-                    g[1 - j].is_adjacent(&st[1 - j].adjacency_matrix, nodes[1 - j], m_neigh);
-                if !has_edge {
+                if !st[1 - j].is_adjacent(nodes[1 - j], m_neigh) {
                     return false;
                 }
             }
         }
-        if succ_count[0] != succ_count[1] {
-            return false;
-        }
         // R_pred
-        if g[0].is_directed() {
-            let mut pred_count = [0, 0];
-            for j in graph_indices.clone() {
-                for n_neigh in g[j].neighbors_directed(nodes[j], Incoming) {
-                    pred_count[j] += 1;
+        if st[0].directed {
+            if !self
+                .problem
+                .admits(st[0].pred[nodes[0]].len(), st[1].pred[nodes[1]].len())
+            {
+                return false;
+            }
+            for j in 0..sides {
+                for &n_neigh in &st[j].pred[nodes[j]] {
                     // the self loop case is handled in outgoing
-                    let m_neigh = st[j].mapping[n_neigh.index()];
-                    if m_neigh == end {
+                    let m_neigh = st[j].mapping[n_neigh];
+                    if m_neigh == UNMAPPED {
                         continue;
                     }
-                    let has_edge =
-                        g[1 - j].is_adjacent(&st[1 - j].adjacency_matrix, m_neigh, nodes[1 - j]);
-                    if !has_edge {
+                    if !st[1 - j].is_adjacent(m_neigh, nodes[1 - j]) {
                         return false;
                     }
                 }
             }
-            if pred_count[0] != pred_count[1] {
-                return false;
-            }
         }
         // semantic feasibility: compare associated data for nodes
-        if F::enabled() && !node_match.eq(&g[0][nodes[0]], &g[1][nodes[1]]) {
+        if F::enabled() && !node_match.eq(nodes[0], nodes[1]) {
             return false;
         }
-        // semantic feasibility: compare associated data for edges
+        // semantic feasibility: compare associated data for edges; the
+        // syntactic check makes sure that every edge of g0 between mapped
+        // nodes has a counterpart in g1.
         if G::enabled() {
             // outgoing edges
-            for j in graph_indices.clone() {
-                let mut edges = g[j].neighbors(nodes[j]).detach();
-                while let Some((n_edge, n_neigh)) = edges.next(g[j]) {
-                    // handle the self loop case; it's not in the mapping (yet)
-                    let m_neigh = if nodes[j] != n_neigh {
-                        st[j].mapping[n_neigh.index()]
-                    } else {
-                        nodes[1 - j]
-                    };
-                    if m_neigh == end {
-                        continue;
-                    }
-                    match g[1 - j].find_edge(nodes[1 - j], m_neigh) {
-                        Some(m_edge) => {
-                            if !edge_match.eq(&g[j][n_edge], &g[1 - j][m_edge]) {
-                                return false;
-                            }
-                        }
-                        None => unreachable!(), // covered by syntactic check
-                    }
-                }
-            }
-            // incoming edges
-            if g[0].is_directed() {
-                for j in graph_indices.clone() {
-                    let mut edges = g[j].neighbors_directed(nodes[j], Incoming).detach();
-                    while let Some((n_edge, n_neigh)) = edges.next(g[j]) {
-                        // the self loop case is handled in outgoing
-                        let m_neigh = st[j].mapping[n_neigh.index()];
-                        if m_neigh == end {
-                            continue;
-                        }
-                        match g[1 - j].find_edge(m_neigh, nodes[1 - j]) {
-                            Some(m_edge) => {
-                                if !edge_match.eq(&g[j][n_edge], &g[1 - j][m_edge]) {
-                                    return false;
-                                }
-                            }
-                            None => unreachable!(), // covered by syntactic check
-                        }
-                    }
+            let (edges0, edges1) = &self.edges;
+            let succ = [&st[0].succ[nodes[0]], &st[1].succ[nodes[1]]];
+            for (k, &n_neigh) in succ[0].iter().enumerate() {
+                // handle the self loop case; it's not in the mapping (yet)
+                let m_neigh = if nodes[0] != n_neigh {
+                    st[0].mapping[n_neigh]
+                } else {
+                    nodes[1]
+                };
+                if m_neigh == UNMAPPED {
+                    continue;
                 }
-            }
-        }
-        true
-    };
-    let mut stack: Vec<Frame<NodeIndex<Ix>>> = vec![Frame::Outer];
-
-    while let Some(frame) = stack.pop() {
-        match frame {
-            Frame::Unwind {
-                nodes,
-                open_list: ol,
-            } => {
-                pop_state(&mut st, nodes);
-
-                match next_from_ix(&mut st, nodes[0], ol) {
-                    None => continue,
-                    Some(nx) => {
-                        let f = Frame::Inner {
-                            nodes: [nx, nodes[1]],
-                            open_list: ol,
-                        };
-                        stack.push(f);
-                    }
+                let m_edge = succ[1].iter().position(|&m| m == m_neigh).unwrap();
+                if !edge_match.eq(
+                    edges0.succ[nodes[0]][k].weight(),
+                    edges1.succ[nodes[1]][m_edge].weight(),
+                ) {
+                    return false;
                 }
             }
-            Frame::Outer => match next_candidate(&mut st) {
-                None => continue,
-                Some((nx, mx, ol)) => {
-                    let f = Frame::Inner {
-                        nodes: [nx, mx],
-                        open_list: ol,
-                    };
-                    stack.push(f);
-                }
-            },
-            Frame::Inner {
-                nodes,
-                open_list: ol,
-            } => {
-                if is_feasible(&mut st, nodes) {
-                    push_state(&mut st, nodes);
-                    if st[0].is_complete() {
-                        return Some(true);
-                    }
-                    // Check cardinalities of Tin, Tout sets
-                    if st[0].out_size == st[1].out_size && st[0].ins_size == st[1].ins_size {
-                        let f0 = Frame::Unwind {
-                            nodes,
-                            open_list: ol,
-                        };
-                        stack.push(f0);
-                        stack.push(Frame::Outer);
+            // incoming edges
+            if st[0].directed {
+                let pred = [&st[0].pred[nodes[0]], &st[1].pred[nodes[1]]];
+                for (k, &n_neigh) in pred[0].iter().enumerate() {
+                    // the self loop case is handled in outgoing
+                    let m_neigh = st[0].mapping[n_neigh];
+                    if m_neigh == UNMAPPED {
                         continue;
                     }
-                    pop_state(&mut st, nodes);
-                }
-                match next_from_ix(&mut st, nodes[0], ol) {
-                    None => continue,
-                    Some(nx) => {
-                        let f = Frame::Inner {
-                            nodes: [nx, nodes[1]],
-                            open_list: ol,
-                        };
-                        stack.push(f);
+                    let m_edge = pred[1].iter().position(|&m| m == m_neigh).unwrap();
+                    if !edge_match.eq(
+                        edges0.pred[nodes[0]][k].weight(),
+                        edges1.pred[nodes[1]][m_edge].weight(),
+                    ) {
+                        return false;
                     }
                 }
             }
         }
+        true
     }
-    None
 }
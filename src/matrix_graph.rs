@@ -1,7 +1,7 @@
 //! `MatrixGraph<N, E, Ty, NullN, NullE, Ix>` is a graph datastructure backed by an adjacency matrix.
 
 use std::marker::PhantomData;
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut};
 
 use std::cmp;
 use std::mem;
@@ -15,9 +15,9 @@ use crate::{Directed, Direction, EdgeType, IntoWeightedEdge, Outgoing, Undirecte
 use crate::graph::NodeIndex as GraphNodeIndex;
 
 use crate::visit::{
-    Data, GetAdjacencyMatrix, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoNeighbors,
-    IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable,
-    NodeCount, NodeIndexable, Visitable,
+    incident_weight, Data, Degree, GetAdjacencyMatrix, GraphBase, GraphProp, IntoEdgeReferences,
+    IntoEdges, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences,
+    NodeCompactIndexable, NodeCount, NodeIndexable, Visitable,
 };
 
 use crate::data::Build;
@@ -1150,6 +1150,33 @@ impl<'a, N, E, Ty: EdgeType, Null: Nullable<Wrapped = E>, Ix: IndexType> IntoEdg
     }
 }
 
+impl<N, E, Ty: EdgeType, Null: Nullable<Wrapped = E>, Ix: IndexType> Degree
+    for &MatrixGraph<N, E, Ty, Null, Ix>
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        incident_weight(Ty::is_directed(), MatrixGraph::edges(self, a), edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        if !Ty::is_directed() {
+            return self.weighted_out_degree(a, edge_weight);
+        }
+        // `on_rows` yields the edges to `a` with their endpoints swapped.
+        let edges =
+            Edges::<Ty, Null, Ix>::on_rows(a.index(), &self.node_adjacencies, self.node_capacity)
+                .map(|(target, source, weight)| (source, target, weight));
+        incident_weight(true, edges, edge_weight)
+    }
+}
+
 impl<N, E, Ty: EdgeType, Null: Nullable<Wrapped = E>, Ix: IndexType> NodeIndexable
     for MatrixGraph<N, E, Ty, Null, Ix>
 {
@@ -3,9 +3,10 @@ use crate::prelude::*;
 use fixedbitset::FixedBitSet;
 use std::collections::HashSet;
 use std::marker::PhantomData;
+use std::ops::Add;
 
 use crate::data::DataMap;
-use crate::visit::{Data, NodeCompactIndexable, NodeCount};
+use crate::visit::{Data, Degree, NodeCompactIndexable, NodeCount};
 use crate::visit::{
     GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors,
     IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences, NodeIndexable, NodeRef,
@@ -257,6 +258,42 @@ where
     }
 }
 
+impl<G, F> Degree for &NodeFiltered<G, F>
+where
+    G: Degree,
+    F: FilterNode<G::NodeId>,
+{
+    fn weighted_out_degree<W, H>(self, a: Self::NodeId, mut edge_weight: H) -> W
+    where
+        W: Default + Add<Output = W>,
+        H: FnMut(Self::EdgeRef) -> W,
+    {
+        let f = &self.1;
+        self.0.weighted_out_degree(a, |edge| {
+            if f.include_node(edge.source()) && f.include_node(edge.target()) {
+                edge_weight(edge)
+            } else {
+                W::default()
+            }
+        })
+    }
+
+    fn weighted_in_degree<W, H>(self, a: Self::NodeId, mut edge_weight: H) -> W
+    where
+        W: Default + Add<Output = W>,
+        H: FnMut(Self::EdgeRef) -> W,
+    {
+        let f = &self.1;
+        self.0.weighted_in_degree(a, |edge| {
+            if f.include_node(edge.source()) && f.include_node(edge.target()) {
+                edge_weight(edge)
+            } else {
+                W::default()
+            }
+        })
+    }
+}
+
 impl<G, F> DataMap for NodeFiltered<G, F>
 where
     G: DataMap,
@@ -475,6 +512,42 @@ where
     }
 }
 
+impl<G, F> Degree for &EdgeFiltered<G, F>
+where
+    G: Degree,
+    F: FilterEdge<G::EdgeRef>,
+{
+    fn weighted_out_degree<W, H>(self, a: Self::NodeId, mut edge_weight: H) -> W
+    where
+        W: Default + Add<Output = W>,
+        H: FnMut(Self::EdgeRef) -> W,
+    {
+        let f = &self.1;
+        self.0.weighted_out_degree(a, |edge| {
+            if f.include_edge(edge) {
+                edge_weight(edge)
+            } else {
+                W::default()
+            }
+        })
+    }
+
+    fn weighted_in_degree<W, H>(self, a: Self::NodeId, mut edge_weight: H) -> W
+    where
+        W: Default + Add<Output = W>,
+        H: FnMut(Self::EdgeRef) -> W,
+    {
+        let f = &self.1;
+        self.0.weighted_in_degree(a, |edge| {
+            if f.include_edge(edge) {
+                edge_weight(edge)
+            } else {
+                W::default()
+            }
+        })
+    }
+}
+
 Data! {delegate_impl [[G, F], G, EdgeFiltered<G, F>, access0]}
 GraphProp! {delegate_impl [[G, F], G, EdgeFiltered<G, F>, access0]}
 IntoNodeIdentifiers! {delegate_impl [['a, G, F], G, &'a EdgeFiltered<G, F>, access0]}
@@ -53,6 +53,7 @@ pub use self::traversal::*;
 use fixedbitset::FixedBitSet;
 use std::collections::HashSet;
 use std::hash::{BuildHasher, Hash};
+use std::ops::Add;
 
 use super::{graph, EdgeType};
 use crate::graph::NodeIndex;
@@ -260,6 +261,151 @@ pub trait IntoEdgesDirected : IntoEdges + IntoNeighborsDirected {
 
 IntoEdgesDirected! {delegate_impl []}
 
+/// Access to the degree of each node.
+///
+/// The degree of a node counts the edges incident to it, depending on the
+/// graph’s edge type:
+///
+/// - `Directed`: The out-degree counts the edges from `a`, the in-degree the
+///   edges to `a`, and the degree their sum. A self loop counts once in each
+///   direction.
+/// - `Undirected`: The out-degree, in-degree and degree all count the edges
+///   connected to `a`, with a self loop counted twice.
+///
+/// Self loops are counted the same way for every graph type, unlike the
+/// number of `neighbors_directed`, which lists the other endpoint of a self
+/// loop of `Graph` once for `Undirected`, but twice for the two directions of
+/// `Directed`.
+///
+/// The weighted degrees sum `edge_weight` over the same edges instead of
+/// counting them.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use petgraph::visit::Degree;
+///
+/// let mut g = Graph::<(), u32>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.extend_with_edges(&[(a, b, 2), (b, a, 3), (a, a, 5)]);
+///
+/// assert_eq!(g.out_degree(a), 2);
+/// assert_eq!(g.in_degree(a), 2);
+/// assert_eq!(g.degree(a), 4);
+/// assert_eq!(g.weighted_out_degree(a, |e| *e.weight()), 7);
+///
+/// let u = Graph::<(), u32, Undirected>::from_edges(&[(0, 1), (0, 0)]);
+/// assert_eq!(u.degree(0.into()), 3);
+/// assert_eq!(u.degree(1.into()), 1);
+/// ```
+pub trait Degree: IntoEdgeReferences + GraphProp {
+    /// Return the sum of `edge_weight` over the outgoing edges of `a`.
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W;
+
+    /// Return the sum of `edge_weight` over the incoming edges of `a`.
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W;
+
+    /// Return the sum of `edge_weight` over the edges of `a`.
+    fn weighted_degree<W, F>(self, a: Self::NodeId, mut edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        if self.is_directed() {
+            self.weighted_out_degree(a, &mut edge_weight) + self.weighted_in_degree(a, edge_weight)
+        } else {
+            self.weighted_out_degree(a, edge_weight)
+        }
+    }
+
+    /// Return the number of outgoing edges of `a`.
+    fn out_degree(self, a: Self::NodeId) -> usize {
+        self.weighted_out_degree(a, |_| 1)
+    }
+
+    /// Return the number of incoming edges of `a`.
+    fn in_degree(self, a: Self::NodeId) -> usize {
+        self.weighted_in_degree(a, |_| 1)
+    }
+
+    /// Return the number of edges of `a`.
+    fn degree(self, a: Self::NodeId) -> usize {
+        self.weighted_degree(a, |_| 1)
+    }
+}
+
+impl<G> Degree for &G
+where
+    G: Degree,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        (*self).weighted_out_degree(a, edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        (*self).weighted_in_degree(a, edge_weight)
+    }
+}
+
+/// Sum `edge_weight` over `edges`, counting self loops twice if the graph is
+/// undirected.
+pub(crate) fn incident_weight<I, W, F>(directed: bool, edges: I, mut edge_weight: F) -> W
+where
+    I: IntoIterator,
+    I::Item: EdgeRef,
+    <I::Item as EdgeRef>::NodeId: PartialEq,
+    W: Default + Add<Output = W>,
+    F: FnMut(I::Item) -> W,
+{
+    let mut total = W::default();
+    for edge in edges {
+        if !directed && edge.source() == edge.target() {
+            total = total + edge_weight(edge);
+        }
+        total = total + edge_weight(edge);
+    }
+    total
+}
+
+impl<N, E, Ty, Ix> Degree for &Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        incident_weight(self.is_directed(), self.edges(a), edge_weight)
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        let edges = self.edges_directed(a, Direction::Incoming);
+        incident_weight(self.is_directed(), edges, edge_weight)
+    }
+}
+
 trait_template! {
 /// Access to the sequence of the graph’s `NodeId`s.
 pub trait IntoNodeIdentifiers : GraphRef {
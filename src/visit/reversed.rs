@@ -1,7 +1,9 @@
+use std::ops::Add;
+
 use crate::{Direction, Incoming};
 
 use crate::visit::{
-    Data, EdgeRef, GraphBase, GraphProp, GraphRef, IntoEdgeReferences, IntoEdges,
+    Data, Degree, EdgeRef, GraphBase, GraphProp, GraphRef, IntoEdgeReferences, IntoEdges,
     IntoEdgesDirected, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers,
     IntoNodeReferences, NodeCompactIndexable, NodeCount, NodeIndexable, Visitable,
 };
@@ -65,6 +67,29 @@ where
     }
 }
 
+impl<G> Degree for Reversed<G>
+where
+    G: Degree,
+{
+    fn weighted_out_degree<W, F>(self, a: Self::NodeId, mut edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        self.0
+            .weighted_in_degree(a, |edge| edge_weight(ReversedEdgeReference(edge)))
+    }
+
+    fn weighted_in_degree<W, F>(self, a: Self::NodeId, mut edge_weight: F) -> W
+    where
+        W: Default + Add<Output = W>,
+        F: FnMut(Self::EdgeRef) -> W,
+    {
+        self.0
+            .weighted_out_degree(a, |edge| edge_weight(ReversedEdgeReference(edge)))
+    }
+}
+
 impl<G: Visitable> Visitable for Reversed<G> {
     type Map = G::Map;
     fn visit_map(&self) -> G::Map {
@@ -223,6 +223,137 @@ fn mst() {
     assert!(mst.find_edge(b, c).is_none());
 }
 
+#[test]
+fn spanning_forests() {
+    use petgraph::algo::{
+        boruvka_spanning_forest, max_spanning_forest, prim_spanning_forest, SpanningForest,
+    };
+
+    // The same graph as in `mst`.
+    let mut gr = Graph::<_, _>::new();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B");
+    let c = gr.add_node("C");
+    let d = gr.add_node("D");
+    let e = gr.add_node("E");
+    let f = gr.add_node("F");
+    let g = gr.add_node("G");
+    gr.add_edge(a, b, 7.);
+    gr.add_edge(a, d, 5.);
+    gr.add_edge(d, b, 9.);
+    gr.add_edge(b, c, 8.);
+    gr.add_edge(b, e, 7.);
+    gr.add_edge(c, e, 5.);
+    gr.add_edge(d, e, 15.);
+    gr.add_edge(d, f, 6.);
+    gr.add_edge(f, e, 8.);
+    gr.add_edge(f, g, 11.);
+    gr.add_edge(e, g, 9.);
+    let h = gr.add_node("H");
+    let i = gr.add_node("I");
+    let j = gr.add_node("J");
+    gr.add_edge(h, i, 1.);
+    gr.add_edge(h, j, 3.);
+    gr.add_edge(i, j, 1.);
+
+    let expected = set(vec![
+        (a, b),
+        (a, d),
+        (b, e),
+        (c, e),
+        (e, g),
+        (d, f),
+        (h, i),
+        (i, j),
+    ]);
+    let check = |forest: SpanningForest<pg::graph::EdgeReference<f64>, f64>, total: f64| {
+        let pairs = forest.edges().iter().map(|e| {
+            let (s, t) = (e.source(), e.target());
+            (std::cmp::min(s, t), std::cmp::max(s, t))
+        });
+        assert_eq!(set(pairs), expected);
+        assert_eq!(*forest.total_weight(), total);
+        assert_eq!(
+            set(forest.edge_ids()),
+            set(forest.edges().iter().map(|e| e.id()))
+        );
+    };
+    check(prim_spanning_forest(&gr, |e| *e.weight()), 41.);
+    check(boruvka_spanning_forest(&gr, |e| *e.weight()), 41.);
+    check(max_spanning_forest(&gr, |e| -*e.weight()), -41.);
+
+    let forest = max_spanning_forest(&gr, |e| *e.weight());
+    assert_eq!(forest.len(), gr.node_count() - 2);
+    assert_eq!(
+        *forest.total_weight(),
+        15. + 11. + 9. + 9. + 8. + 7. + 3. + 1.
+    );
+
+    // Prim's algorithm on an adjacency matrix, with a removed node.
+    let mut m = petgraph::matrix_graph::UnMatrix::<(), u8>::new_undirected();
+    let nodes: Vec<_> = (0..5).map(|_| m.add_node(())).collect();
+    for (x, y, w) in vec![
+        (0, 1, 1),
+        (1, 2, 9),
+        (2, 3, 2),
+        (3, 4, 3),
+        (4, 0, 4),
+        (0, 2, 8),
+    ] {
+        m.add_edge(nodes[x], nodes[y], w);
+    }
+    m.remove_node(nodes[4]);
+    let forest = prim_spanning_forest(&m, |e| *e.weight());
+    assert_eq!(forest.len(), 3);
+    assert_eq!(*forest.total_weight(), 1 + 2 + 8);
+}
+
+#[test]
+fn min_arborescence() {
+    use petgraph::algo::{max_branching, min_spanning_arborescence};
+
+    let mut gr = Graph::<_, _>::new();
+    let r = gr.add_node("R");
+    let a = gr.add_node("A");
+    let b = gr.add_node("B");
+    let c = gr.add_node("C");
+    let d = gr.add_node("D");
+    gr.add_edge(r, a, 5);
+    gr.add_edge(r, b, 1);
+    gr.add_edge(r, c, 6);
+    gr.add_edge(a, b, 2);
+    gr.add_edge(b, c, 3);
+    gr.add_edge(c, a, 1);
+    gr.add_edge(c, d, 2);
+    gr.add_edge(d, b, 0);
+    gr.add_edge(a, r, 0);
+    gr.add_edge(d, d, -7);
+
+    // The cheapest incoming edges form the cycle b -> c -> a -> b, which is
+    // broken by entering it at b from the root.
+    let edges = min_spanning_arborescence(&gr, r, |e| *e.weight()).unwrap();
+    let endpoints = set(edges.iter().map(|e| (e.source(), e.target())));
+    assert_eq!(endpoints, set(vec![(r, b), (b, c), (c, a), (c, d)]));
+    assert_eq!(edges.iter().map(|e| *e.weight()).sum::<i32>(), 7);
+
+    let e = gr.add_node("E");
+    let err = min_spanning_arborescence(&gr, r, |e| *e.weight()).unwrap_err();
+    assert_eq!(err.node_id(), e);
+    gr.add_edge(e, r, 1);
+    assert!(min_spanning_arborescence(&gr, r, |e| *e.weight()).is_err());
+    gr.add_edge(d, e, 4);
+    let edges = min_spanning_arborescence(&gr, r, |e| *e.weight()).unwrap();
+    assert_eq!(edges.len(), gr.node_count() - 1);
+    assert_eq!(edges.iter().map(|e| *e.weight()).sum::<i32>(), 11);
+
+    // Maximum branching: every node has at most one incoming edge, negative
+    // edges and self loops are never used.
+    let branching = max_branching(&gr, |e| *e.weight());
+    let endpoints = set(branching.iter().map(|e| (e.source(), e.target())));
+    assert_eq!(endpoints, set(vec![(r, a), (r, c), (a, b), (c, d), (d, e)]));
+    assert_eq!(branching.iter().map(|e| *e.weight()).sum::<i32>(), 19);
+}
+
 #[test]
 fn selfloop() {
     let mut gr = Graph::new();
@@ -2367,3 +2498,1295 @@ fn test_dominators_simple_fast() {
         "nodes that aren't reachable from the root do not have an idom"
     );
 }
+
+#[test]
+fn lowest_common_ancestors() {
+    use petgraph::algo::lca::{all_lowest_common_ancestors, LowestCommonAncestors};
+
+    // An undirected tree rooted at `a`:
+    //
+    //         a
+    //       / | \
+    //      b  c  d
+    //     / \     \
+    //    e   f     g
+    //        |
+    //        h
+    let mut gr = UnGraph::<_, ()>::new_undirected();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    let d = gr.add_node("d");
+    let e = gr.add_node("e");
+    let f = gr.add_node("f");
+    let g = gr.add_node("g");
+    let h = gr.add_node("h");
+    gr.extend_with_edges(&[(a, b), (a, c), (a, d), (b, e), (b, f), (d, g), (f, h)]);
+
+    let lca = LowestCommonAncestors::new(&gr, a);
+    assert_eq!(lca.root(), a);
+    assert_eq!(lca.lca(e, h), Some(b));
+    assert_eq!(lca.lca(h, e), Some(b));
+    assert_eq!(lca.lca(h, g), Some(a));
+    assert_eq!(lca.lca(f, h), Some(f));
+    assert_eq!(lca.lca(c, c), Some(c));
+    assert_eq!(lca.depth(a), Some(0));
+    assert_eq!(lca.depth(h), Some(3));
+    assert!(lca.is_ancestor(b, h));
+    assert!(!lca.is_ancestor(h, b));
+
+    // Rooted somewhere else, the same tree gives other answers.
+    let lca = LowestCommonAncestors::new(&gr, h);
+    assert_eq!(lca.lca(e, g), Some(b));
+    assert_eq!(lca.lca(a, f), Some(f));
+
+    // Nodes outside of the tree have no common ancestor.
+    let mut gr = gr.into_edge_type::<Directed>();
+    let x = gr.add_node("x");
+    let lca = LowestCommonAncestors::new(&gr, a);
+    assert_eq!(lca.lca(x, a), None);
+    assert_eq!(lca.depth(x), None);
+
+    // Criss-cross merges in a DAG have more than one lowest common ancestor.
+    //
+    //   r -> p -> m1
+    //   |     \  ^
+    //   |      \/
+    //   |      /\
+    //   v     /  v
+    //   s -> q -> m2
+    let mut dag = DiGraph::<_, ()>::new();
+    let r = dag.add_node("r");
+    let p = dag.add_node("p");
+    let s = dag.add_node("s");
+    let q = dag.add_node("q");
+    let m1 = dag.add_node("m1");
+    let m2 = dag.add_node("m2");
+    dag.extend_with_edges(&[(r, p), (r, s), (s, q), (p, m1), (p, m2), (q, m1), (q, m2)]);
+
+    assert_eq!(
+        set(all_lowest_common_ancestors(&dag, m1, m2)),
+        set(vec![p, q])
+    );
+    assert_eq!(all_lowest_common_ancestors(&dag, p, q), vec![r]);
+    assert_eq!(all_lowest_common_ancestors(&dag, s, m2), vec![s]);
+    assert_eq!(all_lowest_common_ancestors(&dag, m1, m1), vec![m1]);
+    let z = dag.add_node("z");
+    assert!(all_lowest_common_ancestors(&dag, z, m1).is_empty());
+}
+
+#[test]
+fn test_dominance_frontiers_and_post_dominators() {
+    use std::collections::HashMap;
+
+    // A loop with a diamond in its body:
+    //
+    //   entry -> head -> left  -> latch -> exit
+    //              ^ \          ^   |
+    //              |  `-> right '   |
+    //              '----------------'
+    let mut graph = DiGraph::<_, ()>::new();
+    let entry = graph.add_node("entry");
+    let head = graph.add_node("head");
+    let left = graph.add_node("left");
+    let right = graph.add_node("right");
+    let latch = graph.add_node("latch");
+    let exit = graph.add_node("exit");
+    graph.extend_with_edges(&[
+        (entry, head),
+        (head, left),
+        (head, right),
+        (left, latch),
+        (right, latch),
+        (latch, head),
+        (latch, exit),
+    ]);
+
+    let doms = dominators::simple_fast(&graph, entry);
+    assert_eq!(
+        set(doms.immediately_dominated_by(head)),
+        set(vec![left, right, latch])
+    );
+    assert_eq!(set(doms.immediately_dominated_by(entry)), set(vec![head]));
+    assert_eq!(doms.immediately_dominated_by(exit).next(), None);
+
+    let frontiers = doms.dominance_frontiers(&graph);
+    let expected: HashMap<_, _> = vec![
+        (entry, set(vec![])),
+        (head, set(vec![head])),
+        (left, set(vec![latch])),
+        (right, set(vec![latch])),
+        (latch, set(vec![head])),
+        (exit, set(vec![])),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(frontiers, expected);
+
+    let tree = doms.dominator_tree();
+    assert_eq!(tree.node_count(), graph.node_count());
+    assert_eq!(tree.edge_count(), graph.node_count() - 1);
+    assert_eq!(tree[n(0)], entry);
+    for edge in tree.raw_edges() {
+        assert_eq!(
+            doms.immediate_dominator(tree[edge.target()]),
+            Some(tree[edge.source()])
+        );
+    }
+    // The order of the nodes does not depend on hashing.
+    let weights = |tree: &DiGraph<NodeIndex, ()>| -> Vec<_> {
+        tree.raw_nodes().iter().map(|node| node.weight).collect()
+    };
+    for _ in 0..10 {
+        let again = dominators::simple_fast(&graph, entry);
+        assert_eq!(weights(&again.dominator_tree()), weights(&tree));
+        assert!(again
+            .immediately_dominated_by(head)
+            .eq(doms.immediately_dominated_by(head)));
+    }
+
+    let post_doms = dominators::post_dominators(&graph, exit);
+    assert_eq!(post_doms.root(), exit);
+    assert_eq!(post_doms.immediate_dominator(latch), Some(exit));
+    assert_eq!(post_doms.immediate_dominator(left), Some(latch));
+    assert_eq!(post_doms.immediate_dominator(right), Some(latch));
+    assert_eq!(post_doms.immediate_dominator(head), Some(latch));
+    assert_eq!(post_doms.immediate_dominator(entry), Some(head));
+    assert_eq!(post_doms.immediate_dominator(exit), None);
+}
+
+#[test]
+fn test_dominators_lengauer_tarjan() {
+    // The graph from `test_dominators_simple_fast`, with nodes r, a, b, ... l
+    // numbered 0, 1, 2, ... 12.
+    let graph = DiGraph::<(), ()>::from_edges(&[
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 4),
+        (2, 1),
+        (2, 4),
+        (2, 5),
+        (3, 6),
+        (3, 7),
+        (4, 12),
+        (5, 8),
+        (6, 9),
+        (7, 9),
+        (7, 10),
+        (8, 5),
+        (8, 11),
+        (9, 11),
+        (10, 9),
+        (11, 0),
+        (11, 9),
+        (12, 8),
+    ]);
+    let (r, c, d, g) = (n(0), n(3), n(4), n(7));
+
+    let doms = dominators::lengauer_tarjan(&graph, r);
+    assert_eq!(doms.root(), r);
+    let idoms: Vec<_> = graph
+        .node_indices()
+        .map(|node| doms.immediate_dominator(node))
+        .collect();
+    assert_eq!(
+        idoms,
+        vec![
+            None,
+            Some(r),
+            Some(r),
+            Some(r),
+            Some(r),
+            Some(r),
+            Some(c),
+            Some(c),
+            Some(r),
+            Some(r),
+            Some(g),
+            Some(r),
+            Some(d),
+        ]
+    );
+
+    let simple = dominators::simple_fast(&graph, r);
+    for node in graph.node_indices() {
+        assert_eq!(
+            doms.dominators(node).unwrap().collect::<Vec<_>>(),
+            simple.dominators(node).unwrap().collect::<Vec<_>>()
+        );
+    }
+
+    let mut graph = graph.clone();
+    let z = graph.add_node(());
+    let doms = dominators::lengauer_tarjan(&graph, r);
+    assert_eq!(doms.immediate_dominator(z), None);
+    assert!(doms.dominators(z).is_none());
+}
+
+#[test]
+fn loop_nesting_forest() {
+    use petgraph::algo::loops;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5
+    //      ^    ^ |  ^ |  |
+    //      |    '-'  '-'  |
+    //      '--------------'
+    let graph = DiGraph::<(), ()>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 2),
+        (2, 3),
+        (3, 2),
+        (3, 4),
+        (4, 1),
+        (4, 5),
+        (3, 3),
+    ]);
+    let forest = loops::loop_nesting_forest(&graph, n(0));
+    assert_eq!(forest.loop_count(), 3);
+    let depths: Vec<_> = graph.node_indices().map(|v| forest.depth(v)).collect();
+    assert_eq!(depths, vec![0, 1, 2, 3, 1, 0]);
+    let self_loop = forest.loop_of(n(3)).unwrap();
+    assert_eq!(forest.header(self_loop), n(3));
+    assert_eq!(forest.back_edges(self_loop), &[(n(3), n(3))]);
+    let middle = forest.parent(self_loop).unwrap();
+    assert_eq!(forest.header(middle), n(2));
+    let mut back_edges = forest.back_edges(middle).to_vec();
+    back_edges.sort();
+    assert_eq!(back_edges, vec![(n(2), n(2)), (n(3), n(2))]);
+    let outer = forest.parent(middle).unwrap();
+    assert_eq!(forest.header(outer), n(1));
+    assert_eq!(forest.parent(outer), None);
+    assert_eq!(forest.back_edges(outer), &[(n(4), n(1))]);
+    assert!(forest.contains(outer, n(3)));
+    assert!(!forest.contains(middle, n(4)));
+    assert!(forest.loops().all(|l| forest.is_reducible(l)));
+
+    // An irreducible loop entered at both 1 and 2.
+    let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (2, 1), (2, 3)]);
+    let forest = loops::loop_nesting_forest(&graph, n(0));
+    assert_eq!(forest.loop_count(), 1);
+    let l = forest.loop_of(n(2)).unwrap();
+    assert!(!forest.is_reducible(l));
+    let mut headers = forest.headers(l).to_vec();
+    headers.sort();
+    assert_eq!(headers, vec![n(1), n(2)]);
+    assert_eq!(forest.header(l), forest.headers(l)[0]);
+    assert_eq!(forest.back_edges(l).len(), 2);
+    assert_eq!(forest.loop_of(n(0)), None);
+    assert_eq!(forest.loop_of(n(3)), None);
+}
+
+#[test]
+fn graph_coloring() {
+    use petgraph::algo::coloring::{
+        dsatur_coloring, exact_coloring, greedy_coloring, is_proper_coloring, NodeOrdering,
+    };
+
+    // A crown graph: greedy coloring in natural order needs a color for every
+    // pair of nodes, but the graph is bipartite.
+    let mut crown = UnGraph::<(), ()>::new_undirected();
+    let left: Vec<_> = (0..4).map(|_| crown.add_node(())).collect();
+    let right: Vec<_> = (0..4).map(|_| crown.add_node(())).collect();
+    for i in 0..4 {
+        for j in 0..4 {
+            if i != j {
+                crown.add_edge(left[i], right[j], ());
+            }
+        }
+    }
+    let mut interleaved = UnGraph::<(), ()>::new_undirected();
+    let nodes: Vec<_> = (0..8).map(|_| interleaved.add_node(())).collect();
+    for e in crown.edge_references() {
+        let position = |n: NodeIndex| 2 * (n.index() % 4) + n.index() / 4;
+        interleaved.add_edge(nodes[position(e.source())], nodes[position(e.target())], ());
+    }
+
+    let (colors, count) = greedy_coloring(&interleaved, NodeOrdering::Natural);
+    assert!(is_proper_coloring(&interleaved, &colors));
+    assert_eq!(count, 4);
+    for &ordering in &[NodeOrdering::SmallestLast, NodeOrdering::LargestFirst] {
+        let (colors, count) = greedy_coloring(&interleaved, ordering);
+        assert!(is_proper_coloring(&interleaved, &colors));
+        assert!(count >= 2);
+    }
+    assert_eq!(dsatur_coloring(&interleaved).1, 2);
+    assert_eq!(exact_coloring(&interleaved).1, 2);
+
+    // An odd wheel needs four colors; self loops are ignored, and directed
+    // edges count in both directions.
+    let mut wheel = DiGraph::<(), ()>::from_edges(&[
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 1),
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (0, 5),
+    ]);
+    wheel.add_edge(n(0), n(0), ());
+    let (colors, count) = dsatur_coloring(&wheel);
+    assert_eq!(count, 4);
+    assert!(is_proper_coloring(&wheel, &colors));
+    let (colors, count) = exact_coloring(&wheel);
+    assert_eq!(count, 4);
+    assert!(is_proper_coloring(&wheel, &colors));
+
+    let mut colors = colors;
+    colors.insert(n(1), colors[&n(0)]);
+    assert!(!is_proper_coloring(&wheel, &colors));
+    colors.remove(&n(1));
+    assert!(!is_proper_coloring(&wheel, &colors));
+
+    let empty = Graph::<(), ()>::new();
+    assert_eq!(exact_coloring(&empty).1, 0);
+}
+
+#[test]
+fn test_bipartite_partition() {
+    use petgraph::algo::bipartite_partition;
+
+    // Two components; the directed edges point both ways between the sides.
+    let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (2, 1), (2, 3), (4, 5), (6, 5)]);
+    let (left, right) = bipartite_partition(&graph).unwrap();
+    assert_eq!(left, vec![n(0), n(2), n(4), n(6)]);
+    assert_eq!(right, vec![n(1), n(3), n(5)]);
+
+    // A cycle of length five in the second component.
+    graph.extend_with_edges(&[(6, 7), (7, 8), (8, 4)]);
+    let cycle = bipartite_partition(&graph).unwrap_err().into_nodes();
+    assert_eq!(cycle.len(), 5);
+    let set: HashSet<_> = cycle.iter().cloned().collect();
+    assert_eq!(set, (4..9).map(n).collect());
+    for (i, &a) in cycle.iter().enumerate() {
+        let b = cycle[(i + 1) % cycle.len()];
+        assert!(graph.contains_edge(a, b) || graph.contains_edge(b, a));
+    }
+
+    // A self loop is an odd cycle of length one.
+    let mut graph = UnGraph::<(), ()>::from_edges(&[(0, 1)]);
+    graph.add_edge(n(1), n(1), ());
+    assert_eq!(bipartite_partition(&graph).unwrap_err().nodes(), &[n(1)]);
+    assert_eq!(
+        bipartite_partition(&Graph::<(), ()>::new()),
+        Ok((vec![], vec![]))
+    );
+}
+
+#[test]
+fn test_maximal_cliques() {
+    use petgraph::algo::{clique_number, maximal_cliques, maximum_clique};
+
+    // The Moon–Moser graph on nine nodes (complete tripartite with parts of
+    // three) has 3^3 maximal cliques, one for each choice of a node per part.
+    let mut graph = UnGraph::<(), ()>::new_undirected();
+    let nodes: Vec<_> = (0..9).map(|_| graph.add_node(())).collect();
+    for i in 0..9 {
+        for j in i + 1..9 {
+            if i / 3 != j / 3 {
+                graph.add_edge(nodes[i], nodes[j], ());
+            }
+        }
+    }
+    let cliques: Vec<_> = maximal_cliques(&graph).collect();
+    assert_eq!(cliques.len(), 27);
+    for clique in &cliques {
+        assert_eq!(clique.len(), 3);
+        let parts: HashSet<_> = clique.iter().map(|n| n.index() / 3).collect();
+        assert_eq!(parts.len(), 3);
+    }
+    let distinct: HashSet<Vec<_>> = cliques
+        .into_iter()
+        .map(|mut clique| {
+            clique.sort();
+            clique
+        })
+        .collect();
+    assert_eq!(distinct.len(), 27);
+    assert_eq!(clique_number(&graph), 3);
+
+    // A directed graph with a K4 and an isolated node.
+    let mut graph =
+        DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 0), (1, 3), (3, 2), (2, 4)]);
+    graph.add_node(());
+    let mut clique = maximum_clique(&graph);
+    clique.sort();
+    assert_eq!(clique, vec![n(0), n(1), n(2), n(3)]);
+    assert!(maximal_cliques(&graph).any(|clique| clique == vec![n(5)]));
+    assert_eq!(maximal_cliques(&graph).count(), 3);
+
+    assert_eq!(maximal_cliques(&Graph::<(), ()>::new()).next(), None);
+    assert_eq!(clique_number(&Graph::<(), ()>::new()), 0);
+}
+
+#[test]
+fn test_covering() {
+    use petgraph::algo::covering::*;
+
+    // The Petersen graph.
+    let petersen = UnGraph::<(), ()>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 0),
+        (0, 5),
+        (1, 6),
+        (2, 7),
+        (3, 8),
+        (4, 9),
+        (5, 7),
+        (7, 9),
+        (9, 6),
+        (6, 8),
+        (8, 5),
+    ]);
+
+    let set = maximum_independent_set(&petersen);
+    assert_eq!(set.len(), 4);
+    assert!(is_independent_set(&petersen, &set));
+    let set = greedy_independent_set(&petersen);
+    assert!(is_independent_set(&petersen, &set));
+    assert!(!is_independent_set(&petersen, &[n(0), n(1)]));
+
+    let cover = minimum_vertex_cover(&petersen);
+    assert_eq!(cover.len(), 6);
+    assert!(is_vertex_cover(&petersen, &cover));
+    let cover = approx_vertex_cover(&petersen);
+    assert!(is_vertex_cover(&petersen, &cover));
+    assert!(cover.len() <= 12);
+    assert!(!is_vertex_cover(&petersen, &[n(0), n(1), n(2), n(3), n(4)]));
+
+    let set = minimum_dominating_set(&petersen);
+    assert_eq!(set.len(), 3);
+    assert!(is_dominating_set(&petersen, &set));
+    let set = greedy_dominating_set(&petersen);
+    assert!(is_dominating_set(&petersen, &set));
+    assert!(!is_dominating_set(&petersen, &[n(0), n(2)]));
+
+    // Directed edges count in both directions, and a node with a self loop is
+    // adjacent to itself.
+    let mut graph = DiGraph::<(), ()>::from_edges(&[(1, 0), (2, 0), (3, 0)]);
+    assert_eq!(minimum_vertex_cover(&graph), vec![n(0)]);
+    assert_eq!(minimum_dominating_set(&graph), vec![n(0)]);
+    assert!(is_dominating_set(&graph, &[n(0)]));
+    graph.add_edge(n(1), n(1), ());
+    assert_eq!(maximum_independent_set(&graph), vec![n(2), n(3)]);
+    assert_eq!(minimum_vertex_cover(&graph), vec![n(0), n(1)]);
+    assert!(!is_independent_set(&graph, &[n(1)]));
+    assert!(is_vertex_cover(&graph, &approx_vertex_cover(&graph)));
+}
+
+#[test]
+fn test_core_number() {
+    use petgraph::algo::{core_number, degeneracy_ordering, k_core};
+    use petgraph::visit::IntoNodeIdentifiers;
+
+    // A K4 (0..4) joined by the path 3 - 4 - 5 to the triangle 5, 6, 7, and
+    // the pendant edge 7 - 8.
+    let edges = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 6),
+        (6, 7),
+        (7, 5),
+        (7, 8),
+    ];
+    let graph = UnGraph::<(), ()>::from_edges(&edges);
+    let cores = core_number(&graph);
+    let expected = vec![3, 3, 3, 3, 2, 2, 2, 2, 1];
+    for (i, &core) in expected.iter().enumerate() {
+        assert_eq!(cores[&n(i)], core);
+    }
+
+    // The same edges, directed, have the same in + out degrees.
+    let directed = DiGraph::<(), ()>::from_edges(&edges);
+    assert_eq!(core_number(&directed), cores);
+
+    // A 2-cycle gives both nodes an in + out degree of 2, and a self loop is
+    // ignored.
+    let cycle = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 0), (1, 1)]);
+    let cores = core_number(&cycle);
+    assert_eq!(cores[&n(0)], 2);
+    assert_eq!(cores[&n(1)], 2);
+
+    let order = degeneracy_ordering(&graph);
+    assert_eq!(order.len(), 9);
+    assert_eq!(order[0], n(8));
+    for (i, &u) in order.iter().enumerate() {
+        let later = graph
+            .neighbors(u)
+            .filter(|v| order[i + 1..].contains(v))
+            .count();
+        assert!(later <= 3);
+    }
+
+    let core = k_core(&graph, 2);
+    let nodes: Vec<_> = core.node_identifiers().collect();
+    assert_eq!(nodes, (0..8).map(n).collect::<Vec<_>>());
+    assert_eq!(core.neighbors(n(8)).count(), 0);
+    let core = k_core(&graph, 3);
+    assert_eq!(core.node_identifiers().count(), 4);
+    assert_eq!(core.neighbors(n(3)).count(), 3);
+    assert_eq!(k_core(&graph, 4).node_identifiers().count(), 0);
+}
+
+#[test]
+fn test_triangles_and_clustering() {
+    use petgraph::algo::{
+        clustering, clustering_csr, transitivity, transitivity_csr, triangles, triangles_csr,
+    };
+    use petgraph::csr::Csr;
+
+    // A K4 (0..4) with a triangle 3, 4, 5 attached, a pendant node 6, a
+    // self loop and a parallel edge.
+    let edges = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (5, 6),
+        (4, 4),
+        (0, 1),
+    ];
+    let graph = UnGraph::<(), ()>::from_edges(&edges);
+    let expected = vec![3, 3, 3, 4, 1, 1, 0];
+    assert_eq!(triangles(&graph), expected);
+
+    let c = clustering(&graph);
+    assert_eq!(c[0], 1.);
+    assert_eq!(c[3], 4. / 10.);
+    assert_eq!(c[5], 1. / 3.);
+    assert_eq!(c[6], 0.);
+    // 5 triangles, and 3 + 3 + 3 + 10 + 1 + 3 paths of length two.
+    assert_eq!(transitivity(&graph), 15. / 23.);
+
+    // Directed edges count in either direction.
+    let directed = DiGraph::<(), ()>::from_edges(&edges);
+    assert_eq!(triangles(&directed), expected);
+    assert_eq!(transitivity(&directed), 15. / 23.);
+
+    // Indexed by node index, with zeros in the holes of a StableGraph.
+    let mut stable = StableUnGraph::<(), ()>::from_edges(&edges);
+    stable.remove_node(n(6));
+    stable.remove_node(n(1));
+    assert_eq!(triangles(&stable), vec![1, 0, 1, 2, 1, 1]);
+
+    let mut csr = Csr::<(), (), Undirected>::with_nodes(7);
+    for &(a, b) in &edges {
+        csr.add_edge(a, b, ());
+    }
+    assert_eq!(triangles_csr(&csr), expected);
+    assert_eq!(triangles(&csr), expected);
+    assert_eq!(clustering_csr(&csr), c);
+    assert_eq!(transitivity_csr(&csr), 15. / 23.);
+}
+
+#[test]
+fn test_page_rank() {
+    use petgraph::algo::{page_rank, personalized_page_rank};
+    use petgraph::csr::Csr;
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+    // A directed cycle ranks every node the same.
+    let cycle = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let scores = page_rank(&cycle, 0.85, 1e-12, 1000);
+    assert!(scores.iter().all(|&s| close(s, 1. / 3.)));
+
+    // Node 1 is dangling: x0 = (0.85 x1 + 0.15) / 2 and x0 + x1 = 1.
+    let dangling = Graph::<(), ()>::from_edges(&[(0, 1)]);
+    let scores = page_rank(&dangling, 0.85, 1e-12, 1000);
+    assert!(close(scores[0], 1. / 2.85));
+    assert!(close(scores[1], 1.85 / 2.85));
+
+    // Jumping only to node 0, from node 1 as well.
+    let scores = personalized_page_rank(&dangling, 0.85, 1e-12, 1000, Some(&[2., 0.]), |_| 1.);
+    assert!(close(scores[0], 1. / 1.85));
+    assert!(close(scores[1], 0.85 / 1.85));
+
+    // Edges with weight zero are not followed.
+    let star = Graph::<(), f64>::from_edges(&[(0, 1, 1.), (0, 2, 0.), (1, 0, 1.), (2, 0, 1.)]);
+    let weighted = personalized_page_rank(&star, 0.85, 1e-12, 1000, None, |e| *e.weight());
+    let unweighted = page_rank(&star, 0.85, 1e-12, 1000);
+    assert!(weighted[1] > weighted[2]);
+    assert!(close(unweighted[1], unweighted[2]));
+
+    // Undirected edges are followed in both directions.
+    let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+    let scores = page_rank(&path, 0.85, 1e-12, 1000);
+    assert!(close(scores.iter().sum(), 1.));
+    assert!(close(scores[0], scores[2]));
+    assert!(scores[1] > scores[0]);
+
+    // Removed nodes score zero, and the rest match the graph without them.
+    let mut stable = StableGraph::<(), ()>::from_edges(&[(0, 3), (3, 1), (1, 2), (2, 3), (3, 0)]);
+    stable.remove_node(n(0));
+    let scores = page_rank(&stable, 0.85, 1e-12, 1000);
+    assert_eq!(scores[0], 0.);
+    assert!(scores[1..].iter().all(|&s| close(s, 1. / 3.)));
+
+    let edges = [(0, 1), (0, 2), (1, 2), (2, 0), (3, 2)];
+    let graph = Graph::<(), ()>::from_edges(&edges);
+    let csr = Csr::<(), ()>::from_sorted_edges(&edges).unwrap();
+    let expected = page_rank(&graph, 0.85, 1e-12, 1000);
+    let scores = page_rank(&csr, 0.85, 1e-12, 1000);
+    for (&a, &b) in scores.iter().zip(&expected) {
+        assert!(close(a, b));
+    }
+    assert!(close(expected[3], 0.15 / 4.));
+}
+
+#[test]
+fn test_betweenness_centrality() {
+    use petgraph::algo::{
+        betweenness_centrality, edge_betweenness_centrality, weighted_betweenness_centrality,
+        weighted_edge_betweenness_centrality,
+    };
+
+    // Two triangles joined by the bridge 2 - 3.
+    let mut g = UnGraph::<(), u32>::new_undirected();
+    let nodes: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+    let edges: Vec<_> = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)]
+        .iter()
+        .map(|&(a, b)| g.add_edge(nodes[a], nodes[b], 1))
+        .collect();
+
+    // 3 * 3 pairs cross the bridge, and 2 more pairs on either side meet 2.
+    let scores = betweenness_centrality(&g, false, None);
+    assert_eq!(scores[&n(0)], 0.);
+    assert_eq!(scores[&n(2)], 6.);
+    assert_eq!(scores[&n(3)], 6.);
+    let normalized = betweenness_centrality(&g, true, None);
+    assert!((normalized[&n(2)] - 6. / 10.).abs() < 1e-9);
+
+    let edge_scores = edge_betweenness_centrality(&g, false, None);
+    assert_eq!(edge_scores.len(), 7);
+    assert_eq!(edge_scores[&edges[3]], 9.);
+    assert_eq!(edge_scores[&edges[0]], 1.);
+    assert_eq!(edge_scores[&edges[1]], 4.);
+    let normalized = edge_betweenness_centrality(&g, true, None);
+    assert!((normalized[&edges[3]] - 9. / 15.).abs() < 1e-9);
+
+    // Sampling every node of one side, scaled up by 6 / 3.
+    let sample = [n(0), n(1), n(2)];
+    let sampled = betweenness_centrality(&g, false, Some(&sample));
+    assert_eq!(sampled[&n(3)], 6.);
+    assert_eq!(sampled[&n(2)], 6.);
+    assert_eq!(sampled[&n(4)], 0.);
+
+    // Making the edge 0 - 1 expensive routes its pair through 2.
+    g[edges[0]] = 3;
+    let scores = weighted_betweenness_centrality(&g, false, None, |e| *e.weight());
+    assert_eq!(scores[&n(2)], 7.);
+    let edge_scores = weighted_edge_betweenness_centrality(&g, false, None, |e| *e.weight());
+    assert_eq!(edge_scores[&edges[0]], 0.);
+    assert_eq!(edge_scores[&edges[2]], 5.);
+
+    // A directed cycle, with a removed node.
+    let mut cycle = StableGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 1), (0, 3)]);
+    cycle.remove_node(n(0));
+    let scores = betweenness_centrality(&cycle, false, None);
+    assert_eq!(scores.len(), 3);
+    assert!(scores.values().all(|&s| s == 1.));
+
+    // Zero-cost edges: 0 -> 1 -> 2 is as short as 0 -> 2, in either order.
+    for edges in &[
+        [(0, 2, 0.), (0, 1, 0.), (1, 2, 0.), (2, 3, 1.)],
+        [(0, 1, 0.), (0, 2, 0.), (1, 2, 0.), (2, 3, 1.)],
+    ] {
+        let g = DiGraph::<(), f64>::from_edges(edges);
+        let scores = weighted_betweenness_centrality(&g, false, None, |e| *e.weight());
+        assert_eq!(scores[&n(1)], 1.);
+        assert_eq!(scores[&n(2)], 2.);
+        let edge_scores = weighted_edge_betweenness_centrality(&g, false, None, |e| *e.weight());
+        assert_eq!(edge_scores[&g.find_edge(n(1), n(2)).unwrap()], 3.);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_betweenness_zero_cost_cycle() {
+    use petgraph::algo::weighted_betweenness_centrality;
+
+    let g = UnGraph::<(), f64>::from_edges(&[(0, 1, 0.), (1, 2, 1.)]);
+    weighted_betweenness_centrality(&g, false, None, |e| *e.weight());
+}
+
+#[test]
+fn test_centrality() {
+    use petgraph::algo::centrality::{
+        closeness_centrality, eigenvector_centrality, harmonic_centrality, hits, katz_centrality,
+        weighted_closeness_centrality, weighted_eigenvector_centrality,
+        weighted_harmonic_centrality, weighted_hits, weighted_katz_centrality,
+    };
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+    // A directed path 0 -> 1 -> 2 and a node 3 by itself.
+    let mut g = Graph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 2, 2.)]);
+    g.add_node(());
+    assert_eq!(
+        closeness_centrality(&g),
+        vec![2. / 3. * 2. / 3., 1. / 3., 0., 0.]
+    );
+    assert_eq!(
+        weighted_closeness_centrality(&g, |e| *e.weight()),
+        vec![2. / 4. * 2. / 3., 1. / 6., 0., 0.]
+    );
+    // Distances to each node instead.
+    assert_eq!(
+        closeness_centrality(Reversed(&g)),
+        vec![0., 1. / 3., 2. / 3. * 2. / 3., 0.]
+    );
+    assert_eq!(harmonic_centrality(&g), vec![1.5, 1., 0., 0.]);
+    assert_eq!(
+        weighted_harmonic_centrality(&g, |e| *e.weight()),
+        vec![1. + 1. / 3., 0.5, 0., 0.]
+    );
+
+    // x = 0.1 A^T x + 1, with edge weights.
+    let katz = weighted_katz_centrality(&g, 0.1, 1., 1e-12, 100, |e| *e.weight());
+    assert!(close(katz[0], 1.));
+    assert!(close(katz[1], 1.1));
+    assert!(close(katz[2], 1.22));
+    assert!(close(katz[3], 1.));
+    let katz = katz_centrality(&g, 0.1, 1., 1e-12, 100);
+    assert!(close(katz[2], 1.11));
+
+    // An even cycle: power iteration on A alone would not converge.
+    let mut cycle = StableUnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0), (4, 4)]);
+    cycle.remove_node(n(4));
+    let eigen = eigenvector_centrality(&cycle, 1e-12, 1000);
+    assert_eq!(eigen.len(), 4);
+    assert!(eigen.iter().all(|&x| close(x, 0.5)));
+    // Constant weights do not change the eigenvector.
+    let eigen = weighted_eigenvector_centrality(&cycle, 1e-12, 1000, |_| 3.);
+    assert!(eigen.iter().all(|&x| close(x, 0.5)));
+
+    // Node 0 links to more authorities than 1 and 3.
+    let web = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (3, 2)]);
+    let (hubs, authorities) = hits(&web, 1e-12, 1000);
+    assert!(close(hubs.iter().sum(), 1.));
+    assert!(close(authorities.iter().sum(), 1.));
+    assert_eq!(hubs[2], 0.);
+    assert!(hubs[0] > hubs[1]);
+    assert!(close(hubs[1], hubs[3]));
+    assert!(authorities[2] > authorities[1]);
+    assert_eq!(authorities[0], 0.);
+    assert_eq!(authorities[3], 0.);
+
+    // A heavy link from 3 makes it the best hub.
+    let web = Graph::<(), f64>::from_edges(&[(0, 1, 1.), (0, 2, 1.), (1, 2, 1.), (3, 2, 5.)]);
+    let (hubs, authorities) = weighted_hits(&web, 1e-12, 1000, |e| *e.weight());
+    assert!(close(hubs.iter().sum(), 1.));
+    assert!(hubs[3] > hubs[0]);
+    assert!(hubs[3] > hubs[1]);
+    assert!(authorities[2] > authorities[1]);
+}
+
+#[test]
+fn test_community() {
+    use petgraph::algo::community::{label_propagation, louvain, modularity};
+    use std::collections::HashMap;
+
+    // Three 4-cliques joined in a ring by light edges, and a weighted
+    // variant where one ring edge is heavy.
+    let mut g = UnGraph::<(), f64>::new_undirected();
+    let nodes: Vec<_> = (0..12).map(|_| g.add_node(())).collect();
+    for i in 0..12 {
+        for j in i + 1..12 {
+            if i / 4 == j / 4 {
+                g.add_edge(nodes[i], nodes[j], 1.);
+            }
+        }
+    }
+    g.add_edge(nodes[3], nodes[4], 1.);
+    g.add_edge(nodes[7], nodes[8], 1.);
+    g.add_edge(nodes[11], nodes[0], 1.);
+
+    let planted: HashMap<_, _> = nodes.iter().map(|&n| (n, n.index() / 4)).collect();
+    // 18 edges inside, each clique has a degree sum of 14, out of 2 * 21.
+    let q = modularity(&g, &planted, |e| *e.weight());
+    assert!((q - (18. / 21. - 3. * (14. / 42.) * (14. / 42.))).abs() < 1e-9);
+    let single: HashMap<_, _> = nodes.iter().map(|&n| (n, 0)).collect();
+    assert!(modularity(&g, &single, |e| *e.weight()).abs() < 1e-9);
+
+    let same_partition = |communities: &HashMap<NodeIndex, usize>| {
+        nodes.iter().all(|a| {
+            nodes
+                .iter()
+                .all(|b| (communities[a] == communities[b]) == (a.index() / 4 == b.index() / 4))
+        })
+    };
+
+    let (communities, lq) = louvain(&g, |e| *e.weight());
+    assert!(same_partition(&communities));
+    assert!((lq - q).abs() < 1e-9);
+    assert_eq!(communities.values().max(), Some(&2));
+
+    for seed in 0..10 {
+        let (communities, pq) = label_propagation(&g, seed, |e| *e.weight());
+        assert!((pq - modularity(&g, &communities, |e| *e.weight())).abs() < 1e-9);
+        // Every node is in a community with the largest weight to it.
+        for &a in &nodes {
+            let mut weights = HashMap::new();
+            for e in g.edges(a) {
+                *weights.entry(communities[&e.target()]).or_insert(0.) += *e.weight();
+            }
+            let max = weights.values().cloned().fold(0., f64::max);
+            assert_eq!(weights[&communities[&a]], max);
+        }
+    }
+
+    // A heavy edge pulls the nodes 3 and 4 together.
+    let heavy = g.find_edge(nodes[3], nodes[4]).unwrap();
+    g[heavy] = 20.;
+    let (communities, _) = louvain(&g, |e| *e.weight());
+    assert_eq!(communities[&nodes[3]], communities[&nodes[4]]);
+    assert!(!same_partition(&communities));
+
+    // No edges: every node is a community of its own.
+    let empty = Graph::<(), ()>::from_edges(&[(0, 0), (1, 1)]);
+    let (communities, q) = louvain(&empty, |_| 0.);
+    assert_eq!(communities.len(), 2);
+    assert_ne!(communities[&n(0)], communities[&n(1)]);
+    assert_eq!(q, 0.);
+}
+
+#[test]
+fn test_distance_measures() {
+    use petgraph::algo::distance_measures::{
+        center, diameter, diameter_csr, eccentricity, periphery, radius, weighted_center,
+        weighted_diameter, weighted_eccentricity, weighted_periphery, weighted_radius,
+    };
+    use petgraph::csr::Csr;
+
+    // A triangle 0, 1, 2 with a tail 2 - 3 - 4.
+    let edges = [(0, 1, 1), (1, 2, 1), (2, 0, 1), (2, 3, 4), (3, 4, 1)];
+    let g = UnGraph::<(), u32>::from_edges(&edges);
+    let eccentricities: Vec<_> = (0..5).map(|i| eccentricity(&g, n(i))).collect();
+    assert_eq!(
+        eccentricities,
+        vec![Some(3), Some(3), Some(2), Some(2), Some(3)]
+    );
+    assert_eq!(diameter(&g), Some(3));
+    assert_eq!(radius(&g), Some(2));
+    assert_eq!(center(&g), vec![n(2), n(3)]);
+    assert_eq!(periphery(&g), vec![n(0), n(1), n(4)]);
+
+    let cost = |e: petgraph::graph::EdgeReference<u32>| *e.weight();
+    assert_eq!(weighted_eccentricity(&g, n(2), cost), Some(5));
+    assert_eq!(weighted_eccentricity(&g, n(3), cost), Some(5));
+    assert_eq!(weighted_diameter(&g, cost), Some(6));
+    assert_eq!(weighted_radius(&g, cost), Some(5));
+    assert_eq!(weighted_center(&g, cost), vec![n(2), n(3)]);
+    assert_eq!(weighted_periphery(&g, cost), vec![n(0), n(1), n(4)]);
+
+    // Not strongly connected, then strongly connected.
+    let mut directed = DiGraph::<(), u32>::from_edges(&edges);
+    assert_eq!(eccentricity(&directed, n(0)), Some(4));
+    assert_eq!(eccentricity(&directed, n(4)), None);
+    assert_eq!(diameter(&directed), None);
+    assert_eq!(weighted_radius(&directed, |e| *e.weight()), None);
+    assert!(center(&directed).is_empty());
+    directed.add_edge(n(4), n(0), 1);
+    assert_eq!(diameter(&directed), Some(4));
+    assert_eq!(radius(&directed), Some(2));
+
+    // Removed nodes do not count.
+    let mut stable = StableUnGraph::<(), u32>::from_edges(&edges);
+    stable.remove_node(n(4));
+    assert_eq!(diameter(&stable), Some(2));
+    assert_eq!(center(&stable), vec![n(2)]);
+
+    let empty = UnGraph::<(), ()>::new_undirected();
+    assert_eq!(diameter(&empty), None);
+    assert!(periphery(&empty).is_empty());
+
+    // Paths and cycles with pendant paths, as undirected Csr graphs.
+    for &(cycle, tail) in &[(1, 0), (2, 3), (7, 0), (8, 2), (9, 30), (30, 9)] {
+        let count = cycle + tail;
+        let mut csr = Csr::<(), (), Undirected>::with_nodes(count);
+        let mut g = UnGraph::<(), ()>::new_undirected();
+        for _ in 0..count {
+            g.add_node(());
+        }
+        let mut add = |a: usize, b: usize| {
+            csr.add_edge(a as u32, b as u32, ());
+            g.add_edge(n(a), n(b), ());
+        };
+        for i in 1..cycle {
+            add(i - 1, i);
+        }
+        if cycle > 2 {
+            add(cycle - 1, 0);
+        }
+        for i in cycle..count {
+            add(i - 1, i);
+        }
+        assert_eq!(diameter_csr(&csr), diameter(&g));
+        assert_eq!(diameter_csr(&csr), Some(cycle / 2 + tail));
+    }
+    let mut disconnected = Csr::<(), (), Undirected>::with_nodes(3);
+    disconnected.add_edge(0, 1, ());
+    assert_eq!(diameter_csr(&disconnected), None);
+    assert_eq!(diameter_csr(&Csr::<(), (), Undirected>::new()), None);
+}
+
+#[test]
+fn test_degree() {
+    use petgraph::algo::{degree_assortativity, degree_histogram, degree_sequence};
+    use petgraph::csr::Csr;
+    use petgraph::matrix_graph::MatrixGraph;
+    use petgraph::visit::{Degree, EdgeFiltered};
+
+    // A path 0 -> 1 -> 2 with a self loop at 0 and an edge 2 -> 0.
+    let edges = [(0, 0, 1), (0, 1, 2), (1, 2, 3), (2, 0, 4)];
+
+    let g = DiGraph::<(), u32>::from_edges(&edges);
+    assert_eq!(g.out_degree(n(0)), 2);
+    assert_eq!(g.in_degree(n(0)), 2);
+    assert_eq!(g.degree(n(0)), 4);
+    assert_eq!(g.degree(n(1)), 2);
+    assert_eq!(g.weighted_out_degree(n(0), |e| *e.weight()), 3);
+    assert_eq!(g.weighted_in_degree(n(0), |e| *e.weight()), 5);
+    assert_eq!(g.weighted_degree(n(0), |e| *e.weight() as f64), 8.);
+
+    let rev = Reversed(&g);
+    assert_eq!(rev.weighted_out_degree(n(0), |e| *e.weight()), 5);
+    assert_eq!(rev.in_degree(n(1)), 1);
+
+    let filtered = NodeFiltered::from_fn(&g, |a| a != n(2));
+    assert_eq!(filtered.degree(n(0)), 3);
+    assert_eq!(filtered.degree(n(2)), 0);
+    let filtered = EdgeFiltered::from_fn(&g, |e: pg::graph::EdgeReference<u32>| {
+        e.source() != e.target()
+    });
+    assert_eq!(filtered.degree(n(0)), 2);
+
+    // Self loops count twice in undirected graphs.
+    let u = UnGraph::<(), u32>::from_edges(&edges);
+    assert_eq!(u.out_degree(n(0)), 4);
+    assert_eq!(u.in_degree(n(0)), 4);
+    assert_eq!(u.degree(n(0)), 4);
+    assert_eq!(u.weighted_degree(n(0), |e| *e.weight()), 8);
+    assert_eq!(degree_sequence(&u), vec![4, 2, 2]);
+    assert_eq!(degree_histogram(&u), vec![0, 0, 2, 0, 1]);
+
+    let mut stable = StableGraph::<(), u32>::from_edges(&edges);
+    stable.remove_node(n(1));
+    assert_eq!(stable.degree(n(0)), 3);
+    assert_eq!(degree_sequence(&stable), vec![3, 1]);
+
+    let map = DiGraphMap::<u32, u32>::from_edges(&edges);
+    assert_eq!(map.in_degree(0), 2);
+    assert_eq!(map.weighted_in_degree(0, |e| *e.2), 5);
+    let map = UnGraphMap::<u32, u32>::from_edges(&edges);
+    assert_eq!(map.degree(0), 4);
+
+    let mut csr = Csr::<(), u32>::with_nodes(3);
+    for &(a, b, w) in &edges {
+        csr.add_edge(a, b, w);
+    }
+    assert_eq!(csr.out_degree(0), 2);
+    assert_eq!(Degree::in_degree(&csr, 0), 2);
+    assert_eq!(Degree::weighted_in_degree(&csr, 0, |e| *e.weight()), 5);
+
+    let mut matrix = MatrixGraph::<(), u32>::with_capacity(3);
+    for _ in 0..3 {
+        matrix.add_node(());
+    }
+    for &(a, b, w) in &edges {
+        matrix.add_edge(
+            pg::matrix_graph::node_index(a as usize),
+            pg::matrix_graph::node_index(b as usize),
+            w,
+        );
+    }
+    let a = pg::matrix_graph::node_index(0);
+    assert_eq!(matrix.in_degree(a), 2);
+    assert_eq!(matrix.weighted_in_degree(a, |e| *e.2), 5);
+    assert_eq!(matrix.degree(a), 4);
+
+    // Stars are disassortative, and a regular graph has no assortativity.
+    let star = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+    assert!((degree_assortativity(&star).unwrap() + 1.).abs() < 1e-9);
+    let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    assert!((degree_assortativity(&path).unwrap() + 0.5).abs() < 1e-9);
+    let cycle = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(degree_assortativity(&cycle), None);
+    assert_eq!(
+        degree_assortativity(&UnGraph::<(), ()>::new_undirected()),
+        None
+    );
+}
+
+#[test]
+fn test_frozen_degree() {
+    use petgraph::algo::degree_sequence;
+    use petgraph::graph::Frozen;
+    use petgraph::visit::Degree;
+
+    let g = DiGraph::<(), u32>::from_edges(&[(0, 0, 1), (0, 1, 2), (1, 2, 3), (2, 0, 4)]);
+    let mut r = &g;
+    let frozen = Frozen::new(&mut r);
+    assert_eq!((&frozen).degree(n(0)), 4);
+    assert_eq!((&frozen).weighted_in_degree(n(0), |e| *e.weight()), 5);
+    assert_eq!(degree_sequence(&frozen), degree_sequence(&g));
+}
+
+#[test]
+fn test_link_prediction() {
+    use petgraph::algo::link_prediction::*;
+    use petgraph::csr::Csr;
+
+    // A square 0 - 1 - 2 - 3 - 0 with the diagonal 1 - 3, and a pendant
+    // node 4 at 3. The parallel edge is ignored.
+    let edges = [(0, 1), (1, 2), (2, 3), (3, 0), (1, 3), (3, 4), (0, 1)];
+    let g = UnGraph::<(), ()>::from_edges(&edges);
+    let mut csr = Csr::<(), (), Undirected>::with_nodes(5);
+    for &(a, b) in &edges {
+        csr.add_edge(a, b, ());
+    }
+    let pairs = vec![(n(0), n(2)), (n(0), n(4)), (n(1), n(3))];
+    let csr_pairs = vec![(0, 2), (0, 4), (1, 3)];
+
+    let common = common_neighbors(&g, pairs.clone());
+    assert_eq!(
+        common,
+        vec![(n(0), n(2), 2), (n(0), n(4), 1), (n(1), n(3), 2)]
+    );
+    let common_csr: Vec<_> = common_neighbors_csr(&csr, csr_pairs.clone());
+    assert_eq!(common_csr, vec![(0, 2, 2), (0, 4, 1), (1, 3, 2)]);
+
+    let jaccard: Vec<f64> = jaccard_coefficient(&g, pairs.clone())
+        .into_iter()
+        .map(|(_, _, s)| s)
+        .collect();
+    assert_eq!(jaccard, vec![1., 0.5, 0.4]);
+    let jaccard_csr: Vec<f64> = jaccard_coefficient_csr(&csr, csr_pairs.clone())
+        .into_iter()
+        .map(|(_, _, s)| s)
+        .collect();
+    assert_eq!(jaccard_csr, jaccard);
+
+    let adamic_adar = adamic_adar_index(&g, pairs.clone());
+    let expected = [
+        1. / 3f64.ln() + 1. / 4f64.ln(),
+        1. / 4f64.ln(),
+        2. / 2f64.ln(),
+    ];
+    for (&(_, _, s), e) in adamic_adar.iter().zip(&expected) {
+        assert!((s - e).abs() < 1e-12);
+    }
+    let adamic_adar_csr = adamic_adar_index_csr(&csr, csr_pairs.clone());
+    for (&(_, _, s), e) in adamic_adar_csr.iter().zip(&expected) {
+        assert!((s - e).abs() < 1e-12);
+    }
+
+    let allocation = resource_allocation_index(&g, pairs.clone());
+    assert_eq!(allocation[0].2, 1. / 3. + 1. / 4.);
+    assert_eq!(allocation[1].2, 1. / 4.);
+    assert_eq!(
+        resource_allocation_index_csr(&csr, csr_pairs.clone())[2].2,
+        1.
+    );
+
+    let attachment = preferential_attachment(&g, pairs);
+    assert_eq!(attachment[2], (n(1), n(3), 12));
+    assert_eq!(preferential_attachment_csr(&csr, csr_pairs)[1], (0, 4, 2));
+
+    // SimRank on a directed graph: 1 and 2 are both referenced by 0 only,
+    // and 4 is referenced by both of them. Node 3 is removed.
+    let mut d = StableGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 4), (2, 4), (3, 0)]);
+    d.remove_node(n(3));
+    let sim = simrank(&d, 0.6, 1e-12, 100);
+    assert_eq!(sim.len(), 5);
+    assert_eq!(sim[1][2], 0.6);
+    assert_eq!(sim[2][1], 0.6);
+    assert_eq!(sim[4][4], 1.);
+    assert_eq!(sim[0][4], 0.);
+    assert_eq!(sim[3][3], 0.);
+    // s(4, 1) = 0.6 / 2 * (s(1, 0) + s(2, 0)) = 0.
+    assert_eq!(sim[4][1], 0.);
+
+    // Undirected: the similarity is symmetric, and a fixed point.
+    let sim = simrank(&g, 0.8, 1e-12, 1000);
+    let neighbors: Vec<HashSet<usize>> = (0..5)
+        .map(|a| g.neighbors(n(a)).map(|b| b.index()).collect())
+        .collect();
+    for a in 0..5 {
+        for b in 0..5 {
+            assert!((sim[a][b] - sim[b][a]).abs() < 1e-9);
+            if a != b {
+                let mut total = 0.;
+                for &i in &neighbors[a] {
+                    for &j in &neighbors[b] {
+                        total += sim[i][j];
+                    }
+                }
+                let expected = 0.8 * total / (neighbors[a].len() * neighbors[b].len()) as f64;
+                assert!((sim[a][b] - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_graph_edit_distance() {
+    use petgraph::algo::{approximate_graph_edit_distance, graph_edit_distance, EditOperation};
+    use petgraph::graphmap::DiGraphMap;
+
+    let unit = |a: Option<&()>, b: Option<&()>| if a.is_some() && b.is_some() { 0. } else { 1. };
+
+    // Closing a path of four nodes to a cycle inserts one edge.
+    let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    let cycle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    let (cost, ops) = graph_edit_distance(&path, &cycle, unit, unit);
+    assert_eq!(cost, 1.);
+    assert_eq!(ops.len(), 4 + 4);
+    assert_eq!(
+        ops.iter()
+            .filter(|op| match op {
+                EditOperation::EdgeInsertion(_) => true,
+                _ => false,
+            })
+            .count(),
+        1
+    );
+    let (cost, _) = graph_edit_distance(&cycle, &path, unit, unit);
+    assert_eq!(cost, 1.);
+    assert!(approximate_graph_edit_distance(&path, &cycle, unit, unit).0 >= 1.);
+
+    // Every node and edge of one graph is inserted into the empty graph.
+    let empty = UnGraph::<(), ()>::default();
+    let (cost, ops) = graph_edit_distance(&empty, &cycle, unit, unit);
+    assert_eq!(cost, 8.);
+    assert_eq!(ops[0], EditOperation::NodeInsertion(n(0)));
+    assert_eq!(ops[4], EditOperation::EdgeInsertion(EdgeIndex::new(0)));
+    let (cost, ops) = approximate_graph_edit_distance(&cycle, &empty, unit, unit);
+    assert_eq!(cost, 8.);
+    assert_eq!(ops[7], EditOperation::EdgeDeletion(EdgeIndex::new(3)));
+
+    // One of two parallel edges and the self loop are deleted.
+    let multi = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 1), (1, 1)]);
+    let single = UnGraph::<(), ()>::from_edges(&[(0, 1)]);
+    assert_eq!(graph_edit_distance(&multi, &single, unit, unit).0, 2.);
+
+    // Isomorphic graphs with distinct weights are at distance zero, also
+    // for the approximation; relabeling a node costs its weight difference.
+    let weights = |a: Option<&u32>, b: Option<&u32>| match (a, b) {
+        (Some(a), Some(b)) => (*a as f64 - *b as f64).abs(),
+        _ => 10.,
+    };
+    let edges = |a: Option<&()>, b: Option<&()>| if a.is_some() && b.is_some() { 0. } else { 3. };
+    let mut g = StableGraph::<u32, ()>::new();
+    let a = g.add_node(1);
+    let hole = g.add_node(0);
+    let b = g.add_node(2);
+    let c = g.add_node(3);
+    g.extend_with_edges(&[(a, b), (b, c), (c, a), (a, c)]);
+    g.remove_node(hole);
+    let mut h = DiGraphMap::<u32, ()>::new();
+    for &(x, y) in &[(3, 1), (1, 3), (2, 3), (1, 2)] {
+        h.add_edge(x, y, ());
+    }
+    let (cost, ops) = graph_edit_distance(&g, &h, weights, edges);
+    assert_eq!(cost, 0.);
+    assert!(ops.contains(&EditOperation::NodeSubstitution(c, 3)));
+    assert!(ops.contains(&EditOperation::EdgeSubstitution(
+        g.find_edge(c, a).unwrap(),
+        (3, 1)
+    )));
+    assert_eq!(
+        approximate_graph_edit_distance(&g, &h, weights, edges).0,
+        0.
+    );
+
+    let mut g2 = g.clone();
+    g2[c] = 5;
+    assert_eq!(graph_edit_distance(&g, &g2, weights, edges).0, 2.);
+    // Without c -> a, and with a -> b reversed, it is cheaper to swap the
+    // weights of a and b and delete c -> b than to edit three edges.
+    let mut g3 = g.clone();
+    let ca = g3.find_edge(c, a).unwrap();
+    g3.remove_edge(ca);
+    let ab = g3.find_edge(a, b).unwrap();
+    g3.remove_edge(ab);
+    g3.add_edge(b, a, ());
+    assert_eq!(graph_edit_distance(&g, &g3, weights, edges).0, 5.);
+}
+
+#[test]
+fn test_tree_isomorphism() {
+    use petgraph::algo::tree::*;
+    use petgraph::csr::Csr;
+    use petgraph::graphmap::UnGraphMap;
+
+    // A root with two children, each with two leaves, numbered differently.
+    let a = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (2, 6)]);
+    let b = UnGraph::<(), ()>::from_edges(&[(6, 5), (5, 4), (5, 3), (6, 2), (2, 1), (2, 0)]);
+    assert_eq!(tree_center(&a), Ok(vec![n(0)]));
+    assert_eq!(tree_center(&b), Ok(vec![n(6)]));
+    assert_eq!(
+        rooted_tree_encoding(&a, n(0)),
+        Ok("((()())(()()))".to_string())
+    );
+    assert_eq!(
+        rooted_tree_encoding(&b, n(6)),
+        rooted_tree_encoding(&a, n(0))
+    );
+    assert_eq!(tree_encoding(&a), rooted_tree_encoding(&a, n(0)));
+    assert_eq!(is_isomorphic_tree(&a, &b), Ok(true));
+    assert_eq!(is_isomorphic_rooted_tree(&a, n(3), &b, n(0)), Ok(true));
+    assert_eq!(is_isomorphic_rooted_tree(&a, n(3), &b, n(2)), Ok(false));
+
+    // Moving a leaf changes the shape, but not the number of nodes.
+    let c = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (5, 6)]);
+    assert_eq!(is_isomorphic_tree(&a, &c), Ok(false));
+    assert_eq!(tree_center(&c), Ok(vec![n(0), n(2)]));
+
+    // Other graph types: a path of four nodes with two centers.
+    let mut csr = Csr::<(), (), Undirected>::with_nodes(4);
+    for &(x, y) in &[(0, 1), (1, 2), (2, 3)] {
+        csr.add_edge(x, y, ());
+    }
+    let map = UnGraphMap::<u8, ()>::from_edges(&[(7, 3), (3, 5), (5, 1)]);
+    let mut stable = StableGraph::<(), ()>::from_edges(&[(0, 1), (2, 1), (2, 3), (3, 4)]);
+    stable.remove_node(n(0));
+    assert_eq!(tree_center(&csr), Ok(vec![1, 2]));
+    assert_eq!(tree_center(&map), Ok(vec![3, 5]));
+    assert_eq!(tree_center(&stable), Ok(vec![n(2), n(3)]));
+    assert_eq!(tree_encoding(&csr), Ok("(()(()))".to_string()));
+    assert_eq!(tree_encoding(&map), tree_encoding(&csr));
+    assert_eq!(is_isomorphic_tree(&csr, &stable), Ok(true));
+
+    // Not trees.
+    let empty = UnGraph::<(), ()>::default();
+    let forest = UnGraph::<(), ()>::from_edges(&[(0, 1), (2, 3)]);
+    let cycle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let parallel = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 0)]);
+    let self_loop = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 1)]);
+    assert_eq!(tree_center(&empty), Err(NotATree::Empty));
+    assert_eq!(tree_encoding(&forest), Err(NotATree::Disconnected));
+    assert_eq!(tree_center(&cycle), Err(NotATree::Cycle));
+    assert_eq!(tree_center(&parallel), Err(NotATree::Cycle));
+    assert_eq!(is_isomorphic_tree(&a, &self_loop), Err(NotATree::Cycle));
+    assert_eq!(rooted_tree_encoding(&self_loop, n(0)), Err(NotATree::Cycle));
+}
@@ -7,7 +7,11 @@ use petgraph::graph::{edge_index, node_index};
 use petgraph::prelude::*;
 use petgraph::EdgeType;
 
-use petgraph::algo::{is_isomorphic, is_isomorphic_matching};
+use petgraph::algo::{
+    automorphisms, canonical_labeling, is_isomorphic, is_isomorphic_matching,
+    is_isomorphic_subgraph, is_isomorphic_subgraph_matching, isomorphisms, subgraph_isomorphisms,
+    subgraph_monomorphisms, wl_hash,
+};
 
 /// Petersen A and B are isomorphic
 ///
@@ -485,6 +489,396 @@ fn iso_multigraph_failure() {
     assert!(!is_isomorphic(&g0, &g1));
 }
 
+#[test]
+fn iso_parallel_edge_count() {
+    use petgraph::csr::Csr;
+
+    let g0 = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 1)]);
+    let g1 = UnGraph::<(), ()>::from_edges(&[(0, 1)]);
+    assert!(!is_isomorphic(&g0, &g1));
+    assert!(!is_isomorphic_matching(&g0, &g1, |_, _| true, |_, _| true));
+    assert_eq!(isomorphisms(&g0, &g1, |_, _| true, |_, _| true).count(), 0);
+    assert!(is_isomorphic(&g0, &g0));
+
+    let g0 = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (1, 2)]);
+    let g1 = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+    assert!(!is_isomorphic(&g0, &g1));
+
+    // An undirected `Csr` has the same number of edges as the other graphs.
+    let g0 = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 1)]);
+    let mut csr = Csr::<(), (), Undirected>::with_nodes(2);
+    csr.add_edge(1, 0, ());
+    csr.add_edge(0, 0, ());
+    assert!(is_isomorphic(&g0, &csr));
+    let g1 = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 1), (1, 1)]);
+    assert!(!is_isomorphic(&g1, &csr));
+}
+
+#[test]
+fn subgraph_petersen() {
+    let petersen = str_to_graph(PETERSEN_A);
+    let c4 = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    let c5 = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+    let claw = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+
+    // The Petersen graph has girth 5, with 12 cycles of length 5,
+    // each of which is matched in 10 ways.
+    assert!(!is_isomorphic_subgraph(&c4, &petersen));
+    assert!(is_isomorphic_subgraph(&c5, &petersen));
+    assert_eq!(
+        subgraph_monomorphisms(&c4, &petersen, |_, _| true, |_, _| true).count(),
+        0
+    );
+    let mappings: Vec<_> =
+        subgraph_isomorphisms(&c5, &petersen, |_, _| true, |_, _| true).collect();
+    assert_eq!(mappings.len(), 120);
+    for mapping in &mappings {
+        for edge in c5.edge_indices() {
+            let (a, b) = c5.edge_endpoints(edge).unwrap();
+            assert!(petersen
+                .find_edge(mapping[a.index()], mapping[b.index()])
+                .is_some());
+        }
+    }
+    assert_eq!(
+        subgraph_monomorphisms(&c5, &petersen, |_, _| true, |_, _| true).count(),
+        120
+    );
+    // Each node is the center of a claw, with its leaves in any order.
+    assert_eq!(
+        subgraph_isomorphisms(&claw, &petersen, |_, _| true, |_, _| true).count(),
+        60
+    );
+    // A graph is a subgraph of itself.
+    assert!(is_isomorphic_subgraph(&petersen, &str_to_graph(PETERSEN_B)));
+    assert!(!is_isomorphic_subgraph(&petersen, &c5));
+}
+
+#[test]
+fn subgraph_induced() {
+    let pattern = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+    let triangle = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+    let cycle = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+
+    // The path is not an induced subgraph of the transitive triangle.
+    assert!(!is_isomorphic_subgraph(&pattern, &triangle));
+    let mono: Vec<_> =
+        subgraph_monomorphisms(&pattern, &triangle, |_, _| true, |_, _| true).collect();
+    assert_eq!(
+        mono,
+        vec![vec![node_index(0), node_index(1), node_index(2)]]
+    );
+
+    // It is matched by every rotation of the directed cycle, without being
+    // induced.
+    assert!(!is_isomorphic_subgraph(&pattern, &cycle));
+    assert_eq!(
+        subgraph_monomorphisms(&pattern, &cycle, |_, _| true, |_, _| true).count(),
+        3
+    );
+
+    // Self loops must match in both directions for induced subgraphs.
+    let mut looped = triangle.clone();
+    looped.add_edge(node_index(1), node_index(1), ());
+    looped.remove_edge(looped.find_edge(node_index(0), node_index(2)).unwrap());
+    assert!(!is_isomorphic_subgraph(&pattern, &looped));
+    assert_eq!(
+        subgraph_monomorphisms(&pattern, &looped, |_, _| true, |_, _| true).count(),
+        1
+    );
+
+    // The empty graph has one mapping into any graph.
+    let empty = Graph::<(), ()>::new();
+    assert_eq!(
+        subgraph_isomorphisms(&empty, &cycle, |_, _| true, |_, _| true).count(),
+        1
+    );
+    assert_eq!(
+        subgraph_isomorphisms(&cycle, &empty, |_, _| true, |_, _| true).count(),
+        0
+    );
+}
+
+#[test]
+fn subgraph_matching() {
+    // A labeled path a -1-> b -2-> c.
+    let mut pattern = Graph::<&str, u32>::new();
+    let a = pattern.add_node("a");
+    let b = pattern.add_node("b");
+    let c = pattern.add_node("c");
+    pattern.add_edge(a, b, 1);
+    pattern.add_edge(b, c, 2);
+
+    let mut target = Graph::<&str, u32>::new();
+    let t0 = target.add_node("c");
+    let t1 = target.add_node("b");
+    let t2 = target.add_node("a");
+    let t3 = target.add_node("b");
+    target.add_edge(t2, t1, 1);
+    target.add_edge(t1, t0, 3);
+    target.add_edge(t2, t3, 1);
+    target.add_edge(t3, t0, 2);
+
+    assert!(is_isomorphic_subgraph(&pattern, &target));
+    assert!(is_isomorphic_subgraph_matching(
+        &pattern,
+        &target,
+        |x, y| x == y,
+        |x, y| x == y
+    ));
+    let matches: Vec<_> =
+        subgraph_isomorphisms(&pattern, &target, |x, y| x == y, |x, y| x == y).collect();
+    assert_eq!(matches, vec![vec![t2, t3, t0]]);
+    let matches = subgraph_monomorphisms(&pattern, &target, |x, y| x == y, |_, _| true);
+    assert_eq!(matches.count(), 2);
+    // The weights of the pattern are passed first.
+    assert!(!is_isomorphic_subgraph_matching(
+        &pattern,
+        &target,
+        |_, _| true,
+        |x, y| x > y
+    ));
+}
+
+#[test]
+fn iso_graph_types() {
+    use petgraph::csr::Csr;
+    use petgraph::graphmap::UnGraphMap;
+    use petgraph::stable_graph::StableUnGraph;
+
+    let petersen = str_to_graph(PETERSEN_A);
+    let petersen_b = str_to_graph(PETERSEN_B);
+
+    // A StableGraph with holes in its node indices.
+    let mut stable = StableUnGraph::<u32, ()>::default();
+    let extra = stable.add_node(10);
+    let nodes: Vec<_> = petersen_b
+        .node_indices()
+        .map(|n| {
+            let node = stable.add_node(n.index() as u32);
+            stable.add_node(11);
+            node
+        })
+        .collect();
+    stable.remove_node(extra);
+    stable.retain_nodes(|g, n| g[n] < 10);
+    for edge in petersen_b.edge_references() {
+        stable.add_edge(
+            nodes[edge.source().index()],
+            nodes[edge.target().index()],
+            (),
+        );
+    }
+
+    // A GraphMap with relabeled nodes.
+    let map = UnGraphMap::<u32, ()>::from_edges(petersen_b.edge_references().map(|e| {
+        (
+            3 * e.source().index() as u32 + 1,
+            3 * e.target().index() as u32 + 1,
+        )
+    }));
+
+    let mut csr = Csr::<(), (), Undirected>::with_nodes(10);
+    for edge in petersen_b.edge_references() {
+        csr.add_edge(
+            edge.source().index() as u32,
+            edge.target().index() as u32,
+            (),
+        );
+    }
+
+    assert!(is_isomorphic(&petersen, &stable));
+    assert!(is_isomorphic(&map, &petersen));
+    assert!(is_isomorphic(&stable, &csr));
+    assert!(is_isomorphic(&csr, &map));
+    assert!(!is_isomorphic(&csr, &str_to_graph(FULL_A)));
+
+    // Match node weights between graph types.
+    assert!(is_isomorphic_matching(
+        &stable,
+        &petersen_b,
+        |&x, _| x < 10,
+        |_, _| true
+    ));
+    let mut csr_weights = Csr::<u32, (), Undirected>::with_nodes(10);
+    for i in 0..10 {
+        csr_weights[i] = i as u32;
+    }
+    for edge in petersen_b.edge_references() {
+        csr_weights.add_edge(
+            edge.source().index() as u32,
+            edge.target().index() as u32,
+            (),
+        );
+    }
+    let mappings: Vec<_> =
+        subgraph_isomorphisms(&csr_weights, &stable, |x, y| x == y, |_, _| true).collect();
+    assert_eq!(mappings, vec![nodes]);
+    assert!(is_isomorphic_matching(
+        &csr_weights,
+        &stable,
+        |x, y| x == y,
+        |_, _| true
+    ));
+}
+
+#[test]
+fn iso_all_mappings() {
+    let petersen_a = str_to_graph(PETERSEN_A);
+    let petersen_b = str_to_graph(PETERSEN_B);
+
+    // The Petersen graph has 120 automorphisms, and so as many isomorphisms
+    // to any graph that is isomorphic to it.
+    let mappings: Vec<_> =
+        isomorphisms(&petersen_a, &petersen_b, |_, _| true, |_, _| true).collect();
+    assert_eq!(mappings.len(), 120);
+    for mapping in &mappings {
+        for edge in petersen_a.edge_references() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            assert!(petersen_b.find_edge(mapping[a], mapping[b]).is_some());
+        }
+    }
+    assert_eq!(
+        isomorphisms(&petersen_a, &str_to_graph(FULL_A), |_, _| true, |_, _| true).count(),
+        0
+    );
+
+    // A directed 4-cycle only has its rotations.
+    let cycle = Graph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(
+        isomorphisms(&cycle, &cycle, |_, _| true, |_, _| true).count(),
+        4
+    );
+    let mut weighted = cycle.clone();
+    weighted[node_index(2)] = 1;
+    let mappings: Vec<_> = isomorphisms(&weighted, &weighted, |x, y| x == y, |_, _| true).collect();
+    assert_eq!(mappings, vec![weighted.node_indices().collect::<Vec<_>>()]);
+}
+
+#[test]
+fn automorphism_orbits() {
+    // Vertex-transitive graphs have a single orbit.
+    let petersen = str_to_graph(PETERSEN_A);
+    assert_eq!(
+        automorphisms(&petersen),
+        vec![petersen.node_indices().collect::<Vec<_>>()]
+    );
+    let coxeter = str_to_graph(COXETER_A);
+    assert_eq!(automorphisms(&coxeter).len(), 1);
+
+    // A path of 5 nodes is symmetric around its center.
+    let path = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let n = node_index::<u32>;
+    assert_eq!(
+        automorphisms(&path),
+        vec![vec![n(0), n(4)], vec![n(1), n(3)], vec![n(2)]]
+    );
+
+    // Directed edges break the symmetry.
+    let directed = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    assert_eq!(
+        automorphisms(&directed),
+        vec![vec![n(0)], vec![n(1)], vec![n(2)], vec![n(3)], vec![n(4)]]
+    );
+
+    // Two triangles joined by an edge, and an isolated node.
+    let mut g = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (0, 3),
+    ]);
+    g.add_node(());
+    assert_eq!(
+        automorphisms(&g),
+        vec![vec![n(0), n(3)], vec![n(1), n(2), n(4), n(5)], vec![n(6)]]
+    );
+    assert_eq!(automorphisms(&Graph::<(), ()>::new()), Vec::<Vec<_>>::new());
+}
+
+#[test]
+fn graph_hashes() {
+    let petersen_a = str_to_graph(PETERSEN_A);
+    let petersen_b = str_to_graph(PETERSEN_B);
+    let coxeter_a = str_to_graph(COXETER_A);
+    let coxeter_b = str_to_graph(COXETER_B);
+    let wl = |g: &Graph<(), (), Undirected>| wl_hash(g, 3, |_| (), |_| ());
+    let canonical = |g: &Graph<(), (), Undirected>| canonical_labeling(g, |_| (), |_| ()).1;
+
+    assert_eq!(wl(&petersen_a), wl(&petersen_b));
+    assert_eq!(canonical(&petersen_a), canonical(&petersen_b));
+    assert_eq!(wl(&coxeter_a), wl(&coxeter_b));
+    assert_eq!(canonical(&coxeter_a), canonical(&coxeter_b));
+    assert_ne!(canonical(&petersen_a), canonical(&str_to_graph(FULL_A)));
+
+    // The prism and K3,3 are both 3-regular on 6 nodes, which the
+    // Weisfeiler–Lehman hash does not tell apart.
+    let prism = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (0, 3),
+        (1, 4),
+        (2, 5),
+    ]);
+    let k33 =
+        Graph::<(), (), Undirected>::from_edges((0..3).flat_map(|i| (3..6).map(move |j| (i, j))));
+    assert_eq!(wl(&prism), wl(&k33));
+    assert_ne!(canonical(&prism), canonical(&k33));
+
+    // The hashes are stable, so they can be stored.
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+    assert_eq!(wl(&g), 12804601264549920350);
+    assert_eq!(canonical(&g), 16135572299321148092);
+}
+
+#[test]
+fn canonical_labels() {
+    // Directed cycles a -> b -> c -> a with one edge labeled differently.
+    let mut g0 = Graph::<&str, u32>::new();
+    let mut g1 = Graph::<&str, u32>::new();
+    for &label in &["a", "b", "c"] {
+        g0.add_node(label);
+    }
+    for &label in &["c", "a", "b"] {
+        g1.add_node(label);
+    }
+    let n = node_index::<u32>;
+    g0.extend_with_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, 2)]);
+    g1.extend_with_edges(&[(1, 2, 1), (2, 0, 1), (0, 1, 2)]);
+
+    let (order0, hash0) = canonical_labeling(&g0, |n| *n.1, |e| *e.weight());
+    let (order1, hash1) = canonical_labeling(&g1, |n| *n.1, |e| *e.weight());
+    assert_eq!(hash0, hash1);
+    assert_eq!(order0, vec![n(0), n(1), n(2)]);
+    assert_eq!(order1, vec![n(1), n(2), n(0)]);
+    assert_eq!(
+        wl_hash(&g0, 2, |n| *n.1, |e| *e.weight()),
+        wl_hash(&g1, 2, |n| *n.1, |e| *e.weight())
+    );
+
+    // Changing the labels or the direction of an edge changes the hashes.
+    g1[edge_index(0)] = 2;
+    assert_ne!(canonical_labeling(&g1, |n| *n.1, |e| *e.weight()).1, hash0);
+    assert_eq!(
+        canonical_labeling(&g1, |n| *n.1, |_| ()).1,
+        canonical_labeling(&g0, |n| *n.1, |_| ()).1
+    );
+    let mut g2 = g0.clone();
+    g2.reverse();
+    assert_ne!(canonical_labeling(&g2, |n| *n.1, |e| *e.weight()).1, hash0);
+    assert_ne!(
+        wl_hash(&g2, 2, |n| *n.1, |e| *e.weight()),
+        wl_hash(&g0, 2, |n| *n.1, |e| *e.weight())
+    );
+}
+
 /// Isomorphic pair
 const COXETER_A: &str = "
  0 1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0 0 0 0 0 0 0 1 
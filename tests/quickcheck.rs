@@ -82,7 +82,7 @@ quickcheck! {
     fn reverse_undirected(g: Small<UnGraph<(), ()>>) -> bool {
         let mut h = (*g).clone();
         h.reverse();
-        is_isomorphic(&g, &h)
+        is_isomorphic(&*g, &h)
     }
 }
 
@@ -274,10 +274,10 @@ fn isomorphism_1() {
                 ng.add_edge(map[s.index()], map[t.index()], g[i]);
             }
             if g.node_count() < 20 && g.edge_count() < 50 {
-                assert!(is_isomorphic(&g, &ng));
+                assert!(is_isomorphic(&*g, &ng));
             }
             assert!(is_isomorphic_matching(
-                &g,
+                &*g,
                 &ng,
                 PartialEq::eq,
                 PartialEq::eq
@@ -305,14 +305,14 @@ fn isomorphism_modify() {
         }
         if i.index() < g.node_count() || j.index() < g.edge_count() {
             assert!(!is_isomorphic_matching(
-                &g,
+                &*g,
                 &ng,
                 PartialEq::eq,
                 PartialEq::eq
             ));
         } else {
             assert!(is_isomorphic_matching(
-                &g,
+                &*g,
                 &ng,
                 PartialEq::eq,
                 PartialEq::eq
@@ -806,6 +806,34 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn dominators_lengauer_tarjan_agrees(gr: Graph<(), ()>, node: usize) -> bool {
+        use petgraph::algo::dominators::{lengauer_tarjan, simple_fast};
+        if gr.node_count() == 0 {
+            return true;
+        }
+        let root = node_index(node % gr.node_count());
+        let lt = lengauer_tarjan(&gr, root);
+        let sf = simple_fast(&gr, root);
+        gr.node_indices()
+            .all(|n| lt.immediate_dominator(n) == sf.immediate_dominator(n))
+    }
+}
+
+quickcheck! {
+    fn colorings_are_proper(gr: Small<Graph<(), ()>>) -> bool {
+        use petgraph::algo::coloring::*;
+        let (exact, chromatic) = exact_coloring(&*gr);
+        let mut valid = is_proper_coloring(&*gr, &exact);
+        for &ordering in &[NodeOrdering::Natural, NodeOrdering::LargestFirst, NodeOrdering::SmallestLast] {
+            let (colors, count) = greedy_coloring(&*gr, ordering);
+            valid &= is_proper_coloring(&*gr, &colors) && count >= chromatic;
+        }
+        let (colors, count) = dsatur_coloring(&*gr);
+        valid && is_proper_coloring(&*gr, &colors) && count >= chromatic
+    }
+}
+
 quickcheck! {
     fn test_bellman_ford(gr: Graph<(), f32>) -> bool {
         let mut gr = gr;